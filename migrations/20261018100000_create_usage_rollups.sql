@@ -0,0 +1,48 @@
+-- Store the exact moment of each request, so that usage can be rolled up per hour
+ALTER TABLE usage
+    ALTER COLUMN creation_date TYPE TIMESTAMP WITH TIME ZONE USING creation_date::timestamptz,
+    ALTER COLUMN creation_date SET DEFAULT NOW();
+
+CREATE INDEX IF NOT EXISTS idx_usage_user_id_creation_date ON usage (user_id, creation_date);
+CREATE INDEX IF NOT EXISTS idx_usage_creation_date ON usage (creation_date);
+
+-- Hourly aggregates of the raw usage rows
+CREATE TABLE IF NOT EXISTS usage_hourly (
+    user_id UUID NOT NULL REFERENCES users(id),
+    bucket TIMESTAMP WITH TIME ZONE NOT NULL,  -- Start of the hour
+    endpoint VARCHAR(255) NOT NULL,
+    count BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (user_id, bucket, endpoint)
+);
+
+CREATE INDEX IF NOT EXISTS idx_usage_hourly_bucket ON usage_hourly (bucket);
+
+-- Daily aggregates, derived from the hourly aggregates
+CREATE TABLE IF NOT EXISTS usage_daily (
+    user_id UUID NOT NULL REFERENCES users(id),
+    day DATE NOT NULL,
+    endpoint VARCHAR(255) NOT NULL,
+    count BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (user_id, day, endpoint)
+);
+
+CREATE INDEX IF NOT EXISTS idx_usage_daily_day ON usage_daily (day);
+
+-- Raw usage rows that passed the retention period (only used when USAGE_RETENTION_MODE=archive)
+CREATE TABLE IF NOT EXISTS usage_archive (
+    id UUID PRIMARY KEY,
+    endpoint VARCHAR(255) NOT NULL,
+    user_id UUID NOT NULL,
+    creation_date TIMESTAMP WITH TIME ZONE NOT NULL,
+    archived_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+-- Single row keeping track of how far the raw usage has been rolled up
+CREATE TABLE IF NOT EXISTS usage_rollup_state (
+    id BOOLEAN PRIMARY KEY DEFAULT TRUE CHECK (id),
+    rolled_up_until TIMESTAMP WITH TIME ZONE NOT NULL
+);
+
+INSERT INTO usage_rollup_state (id, rolled_up_until)
+VALUES (TRUE, COALESCE((SELECT date_trunc('hour', MIN(creation_date)) FROM usage), date_trunc('hour', NOW())))
+ON CONFLICT (id) DO NOTHING;
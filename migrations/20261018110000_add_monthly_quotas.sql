@@ -0,0 +1,12 @@
+-- Optional monthly quota per tier (NULL means no monthly quota)
+ALTER TABLE tiers
+    ADD COLUMN IF NOT EXISTS requests_per_month INT;
+
+-- Keeps track of the quota threshold emails that have been sent per period
+CREATE TABLE IF NOT EXISTS usage_quota_notifications (
+    user_id UUID NOT NULL REFERENCES users(id),
+    period_start DATE NOT NULL,  -- First day of the month the notification belongs to
+    threshold INT NOT NULL,  -- Percentage of the monthly quota
+    sent_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, period_start, threshold)
+);
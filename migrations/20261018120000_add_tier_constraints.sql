@@ -0,0 +1,6 @@
+-- Tiers are managed through the API, so guard the invariants the rate limiter relies on
+ALTER TABLE tiers
+    ADD CONSTRAINT unique_level UNIQUE (level),
+    ADD CONSTRAINT positive_level CHECK (level > 0),
+    ADD CONSTRAINT non_negative_requests_per_day CHECK (requests_per_day >= 0),
+    ADD CONSTRAINT non_negative_requests_per_month CHECK (requests_per_month IS NULL OR requests_per_month >= 0);
@@ -0,0 +1,49 @@
+-- Prices per tier. A price applies to every period starting on or after valid_from, until a newer
+-- price replaces it. Prices are never updated in place, so that statements can be reproduced.
+CREATE TABLE IF NOT EXISTS billing_prices (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tier_id UUID NOT NULL REFERENCES tiers(id) ON DELETE CASCADE,
+    valid_from DATE NOT NULL,
+    currency CHAR(3) NOT NULL DEFAULT 'EUR',
+    base_fee_cents BIGINT NOT NULL CHECK (base_fee_cents >= 0),
+    included_requests BIGINT NOT NULL CHECK (included_requests >= 0),
+    overage_cents_per_1000 BIGINT NOT NULL CHECK (overage_cents_per_1000 >= 0),  -- Price per 1000 requests above the included amount
+    creation_date TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    CONSTRAINT unique_tier_valid_from UNIQUE (tier_id, valid_from)
+);
+
+INSERT INTO billing_prices (tier_id, valid_from, base_fee_cents, included_requests, overage_cents_per_1000)
+SELECT id, '2025-01-01', 0, 10000, 50 FROM tiers WHERE name = 'Low'
+ON CONFLICT (tier_id, valid_from) DO NOTHING;
+
+INSERT INTO billing_prices (tier_id, valid_from, base_fee_cents, included_requests, overage_cents_per_1000)
+SELECT id, '2025-01-01', 1900, 100000, 40 FROM tiers WHERE name = 'Medium'
+ON CONFLICT (tier_id, valid_from) DO NOTHING;
+
+INSERT INTO billing_prices (tier_id, valid_from, base_fee_cents, included_requests, overage_cents_per_1000)
+SELECT id, '2025-01-01', 4900, 250000, 30 FROM tiers WHERE name = 'Max'
+ON CONFLICT (tier_id, valid_from) DO NOTHING;
+
+-- Monthly statements. Every input of the calculation is copied into the statement, so that it stays
+-- the same when tiers or prices change afterwards. One statement per user and period.
+CREATE TABLE IF NOT EXISTS billing_statements (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id),
+    period_start DATE NOT NULL,  -- First day of the month
+    period_end DATE NOT NULL,    -- First day of the next month (exclusive)
+    price_id UUID REFERENCES billing_prices(id) ON DELETE SET NULL,
+    tier_level INT NOT NULL,
+    tier_name VARCHAR(255) NOT NULL,
+    currency CHAR(3) NOT NULL,
+    base_fee_cents BIGINT NOT NULL,
+    included_requests BIGINT NOT NULL,
+    overage_cents_per_1000 BIGINT NOT NULL,
+    request_count BIGINT NOT NULL,
+    overage_requests BIGINT NOT NULL,
+    overage_cents BIGINT NOT NULL,
+    total_cents BIGINT NOT NULL,
+    generated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    CONSTRAINT unique_user_period UNIQUE (user_id, period_start)
+);
+
+CREATE INDEX IF NOT EXISTS idx_billing_statements_period_start ON billing_statements (period_start);
@@ -0,0 +1,6 @@
+-- Support the filters and (cursor) pagination of the user list
+CREATE INDEX IF NOT EXISTS idx_users_creation_date_id ON users (creation_date, id);
+CREATE INDEX IF NOT EXISTS idx_users_status ON users (status);
+CREATE INDEX IF NOT EXISTS idx_users_role_level ON users (role_level);
+CREATE INDEX IF NOT EXISTS idx_users_tier_level ON users (tier_level);
+CREATE INDEX IF NOT EXISTS idx_users_country_code ON users (country_code);
@@ -0,0 +1,10 @@
+-- Trigram and full-text indexes for the admin user search
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX IF NOT EXISTS idx_users_username_trgm ON users USING GIN (username gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_users_email_trgm ON users USING GIN (email gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_users_full_name_trgm ON users USING GIN ((COALESCE(first_name, '') || ' ' || COALESCE(last_name, '')) gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_users_description_trgm ON users USING GIN (description gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_users_search_tsv ON users USING GIN (
+    to_tsvector('simple', username || ' ' || email || ' ' || COALESCE(first_name, '') || ' ' || COALESCE(last_name, '') || ' ' || COALESCE(description, ''))
+);
@@ -0,0 +1,42 @@
+-- Deleted users are kept during a grace period (status 'deleted'), after which they are purged
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMP WITH TIME ZONE,
+    ADD COLUMN IF NOT EXISTS status_before_deletion TEXT,       -- Status to go back to when the user is restored
+    ADD COLUMN IF NOT EXISTS purge_after TIMESTAMP WITH TIME ZONE,
+    ADD COLUMN IF NOT EXISTS purge_started_at TIMESTAMP WITH TIME ZONE;
+
+CREATE INDEX IF NOT EXISTS idx_users_purge_after ON users (purge_after) WHERE status = 'deleted';
+
+-- Purging a user removes all data that belongs to it
+ALTER TABLE apikeys
+    DROP CONSTRAINT IF EXISTS apikeys_user_id_fkey,
+    ADD CONSTRAINT apikeys_user_id_fkey FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE;
+
+ALTER TABLE todos
+    DROP CONSTRAINT IF EXISTS todos_user_id_fkey,
+    ADD CONSTRAINT todos_user_id_fkey FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE;
+
+ALTER TABLE usage_hourly
+    DROP CONSTRAINT IF EXISTS usage_hourly_user_id_fkey,
+    ADD CONSTRAINT usage_hourly_user_id_fkey FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE;
+
+ALTER TABLE usage_daily
+    DROP CONSTRAINT IF EXISTS usage_daily_user_id_fkey,
+    ADD CONSTRAINT usage_daily_user_id_fkey FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE;
+
+ALTER TABLE usage_quota_notifications
+    DROP CONSTRAINT IF EXISTS usage_quota_notifications_user_id_fkey,
+    ADD CONSTRAINT usage_quota_notifications_user_id_fkey FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE;
+
+ALTER TABLE billing_statements
+    DROP CONSTRAINT IF EXISTS billing_statements_user_id_fkey,
+    ADD CONSTRAINT billing_statements_user_id_fkey FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE;
+
+-- Raw usage rows are kept for statistics, but are no longer linked to the purged user
+ALTER TABLE usage
+    ALTER COLUMN user_id DROP NOT NULL,
+    DROP CONSTRAINT IF EXISTS usage_user_id_fkey,
+    ADD CONSTRAINT usage_user_id_fkey FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE SET NULL;
+
+ALTER TABLE usage_archive
+    ALTER COLUMN user_id DROP NOT NULL;
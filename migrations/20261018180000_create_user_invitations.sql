@@ -0,0 +1,22 @@
+-- Invitations sent by admins, the invitee creates its own account when accepting
+CREATE TABLE IF NOT EXISTS user_invitations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    email TEXT NOT NULL,
+    role_level INT NOT NULL,
+    tier_level INT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending',  -- 'pending', 'accepted', 'revoked'
+    token_nonce TEXT NOT NULL,               -- Part of the signed link, replaced when resending so older links stop working
+    invited_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    user_id UUID REFERENCES users(id) ON DELETE SET NULL,  -- The user created when accepting
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    last_sent_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    send_count INT NOT NULL DEFAULT 1,
+    accepted_at TIMESTAMP WITH TIME ZONE,
+    revoked_at TIMESTAMP WITH TIME ZONE,
+    CONSTRAINT check_user_invitations_status CHECK (status IN ('pending', 'accepted', 'revoked'))
+);
+
+-- Only one open invitation per email address
+CREATE UNIQUE INDEX IF NOT EXISTS idx_user_invitations_pending_email ON user_invitations (email) WHERE status = 'pending';
+CREATE INDEX IF NOT EXISTS idx_user_invitations_created_at ON user_invitations (created_at);
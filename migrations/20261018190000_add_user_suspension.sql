@@ -0,0 +1,9 @@
+-- Suspended users (status 'suspended') can not sign in until they are reinstated, or until the suspension ends
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS suspension_reason TEXT,
+    ADD COLUMN IF NOT EXISTS suspended_at TIMESTAMP WITH TIME ZONE,
+    ADD COLUMN IF NOT EXISTS suspended_until TIMESTAMP WITH TIME ZONE,  -- NULL means until reinstated by an admin
+    ADD COLUMN IF NOT EXISTS suspended_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    ADD COLUMN IF NOT EXISTS tokens_valid_after TIMESTAMP WITH TIME ZONE;  -- Tokens issued before this moment are rejected
+
+CREATE INDEX IF NOT EXISTS idx_users_suspended_until ON users (suspended_until) WHERE status = 'suspended';
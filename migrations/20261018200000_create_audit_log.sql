@@ -0,0 +1,38 @@
+-- Append-only log of security relevant events, each entry is chained to the previous one by its hash
+CREATE TABLE IF NOT EXISTS audit_log (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    seq BIGSERIAL NOT NULL UNIQUE,           -- Order of the hash chain
+    occurred_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    actor_id UUID,                           -- No foreign key, entries outlive purged users
+    action TEXT NOT NULL,                    -- e.g. 'user.login', 'apikey.create'
+    target_type TEXT,                        -- e.g. 'user', 'apikey', 'tier'
+    target_id TEXT,
+    ip TEXT,
+    user_agent TEXT,
+    before JSONB,                            -- Changed fields before the event
+    after JSONB,                             -- Changed fields after the event
+    prev_hash TEXT,                          -- Hash of the previous entry, NULL for the first one
+    hash TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_occurred_at ON audit_log (occurred_at);
+CREATE INDEX IF NOT EXISTS idx_audit_log_actor_id ON audit_log (actor_id);
+CREATE INDEX IF NOT EXISTS idx_audit_log_target ON audit_log (target_type, target_id);
+CREATE INDEX IF NOT EXISTS idx_audit_log_action ON audit_log (action);
+
+-- Entries can only be added, never changed or removed
+CREATE OR REPLACE FUNCTION prevent_audit_log_modification() RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'audit_log is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS audit_log_append_only ON audit_log;
+CREATE TRIGGER audit_log_append_only
+    BEFORE UPDATE OR DELETE ON audit_log
+    FOR EACH ROW EXECUTE FUNCTION prevent_audit_log_modification();
+
+DROP TRIGGER IF EXISTS audit_log_no_truncate ON audit_log;
+CREATE TRIGGER audit_log_no_truncate
+    BEFORE TRUNCATE ON audit_log
+    FOR EACH STATEMENT EXECUTE FUNCTION prevent_audit_log_modification();
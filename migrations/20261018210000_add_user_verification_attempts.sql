@@ -0,0 +1,6 @@
+-- Pending users can request a new verification code, and only try a limited amount of codes
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS verification_attempts INT NOT NULL DEFAULT 0,  -- Failed attempts with the current code
+    ADD COLUMN IF NOT EXISTS verification_sent_at TIMESTAMP WITH TIME ZONE;
+
+CREATE INDEX IF NOT EXISTS idx_users_pending_verification_expires_at ON users (verification_expires_at) WHERE status = 'pending';
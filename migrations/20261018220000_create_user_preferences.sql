@@ -0,0 +1,7 @@
+-- Per-user settings, only the preferences a user has set are stored (the defaults live in the application)
+CREATE TABLE user_preferences (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    preferences JSONB NOT NULL DEFAULT '{}'::jsonb,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT user_preferences_is_object CHECK (jsonb_typeof(preferences) = 'object')
+);
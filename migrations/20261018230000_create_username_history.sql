@@ -0,0 +1,11 @@
+-- Previous usernames, blocked from reuse by other users until reserved_until
+CREATE TABLE username_history (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    username VARCHAR(255) NOT NULL,
+    changed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    reserved_until TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_username_history_username ON username_history (username, reserved_until);
+CREATE INDEX idx_username_history_user_id ON username_history (user_id, changed_at DESC);
@@ -0,0 +1,25 @@
+-- Due dates with the time zone they were set in, and priorities (0 = none, 1 = low, 2 = medium, 3 = high)
+ALTER TABLE todos
+    ADD COLUMN due_at TIMESTAMPTZ,
+    ADD COLUMN due_timezone TEXT,
+    ADD COLUMN priority SMALLINT NOT NULL DEFAULT 0 CHECK (priority BETWEEN 0 AND 3);
+
+CREATE INDEX idx_todos_user_id_due_at ON todos (user_id, due_at);
+CREATE INDEX idx_todos_user_id_priority ON todos (user_id, priority);
+
+-- Free-form tags, shared by the todos of a user and matched case-insensitively
+CREATE TABLE todo_tags (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(30) NOT NULL
+);
+
+CREATE UNIQUE INDEX idx_todo_tags_user_id_name ON todo_tags (user_id, LOWER(name));
+
+CREATE TABLE todo_tag_links (
+    todo_id UUID NOT NULL REFERENCES todos(id) ON DELETE CASCADE,
+    tag_id UUID NOT NULL REFERENCES todo_tags(id) ON DELETE CASCADE,
+    PRIMARY KEY (todo_id, tag_id)
+);
+
+CREATE INDEX idx_todo_tag_links_tag_id ON todo_tag_links (tag_id);
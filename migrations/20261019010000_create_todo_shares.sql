@@ -0,0 +1,11 @@
+-- Todos shared by their owner with other users, as viewer (read only) or editor
+CREATE TABLE todo_shares (
+    todo_id UUID NOT NULL REFERENCES todos(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    permission TEXT NOT NULL CHECK (permission IN ('viewer', 'editor')),
+    shared_by UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (todo_id, user_id)
+);
+
+CREATE INDEX idx_todo_shares_user_id ON todo_shares (user_id);
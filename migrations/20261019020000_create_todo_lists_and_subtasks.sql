@@ -0,0 +1,20 @@
+-- Named lists (projects) owning todos, ordered manually by position
+CREATE TABLE todo_lists (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(50) NOT NULL,
+    description VARCHAR(200),
+    position INT NOT NULL DEFAULT 0,
+    creation_date TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX idx_todo_lists_user_id_name ON todo_lists (user_id, LOWER(name));
+
+-- Todos belong to a list (or none) and optionally to a parent todo, ordered by position within both
+ALTER TABLE todos
+    ADD COLUMN list_id UUID REFERENCES todo_lists(id) ON DELETE CASCADE,
+    ADD COLUMN parent_id UUID REFERENCES todos(id) ON DELETE CASCADE,
+    ADD COLUMN position INT NOT NULL DEFAULT 0;
+
+CREATE INDEX idx_todos_list_id ON todos (list_id, position);
+CREATE INDEX idx_todos_parent_id ON todos (parent_id, position);
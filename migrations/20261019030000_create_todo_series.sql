@@ -0,0 +1,31 @@
+-- Recurring todos: a series (RFC 5545 rule) generates its next occurrence when the current one is completed.
+-- New occurrences are created from the template in the series, so edits to a single occurrence stay with it.
+CREATE TABLE todo_series (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    rrule TEXT NOT NULL,
+    dtstart TIMESTAMPTZ NOT NULL,
+    timezone TEXT NOT NULL,
+    task TEXT NOT NULL,
+    description TEXT,
+    priority SMALLINT NOT NULL DEFAULT 0,
+    tags TEXT[] NOT NULL DEFAULT '{}',
+    list_id UUID REFERENCES todo_lists(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_todo_series_user_id ON todo_series (user_id);
+
+-- Occurrences that are skipped, like EXDATE in RFC 5545
+CREATE TABLE todo_series_skips (
+    series_id UUID NOT NULL REFERENCES todo_series(id) ON DELETE CASCADE,
+    occurrence_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (series_id, occurrence_at)
+);
+
+-- Todos generated by a series, occurrence_at is the scheduled time even when the due date is moved
+ALTER TABLE todos
+    ADD COLUMN series_id UUID REFERENCES todo_series(id) ON DELETE SET NULL,
+    ADD COLUMN occurrence_at TIMESTAMPTZ;
+
+CREATE UNIQUE INDEX idx_todos_series_id_occurrence_at ON todos (series_id, occurrence_at);
@@ -0,0 +1,15 @@
+-- Files attached to todos, stored in S3, counting towards the storage quota of the todo owner
+CREATE TABLE todo_attachments (
+    id UUID PRIMARY KEY,
+    todo_id UUID NOT NULL REFERENCES todos(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    uploaded_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    file_name VARCHAR(255) NOT NULL,
+    content_type VARCHAR(255) NOT NULL,
+    size BIGINT NOT NULL CHECK (size > 0),
+    object_key TEXT NOT NULL UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_todo_attachments_todo_id ON todo_attachments (todo_id, created_at);
+CREATE INDEX idx_todo_attachments_user_id ON todo_attachments (user_id);
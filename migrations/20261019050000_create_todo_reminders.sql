@@ -0,0 +1,14 @@
+-- Reminder emails of todos, one per due date and lead time. A row is the claim of the instance sending it,
+-- so every reminder goes out once, also across instances and restarts. A new due date gets new reminders.
+CREATE TABLE todo_reminders (
+    todo_id UUID NOT NULL REFERENCES todos(id) ON DELETE CASCADE,
+    due_at TIMESTAMPTZ NOT NULL,
+    lead_minutes INT NOT NULL,
+    status VARCHAR(10) NOT NULL DEFAULT 'sending' CHECK (status IN ('sending', 'sent')),
+    claimed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    sent_at TIMESTAMPTZ,
+    PRIMARY KEY (todo_id, due_at, lead_minutes)
+);
+
+-- Upcoming open todos are scanned for reminders
+CREATE INDEX idx_todos_due_at_open ON todos (due_at) WHERE due_at IS NOT NULL AND completed IS NOT TRUE;
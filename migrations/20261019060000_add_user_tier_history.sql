@@ -0,0 +1,49 @@
+-- The tiers users have held over time, so that a period is billed with the tier of that period,
+-- also when the tier changes before the statements are generated. Kept up to date by a trigger,
+-- so that every way of changing tier_level is recorded.
+CREATE TABLE IF NOT EXISTS user_tier_history (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    tier_id UUID NOT NULL REFERENCES tiers(id) ON DELETE RESTRICT,  -- Tiers that users have held are kept for billing
+    valid_from TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, valid_from)
+);
+
+-- Existing users are assumed to have held their current tier since they were created
+INSERT INTO user_tier_history (user_id, tier_id, valid_from)
+SELECT u.id, t.id, u.creation_date::TIMESTAMP AT TIME ZONE 'UTC'
+FROM users u
+JOIN tiers t ON t.level = u.tier_level
+ON CONFLICT DO NOTHING;
+
+CREATE OR REPLACE FUNCTION record_user_tier_history() RETURNS TRIGGER AS $$
+DECLARE
+    new_tier_id UUID;
+BEGIN
+    SELECT id INTO new_tier_id FROM tiers WHERE level = NEW.tier_level;
+    IF new_tier_id IS NULL THEN
+        RETURN NEW;
+    END IF;
+
+    -- Moving users along with a changed tier level keeps them on the same tier
+    IF new_tier_id IS DISTINCT FROM (
+        SELECT tier_id FROM user_tier_history WHERE user_id = NEW.id ORDER BY valid_from DESC LIMIT 1
+    ) THEN
+        INSERT INTO user_tier_history (user_id, tier_id, valid_from)
+        VALUES (NEW.id, new_tier_id, NOW())
+        ON CONFLICT (user_id, valid_from) DO UPDATE SET tier_id = EXCLUDED.tier_id;
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS users_tier_history ON users;
+CREATE TRIGGER users_tier_history
+    AFTER INSERT OR UPDATE OF tier_level ON users
+    FOR EACH ROW EXECUTE FUNCTION record_user_tier_history();
+
+-- Statements are financial records: they outlive the purge of the user, without the link to the user
+ALTER TABLE billing_statements
+    ALTER COLUMN user_id DROP NOT NULL,
+    DROP CONSTRAINT IF EXISTS billing_statements_user_id_fkey,
+    ADD CONSTRAINT billing_statements_user_id_fkey FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE SET NULL;
@@ -0,0 +1,42 @@
+-- The tenant (organization) a user belongs to, assigned by admins. Todos and lists are only
+-- shared within a tenant; users without a tenant only share with other users without one.
+ALTER TABLE users ADD COLUMN IF NOT EXISTS tenant VARCHAR(100);
+
+CREATE INDEX IF NOT EXISTS idx_users_tenant ON users (tenant);
+
+-- Lists shared by their owner with other users, which gives access to all todos in the list
+CREATE TABLE todo_list_shares (
+    list_id UUID NOT NULL REFERENCES todo_lists(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    permission TEXT NOT NULL CHECK (permission IN ('viewer', 'editor')),
+    shared_by UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (list_id, user_id)
+);
+
+CREATE INDEX idx_todo_list_shares_user_id ON todo_list_shares (user_id);
+
+-- A user moving to another tenant loses the shares with the users of the previous tenant, both ways
+CREATE OR REPLACE FUNCTION remove_cross_tenant_shares() RETURNS TRIGGER AS $$
+BEGIN
+    DELETE FROM todo_shares s
+    USING todos t, users owner, users recipient
+    WHERE t.id = s.todo_id AND owner.id = t.user_id AND recipient.id = s.user_id
+        AND NEW.id IN (owner.id, recipient.id)
+        AND owner.tenant IS DISTINCT FROM recipient.tenant;
+
+    DELETE FROM todo_list_shares s
+    USING todo_lists l, users owner, users recipient
+    WHERE l.id = s.list_id AND owner.id = l.user_id AND recipient.id = s.user_id
+        AND NEW.id IN (owner.id, recipient.id)
+        AND owner.tenant IS DISTINCT FROM recipient.tenant;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS users_tenant_shares ON users;
+CREATE TRIGGER users_tenant_shares
+    AFTER UPDATE OF tenant ON users
+    FOR EACH ROW WHEN (OLD.tenant IS DISTINCT FROM NEW.tenant)
+    EXECUTE FUNCTION remove_cross_tenant_shares();
@@ -0,0 +1,4 @@
+-- Storage the todo attachments of the users of a tier may take up together, NULL uses TODO_ATTACHMENT_QUOTA_MB
+ALTER TABLE tiers
+    ADD COLUMN IF NOT EXISTS attachment_quota_mb INT,
+    ADD CONSTRAINT non_negative_attachment_quota_mb CHECK (attachment_quota_mb IS NULL OR attachment_quota_mb >= 0);
@@ -0,0 +1,31 @@
+use deadpool_redis::Pool;
+use deadpool_redis::redis;
+
+/// Increments a counter that expires after `window_secs`, starting a new window when it has expired.
+/// Returns Ok((count, seconds until the window resets)) on success, or Err(String) with error details.
+pub async fn increment_in_cache(
+    redis_pool: &Pool,
+    key: &str,
+    window_secs: u64,
+) -> Result<(u64, u64), String> {
+    // Input validation
+    if key.trim().is_empty() {
+        return Err("Redis increment error: key is empty".to_string());
+    }
+
+    // Get a connection from the pool
+    let mut conn = redis_pool.get().await
+        .map_err(|e| format!("Failed to get Redis connection: {e}"))?;
+
+    // Create the counter with its expiry only if it does not exist yet, so that the window is fixed
+    let (count, ttl): (u64, i64) = redis::pipe()
+        .atomic()
+        .cmd("SET").arg(key).arg(0).arg("EX").arg(window_secs.max(1)).arg("NX").ignore()
+        .incr(key, 1)
+        .ttl(key)
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| format!("Failed to increment value in Redis: {e}"))?;
+
+    Ok((count, ttl.max(0) as u64))
+}
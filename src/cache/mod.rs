@@ -1,4 +1,5 @@
 // Module declarations
 pub mod connect;
 pub mod add;
-pub mod delete;
\ No newline at end of file
+pub mod delete;
+pub mod increment;
\ No newline at end of file
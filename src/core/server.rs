@@ -15,6 +15,15 @@ use crate::cache::connect::connect_to_cache;  // Function to connect to cache
 use crate::mail::connect::connect_to_mail;  // Function to connect to mail service  
 use crate::config;  // Environment configuration helper
 use crate::routes::create_routes;  // Function to create application routes
+use crate::middlewares::auth::start_batched_writes;  // Function to start the batched usage writes
+use crate::jobs::usage_rollup::start_usage_rollup;  // Function to start the usage rollups
+use crate::jobs::usage_quota::start_quota_notifications;  // Function to start the quota notifications
+use crate::jobs::billing::start_billing_statements;  // Function to start generating the billing statements
+use crate::jobs::data_export::start_data_exports;  // Function to start building the personal data exports
+use crate::jobs::user_purge::start_user_purge;  // Function to start purging deleted users
+use crate::jobs::suspension_expiry::start_suspension_expiry;  // Function to start ending suspensions
+use crate::jobs::pending_user_cleanup::start_pending_user_cleanup;  // Function to start deleting unverified registrations
+use crate::jobs::todo_reminders::start_todo_reminders;  // Function to start sending the todo reminders
 
 use std::time::Duration;
 
@@ -46,6 +55,18 @@ pub async fn create_server() -> Router<()> {
         .expect("❌  Failed to connect to mail.");
     println!("✔️   Connected to mail.");
 
+    // === Background Jobs ===
+    start_batched_writes(database.clone());
+    start_usage_rollup(database.clone());
+    start_quota_notifications(database.clone(), mail.clone());
+    start_billing_statements(database.clone());
+    start_data_exports(database.clone(), storage.clone(), mail.clone());
+    start_user_purge(database.clone(), storage.clone(), mail.clone());
+    start_suspension_expiry(database.clone(), mail.clone());
+    start_pending_user_cleanup(database.clone());
+    start_todo_reminders(database.clone(), mail.clone());
+    println!("✔️   Background jobs have been started.");
+
     let shared_state = Arc::new(AppState { database: database, storage: storage, cache: cache, mail: mail });
 
     // === Application Routes ===
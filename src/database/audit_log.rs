@@ -0,0 +1,154 @@
+use sqlx::postgres::PgPool;
+use chrono::{SubsecRound, Utc};
+
+use crate::database::list_query::{ListParams, ListQuery, SortField};
+use crate::models::audit::{AuditEvent, AuditLogEntry, AuditLogFilter};
+use crate::models::pagination::Page;
+use crate::utils::audit::{compute_audit_hash, AuditContext};
+
+/// Advisory lock serializing appends to the hash chain.
+const AUDIT_LOG_LOCK_KEY: i64 = 260_002;
+
+/// Columns of an audit log entry, in the order of `AuditLogEntry`.
+const AUDIT_LOG_COLUMNS: &str =
+    "id, seq, occurred_at, actor_id, action, target_type, target_id, ip, user_agent, before, after, prev_hash, hash";
+
+/// Fields the audit log can be sorted on.
+pub const AUDIT_LOG_SORT_FIELDS: &[SortField] = &[
+    SortField { name: "occurred_at", column: "occurred_at", sql_type: "TIMESTAMPTZ" },
+    SortField { name: "action", column: "action", sql_type: "TEXT" },
+];
+
+/// Appends an event to the audit log, chained to the hash of the last entry
+///
+/// # Concurrency
+/// - Guarded by a transaction scoped advisory lock, so entries are chained one at a time
+///
+/// # Security
+/// - The table only accepts inserts, updates and deletes are rejected by a trigger
+pub async fn insert_audit_log_entry_into_db(
+    pool: &PgPool,
+    context: &AuditContext,
+    event: AuditEvent,
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query!("SELECT pg_advisory_xact_lock($1)", AUDIT_LOG_LOCK_KEY)
+        .execute(&mut *tx)
+        .await?;
+
+    let prev_hash = sqlx::query_scalar!("SELECT hash FROM audit_log ORDER BY seq DESC LIMIT 1")
+        .fetch_optional(&mut *tx)
+        .await?;
+
+    // The database stores microseconds, the hash must match what is read back
+    let occurred_at = Utc::now().trunc_subsecs(6);
+    let hash = compute_audit_hash(
+        prev_hash.as_deref(),
+        &occurred_at,
+        event.actor_id.map(|id| id.to_string()).as_deref(),
+        &event.action,
+        event.target_type.as_deref(),
+        event.target_id.as_deref(),
+        context.ip.as_deref(),
+        context.user_agent.as_deref(),
+        event.before.as_ref(),
+        event.after.as_ref(),
+    );
+
+    sqlx::query!(
+        "INSERT INTO audit_log (occurred_at, actor_id, action, target_type, target_id, ip, user_agent, before, after, prev_hash, hash)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)",
+        occurred_at,
+        event.actor_id,
+        event.action,
+        event.target_type,
+        event.target_id,
+        context.ip,
+        context.user_agent,
+        event.before,
+        event.after,
+        prev_hash,
+        hash
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await
+}
+
+// Adds the filters of the audit log to a query
+fn apply_audit_log_filter<'args>(query: &mut ListQuery<'args>, filter: &AuditLogFilter) {
+    query
+        .filter("actor_id", "=", filter.actor_id)
+        .filter("target_type", "=", filter.target_type.clone())
+        .filter("target_id", "=", filter.target_id.clone())
+        .filter("action", "=", filter.action.clone())
+        .filter("occurred_at", ">=", filter.from)
+        .filter("occurred_at", "<", filter.to);
+}
+
+/// Retrieves a page of audit log entries matching the filters, with the total amount of matches
+///
+/// # Security
+/// - Requires admin privileges (enforced at application layer)
+/// - Filter values are bound, sort columns come from `AUDIT_LOG_SORT_FIELDS` only
+pub async fn fetch_audit_log_page_from_db(
+    pool: &PgPool,
+    filter: &AuditLogFilter,
+    params: &ListParams,
+) -> Result<Page<AuditLogEntry>, sqlx::Error> {
+    let mut count = ListQuery::new("SELECT COUNT(*) FROM audit_log");
+    let mut list = ListQuery::new(&format!("SELECT {} FROM audit_log", AUDIT_LOG_COLUMNS));
+
+    for query in [&mut count, &mut list] {
+        apply_audit_log_filter(query, filter);
+    }
+
+    let total: i64 = count.builder().build_query_scalar().fetch_one(pool).await?;
+
+    list.paginate(params, "id");
+    let rows = list.builder().build_query_as::<AuditLogEntry>().fetch_all(pool).await?;
+
+    Ok(params.build_page(rows, total, |entry| {
+        let value = match params.sort.name {
+            "action" => entry.action.clone(),
+            _ => entry.occurred_at.to_rfc3339(),
+        };
+        (value, entry.id)
+    }))
+}
+
+/// Retrieves the audit log entries matching the filters in chain order, for exporting
+///
+/// # Security
+/// - Requires admin privileges (enforced at application layer)
+/// - At most `limit` entries are returned
+pub async fn fetch_audit_log_export_from_db(
+    pool: &PgPool,
+    filter: &AuditLogFilter,
+    limit: i64,
+) -> Result<Vec<AuditLogEntry>, sqlx::Error> {
+    let mut query = ListQuery::new(&format!("SELECT {} FROM audit_log", AUDIT_LOG_COLUMNS));
+    apply_audit_log_filter(&mut query, filter);
+    query.builder().push(" ORDER BY seq LIMIT ").push_bind(limit);
+
+    query.builder().build_query_as::<AuditLogEntry>().fetch_all(pool).await
+}
+
+/// Retrieves a batch of audit log entries following the given position in the chain
+pub async fn fetch_audit_log_batch_from_db(
+    pool: &PgPool,
+    after_seq: i64,
+    limit: i64,
+) -> Result<Vec<AuditLogEntry>, sqlx::Error> {
+    sqlx::query_as!(
+        AuditLogEntry,
+        "SELECT id, seq, occurred_at, actor_id, action, target_type, target_id, ip, user_agent, before, after, prev_hash, hash
+        FROM audit_log WHERE seq > $1 ORDER BY seq LIMIT $2",
+        after_seq,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+}
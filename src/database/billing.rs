@@ -0,0 +1,147 @@
+use sqlx::postgres::PgPool;
+use uuid::Uuid;
+use chrono::NaiveDate;
+use crate::models::billing::{BillingPrice, BillingPriceInsertBody, BillingStatement};
+
+/// Retrieves all prices, newest first per tier
+///
+/// # Security
+/// - Only exposes the public price list
+pub async fn fetch_billing_prices_from_db(pool: &PgPool) -> Result<Vec<BillingPrice>, sqlx::Error> {
+    sqlx::query_as!(
+        BillingPrice,
+        r#"SELECT id, tier_id, valid_from, currency AS "currency!", base_fee_cents, included_requests,
+               overage_cents_per_1000, creation_date
+        FROM billing_prices ORDER BY tier_id, valid_from DESC"#
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Inserts a new price for a tier
+///
+/// # Security
+/// - Prices are append-only, so earlier statements stay reproducible
+pub async fn insert_billing_price_into_db(pool: &PgPool, price: BillingPriceInsertBody) -> Result<BillingPrice, sqlx::Error> {
+    sqlx::query_as!(
+        BillingPrice,
+        r#"INSERT INTO billing_prices (tier_id, valid_from, currency, base_fee_cents, included_requests, overage_cents_per_1000)
+        VALUES ($1, $2, UPPER(COALESCE($3, 'EUR')), $4, $5, $6)
+        RETURNING id, tier_id, valid_from, currency AS "currency!", base_fee_cents, included_requests,
+                  overage_cents_per_1000, creation_date"#,
+        price.tier_id,
+        price.valid_from,
+        price.currency,
+        price.base_fee_cents,
+        price.included_requests,
+        price.overage_cents_per_1000
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Generates the statements of a period for every user that does not have one yet
+///
+/// The request count is taken from the daily usage aggregates, which are kept forever,
+/// so that a statement can be reproduced after the fact. The tier is the one the user held at
+/// the end of the period according to `user_tier_history`, not the current one, and the applied
+/// price is the newest price of that tier that is valid at the start of the period.
+///
+/// # Security
+/// - Idempotent: existing statements are never overwritten (unique per user and period)
+/// - Safe to run on multiple instances at the same time
+///
+/// # Returns
+/// The amount of newly generated statements
+pub async fn generate_billing_statements_in_db(
+    pool: &PgPool,
+    period_start: NaiveDate,
+    period_end: NaiveDate,
+    user_id: Option<Uuid>,
+) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query!(
+        r#"INSERT INTO billing_statements
+            (user_id, period_start, period_end, price_id, tier_level, tier_name, currency, base_fee_cents,
+             included_requests, overage_cents_per_1000, request_count, overage_requests, overage_cents, total_cents)
+        SELECT u.id, $1, $2, p.id, t.level, t.name, p.currency, p.base_fee_cents,
+               p.included_requests, p.overage_cents_per_1000, c.request_count, c.overage_requests,
+               (c.overage_requests * p.overage_cents_per_1000 + 999) / 1000,
+               p.base_fee_cents + (c.overage_requests * p.overage_cents_per_1000 + 999) / 1000
+        FROM users u
+        JOIN LATERAL (
+            SELECT h.tier_id FROM user_tier_history h
+            WHERE h.user_id = u.id AND h.valid_from < ($2::DATE)::TIMESTAMP AT TIME ZONE 'UTC'
+            ORDER BY h.valid_from DESC
+            LIMIT 1
+        ) th ON TRUE
+        JOIN tiers t ON t.id = th.tier_id
+        JOIN LATERAL (
+            SELECT * FROM billing_prices bp
+            WHERE bp.tier_id = t.id AND bp.valid_from <= $1
+            ORDER BY bp.valid_from DESC
+            LIMIT 1
+        ) p ON TRUE
+        CROSS JOIN LATERAL (
+            SELECT x.request_count, GREATEST(x.request_count - p.included_requests, 0) AS overage_requests
+            FROM (
+                SELECT COALESCE(SUM(d.count), 0)::BIGINT AS request_count
+                FROM usage_daily d
+                WHERE d.user_id = u.id AND d.day >= $1 AND d.day < $2
+            ) x
+        ) c
+        WHERE u.status <> 'pending'
+        AND u.creation_date < $2
+        AND ($3::UUID IS NULL OR u.id = $3)
+        ON CONFLICT (user_id, period_start) DO NOTHING"#,
+        period_start,
+        period_end,
+        user_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Retrieves the start of the last period that has been billed, if any
+///
+/// Prices cannot start in or before this period anymore, so that its statements stay reproducible.
+pub async fn fetch_last_billed_period_from_db(pool: &PgPool) -> Result<Option<NaiveDate>, sqlx::Error> {
+    sqlx::query_scalar!("SELECT MAX(period_start) FROM billing_statements")
+        .fetch_one(pool)
+        .await
+}
+
+/// Retrieves all statements of a user, newest first
+///
+/// # Security
+/// - Explicit user ownership check
+pub async fn fetch_billing_statements_by_user_from_db(pool: &PgPool, user_id: Uuid) -> Result<Vec<BillingStatement>, sqlx::Error> {
+    sqlx::query_as!(
+        BillingStatement,
+        r#"SELECT id, user_id, period_start, period_end, price_id, tier_level, tier_name, currency AS "currency!",
+               base_fee_cents, included_requests, overage_cents_per_1000, request_count, overage_requests,
+               overage_cents, total_cents, generated_at
+        FROM billing_statements WHERE user_id = $1 ORDER BY period_start DESC"#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Retrieves a statement by its ID
+///
+/// # Security
+/// - Callers must check that the statement belongs to the requesting user (or that the user is an admin)
+pub async fn fetch_billing_statement_by_id_from_db(pool: &PgPool, id: Uuid) -> Result<Option<BillingStatement>, sqlx::Error> {
+    sqlx::query_as!(
+        BillingStatement,
+        r#"SELECT id, user_id, period_start, period_end, price_id, tier_level, tier_name, currency AS "currency!",
+               base_fee_cents, included_requests, overage_cents_per_1000, request_count, overage_requests,
+               overage_cents, total_cents, generated_at
+        FROM billing_statements WHERE id = $1"#,
+        id
+    )
+    .fetch_optional(pool)
+    .await
+}
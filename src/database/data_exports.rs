@@ -0,0 +1,144 @@
+use sqlx::postgres::PgPool;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use crate::models::data_export::DataExport;
+
+/// Requests a new data export for a user
+///
+/// # Security
+/// - The export is processed by the background job, never within the request
+pub async fn insert_data_export_into_db(pool: &PgPool, user_id: Uuid) -> Result<DataExport, sqlx::Error> {
+    sqlx::query_as!(
+        DataExport,
+        "INSERT INTO data_exports (user_id) VALUES ($1)
+        RETURNING id, user_id, status, object_key, error, requested_at, completed_at, expires_at",
+        user_id
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Retrieves a data export of a user by its ID
+///
+/// # Security
+/// - Explicit user ownership check
+pub async fn fetch_data_export_by_id_from_db(pool: &PgPool, id: Uuid, user_id: Uuid) -> Result<Option<DataExport>, sqlx::Error> {
+    sqlx::query_as!(
+        DataExport,
+        "SELECT id, user_id, status, object_key, error, requested_at, completed_at, expires_at
+        FROM data_exports WHERE id = $1 AND user_id = $2",
+        id,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Retrieves the latest data export of a user that did not fail
+///
+/// # Security
+/// - Explicit user ownership check
+pub async fn fetch_latest_data_export_from_db(pool: &PgPool, user_id: Uuid) -> Result<Option<DataExport>, sqlx::Error> {
+    sqlx::query_as!(
+        DataExport,
+        "SELECT id, user_id, status, object_key, error, requested_at, completed_at, expires_at
+        FROM data_exports WHERE user_id = $1 AND status <> 'failed'
+        ORDER BY requested_at DESC LIMIT 1",
+        user_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Claims the oldest pending data export for processing
+///
+/// Exports stuck in processing (e.g. after a crash) are claimed again after 30 minutes.
+///
+/// # Concurrency
+/// - `FOR UPDATE SKIP LOCKED` ensures every export is claimed by one instance only
+pub async fn claim_pending_data_export_in_db(pool: &PgPool) -> Result<Option<DataExport>, sqlx::Error> {
+    sqlx::query_as!(
+        DataExport,
+        "UPDATE data_exports SET status = 'processing', started_at = NOW()
+        WHERE id = (
+            SELECT id FROM data_exports
+            WHERE status = 'pending'
+            OR (status = 'processing' AND started_at < NOW() - INTERVAL '30 minutes')
+            ORDER BY requested_at
+            LIMIT 1
+            FOR UPDATE SKIP LOCKED
+        )
+        RETURNING id, user_id, status, object_key, error, requested_at, completed_at, expires_at"
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Marks a data export as completed
+pub async fn complete_data_export_in_db(
+    pool: &PgPool,
+    id: Uuid,
+    object_key: &str,
+    expires_at: DateTime<Utc>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "UPDATE data_exports SET status = 'completed', object_key = $2, completed_at = NOW(), expires_at = $3, error = NULL
+        WHERE id = $1",
+        id,
+        object_key,
+        expires_at
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Marks a data export as failed
+pub async fn fail_data_export_in_db(pool: &PgPool, id: Uuid, error: &str) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "UPDATE data_exports SET status = 'failed', error = $2 WHERE id = $1",
+        id,
+        error
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Retrieves completed data exports whose bundle has expired
+pub async fn fetch_expired_data_exports_from_db(pool: &PgPool) -> Result<Vec<DataExport>, sqlx::Error> {
+    sqlx::query_as!(
+        DataExport,
+        "SELECT id, user_id, status, object_key, error, requested_at, completed_at, expires_at
+        FROM data_exports WHERE status = 'completed' AND expires_at < NOW()"
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Marks a data export as expired, after its bundle has been deleted
+pub async fn expire_data_export_in_db(pool: &PgPool, id: Uuid) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "UPDATE data_exports SET status = 'expired', object_key = NULL WHERE id = $1",
+        id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Retrieves the keys of all stored bundles of a user
+///
+/// # Security
+/// - Explicit user ownership check
+pub async fn fetch_data_export_keys_by_user_from_db(pool: &PgPool, user_id: Uuid) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"SELECT object_key AS "object_key!" FROM data_exports WHERE user_id = $1 AND object_key IS NOT NULL"#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+}
@@ -0,0 +1,179 @@
+use sqlx::postgres::PgPool;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use crate::models::invitation::{Invitation, InvitationAcceptBody};
+use crate::models::user::UserInsertResponse;
+
+/// Creates a new pending invitation
+///
+/// # Security
+/// - Requires admin privileges (enforced at application layer)
+/// - Only one pending invitation per email address (unique index)
+pub async fn insert_invitation_into_db(
+    pool: &PgPool,
+    email: &str,
+    role_level: i32,
+    tier_level: i32,
+    token_nonce: &str,
+    invited_by: Uuid,
+    expires_at: DateTime<Utc>,
+) -> Result<Invitation, sqlx::Error> {
+    sqlx::query_as!(
+        Invitation,
+        "INSERT INTO user_invitations (email, role_level, tier_level, token_nonce, invited_by, expires_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id, email, role_level, tier_level, status, token_nonce, invited_by, user_id,
+                  created_at, expires_at, last_sent_at, send_count, accepted_at, revoked_at",
+        email,
+        role_level,
+        tier_level,
+        token_nonce,
+        invited_by,
+        expires_at
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Retrieves all invitations, newest first
+///
+/// # Arguments
+/// - `status`: Only invitations with this status, "expired" selects the pending invitations that have expired
+pub async fn fetch_invitations_from_db(pool: &PgPool, status: Option<&str>) -> Result<Vec<Invitation>, sqlx::Error> {
+    sqlx::query_as!(
+        Invitation,
+        "SELECT id, email, role_level, tier_level, status, token_nonce, invited_by, user_id,
+                created_at, expires_at, last_sent_at, send_count, accepted_at, revoked_at
+        FROM user_invitations
+        WHERE CASE $1::text
+            WHEN 'expired' THEN status = 'pending' AND expires_at <= NOW()
+            WHEN 'pending' THEN status = 'pending' AND expires_at > NOW()
+            ELSE $1::text IS NULL OR status = $1::text
+        END
+        ORDER BY created_at DESC",
+        status
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Retrieves an invitation by its ID
+pub async fn fetch_invitation_by_id_from_db(pool: &PgPool, id: Uuid) -> Result<Option<Invitation>, sqlx::Error> {
+    sqlx::query_as!(
+        Invitation,
+        "SELECT id, email, role_level, tier_level, status, token_nonce, invited_by, user_id,
+                created_at, expires_at, last_sent_at, send_count, accepted_at, revoked_at
+        FROM user_invitations WHERE id = $1",
+        id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Renews a pending invitation before sending it again
+///
+/// The nonce is replaced, so links from earlier emails stop working.
+pub async fn renew_invitation_in_db(
+    pool: &PgPool,
+    id: Uuid,
+    token_nonce: &str,
+    expires_at: DateTime<Utc>,
+) -> Result<Option<Invitation>, sqlx::Error> {
+    sqlx::query_as!(
+        Invitation,
+        "UPDATE user_invitations
+        SET token_nonce = $2, expires_at = $3, last_sent_at = NOW(), send_count = send_count + 1
+        WHERE id = $1 AND status = 'pending'
+        RETURNING id, email, role_level, tier_level, status, token_nonce, invited_by, user_id,
+                  created_at, expires_at, last_sent_at, send_count, accepted_at, revoked_at",
+        id,
+        token_nonce,
+        expires_at
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Revokes a pending invitation
+///
+/// # Returns
+/// - Affected rows, 0 when the invitation does not exist or is no longer pending
+pub async fn revoke_invitation_in_db(pool: &PgPool, id: Uuid) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query!(
+        "UPDATE user_invitations SET status = 'revoked', revoked_at = NOW() WHERE id = $1 AND status = 'pending'",
+        id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Accepts an invitation by creating its user
+///
+/// # Security
+/// - The invitation is locked and rechecked (pending, nonce, expiry) within the transaction,
+///   so a link can only be used once
+/// - Returns `None` when the invitation can no longer be accepted
+pub async fn accept_invitation_in_db(
+    pool: &PgPool,
+    id: Uuid,
+    token_nonce: &str,
+    body: &InvitationAcceptBody,
+    password_hash: &str,
+    totp_secret: &str,
+) -> Result<Option<UserInsertResponse>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let invitation = sqlx::query!(
+        "SELECT email, role_level, tier_level FROM user_invitations
+        WHERE id = $1 AND token_nonce = $2 AND status = 'pending' AND expires_at > NOW()
+        FOR UPDATE",
+        id,
+        token_nonce
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some(invitation) = invitation else {
+        return Ok(None);
+    };
+
+    // The email address has been verified by following the link, so the user is active right away
+    let user = sqlx::query_as!(
+        UserInsertResponse,
+        r#"INSERT INTO users
+           (username, email, password_hash, totp_secret, role_level, tier_level, creation_date, status,
+            first_name, last_name, country_code, language_code, birthday, description)
+           VALUES ($1, $2, $3, $4, $5, $6, NOW()::timestamp, 'active', $7, $8, $9, $10, $11, $12)
+           RETURNING id, username, email, totp_secret, role_level, tier_level, creation_date,
+                     first_name, last_name, country_code, language_code, birthday, description,
+                     profile_picture_url"#,
+        body.username.trim(),
+        invitation.email,
+        password_hash,
+        totp_secret,
+        invitation.role_level,
+        invitation.tier_level,
+        body.first_name,
+        body.last_name,
+        body.country_code,
+        body.language_code,
+        body.birthday,
+        body.description,
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        "UPDATE user_invitations SET status = 'accepted', accepted_at = NOW(), user_id = $2 WHERE id = $1",
+        id,
+        user.id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Some(user))
+}
@@ -0,0 +1,299 @@
+//! Reusable building blocks for paginated, filtered and sorted list queries.
+//!
+//! A list endpoint declares the fields it can be sorted on, parses the shared
+//! [`PaginationQuery`] into [`ListParams`] and builds its queries with [`ListQuery`]:
+//!
+//! ```ignore
+//! let mut count = ListQuery::new("SELECT COUNT(*) FROM users");
+//! let mut list = ListQuery::new("SELECT id, username FROM users");
+//! for query in [&mut count, &mut list] {
+//!     query.filter("role_level", "=", filter.role_level);
+//! }
+//! list.paginate(&params, "id");
+//! ```
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, NaiveDate};
+use serde::{Deserialize, Serialize};
+use sqlx::{Encode, Postgres, QueryBuilder, Type};
+use uuid::Uuid;
+
+use crate::models::pagination::{Page, PaginationQuery};
+
+/// Amount of items returned when no limit is given.
+pub const DEFAULT_LIMIT: i64 = 50;
+
+/// Maximum amount of items per page.
+pub const MAX_LIMIT: i64 = 200;
+
+/// A field a list can be sorted on.
+///
+/// Sort columns must be `NOT NULL`, as the cursor compares on them.
+pub struct SortField {
+    /// Name of the field in the API.
+    pub name: &'static str,
+    /// SQL column (or expression) to sort on.
+    pub column: &'static str,
+    /// SQL type of the column, used to cast the value stored in a cursor.
+    /// One of `TEXT`, `INT`, `SMALLINT`, `DATE` or `TIMESTAMPTZ`.
+    pub sql_type: &'static str,
+}
+
+impl SortField {
+    /// Whether a cursor value can be cast to the type of the column, so a cursor never fails the query.
+    fn accepts(&self, value: &str) -> bool {
+        match self.sql_type {
+            "TEXT" => true,
+            "INT" => value.parse::<i32>().is_ok(),
+            "SMALLINT" => value.parse::<i16>().is_ok(),
+            "DATE" => NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok(),
+            "TIMESTAMPTZ" => value == "infinity" || DateTime::parse_from_rfc3339(value).is_ok(),
+            _ => false,
+        }
+    }
+}
+
+/// Position after the last item of a page.
+///
+/// It only continues the list it was handed out for, with the same sort field and order.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Cursor {
+    /// Name of the sort field of the list.
+    pub sort: String,
+    /// Whether the list is sorted in descending order.
+    pub descending: bool,
+    /// Sort value of the last item, as text.
+    pub value: String,
+    /// ID of the last item, which breaks ties between equal sort values.
+    pub id: Uuid,
+}
+
+impl Cursor {
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(self).unwrap_or_default())
+    }
+
+    pub fn decode(value: &str) -> Option<Self> {
+        let bytes = URL_SAFE_NO_PAD.decode(value).ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+}
+
+/// Validated pagination and sort parameters.
+pub struct ListParams {
+    pub limit: i64,
+    pub offset: Option<i64>,
+    pub cursor: Option<Cursor>,
+    pub sort: &'static SortField,
+    pub descending: bool,
+}
+
+impl ListParams {
+    /// Validates the query against the sortable fields of a list. The first field is the default.
+    pub fn parse(query: &PaginationQuery, fields: &'static [SortField]) -> Result<Self, String> {
+        let limit = query.limit.unwrap_or(DEFAULT_LIMIT);
+        if !(1..=MAX_LIMIT).contains(&limit) {
+            return Err(format!("Limit must be between 1 and {}", MAX_LIMIT));
+        }
+
+        if query.offset.is_some() && query.cursor.is_some() {
+            return Err("Offset and cursor cannot be combined".to_string());
+        }
+        if query.offset.is_some_and(|offset| offset < 0) {
+            return Err("Offset cannot be negative".to_string());
+        }
+
+        let sort = match &query.sort {
+            Some(name) => fields.iter().find(|field| field.name == name).ok_or_else(|| {
+                let names: Vec<&str> = fields.iter().map(|field| field.name).collect();
+                format!("Sort must be one of: {}", names.join(", "))
+            })?,
+            None => &fields[0],
+        };
+
+        let descending = match query.order.as_deref().map(str::to_lowercase).as_deref() {
+            None | Some("asc") => false,
+            Some("desc") => true,
+            Some(_) => return Err("Order must be 'asc' or 'desc'".to_string()),
+        };
+
+        let cursor = match &query.cursor {
+            Some(cursor) => Some(
+                Cursor::decode(cursor)
+                    .filter(|cursor| cursor.sort == sort.name && cursor.descending == descending && sort.accepts(&cursor.value))
+                    .ok_or_else(|| "Invalid cursor".to_string())?,
+            ),
+            None => None,
+        };
+
+        Ok(Self { limit, offset: query.offset, cursor, sort, descending })
+    }
+
+    /// Turns the fetched rows (at most `limit + 1`) into a page.
+    ///
+    /// `cursor_of` returns the sort value (as text) and the ID of a row.
+    pub fn build_page<T>(&self, mut rows: Vec<T>, total: i64, cursor_of: impl Fn(&T) -> (String, Uuid)) -> Page<T> {
+        let has_more = rows.len() as i64 > self.limit;
+        rows.truncate(self.limit as usize);
+
+        // Offset pagination knows its next page by number, so only hand out a cursor for cursor pagination
+        let next_cursor = if has_more && self.offset.is_none() {
+            rows.last().map(|row| {
+                let (value, id) = cursor_of(row);
+                Cursor { sort: self.sort.name.to_string(), descending: self.descending, value, id }.encode()
+            })
+        } else {
+            None
+        };
+
+        Page { items: rows, total, limit: self.limit, offset: self.offset, next_cursor }
+    }
+}
+
+/// A query with optional filters, built safely with bound parameters.
+pub struct ListQuery<'args> {
+    builder: QueryBuilder<'args, Postgres>,
+    has_where: bool,
+}
+
+impl<'args> ListQuery<'args> {
+    /// Starts a query from a `SELECT ... FROM ...` without a `WHERE` clause.
+    pub fn new(select: &str) -> Self {
+        Self { builder: QueryBuilder::new(select), has_where: false }
+    }
+
+    fn push_condition(&mut self) {
+        self.builder.push(if self.has_where { " AND " } else { " WHERE " });
+        self.has_where = true;
+    }
+
+    /// Adds `column <operator> value` when a value is given.
+    pub fn filter<T>(&mut self, column: &str, operator: &str, value: Option<T>) -> &mut Self
+    where
+        T: 'args + Encode<'args, Postgres> + Type<Postgres> + Send,
+    {
+        if let Some(value) = value {
+            self.push_condition();
+            self.builder.push(format!("{} {} ", column, operator));
+            self.builder.push_bind(value);
+        }
+        self
+    }
+
+    /// Adds `<before> value <after>` when a value is given, for conditions like subqueries.
+    pub fn filter_expr<T>(&mut self, before: &str, value: Option<T>, after: &str) -> &mut Self
+    where
+        T: 'args + Encode<'args, Postgres> + Type<Postgres> + Send,
+    {
+        if let Some(value) = value {
+            self.push_condition();
+            self.builder.push(before);
+            self.builder.push_bind(value);
+            self.builder.push(after);
+        }
+        self
+    }
+
+    /// Adds the cursor condition, ordering and limit. Fetches one extra row to detect a next page.
+    pub fn paginate(&mut self, params: &ListParams, id_column: &str) -> &mut Self {
+        let direction = if params.descending { "DESC" } else { "ASC" };
+
+        if let Some(cursor) = &params.cursor {
+            self.push_condition();
+            self.builder.push(format!(
+                "({}, {}) {} (CAST(",
+                params.sort.column,
+                id_column,
+                if params.descending { "<" } else { ">" }
+            ));
+            self.builder.push_bind(cursor.value.clone());
+            self.builder.push(format!(" AS {}), ", params.sort.sql_type));
+            self.builder.push_bind(cursor.id);
+            self.builder.push(")");
+        }
+
+        self.builder.push(format!(
+            " ORDER BY {} {}, {} {} LIMIT ",
+            params.sort.column, direction, id_column, direction
+        ));
+        self.builder.push_bind(params.limit + 1);
+
+        if let Some(offset) = params.offset {
+            self.builder.push(" OFFSET ");
+            self.builder.push_bind(offset);
+        }
+        self
+    }
+
+    /// Gives access to the underlying builder, to build and run the query.
+    pub fn builder(&mut self) -> &mut QueryBuilder<'args, Postgres> {
+        &mut self.builder
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIELDS: &[SortField] = &[
+        SortField { name: "creation_date", column: "creation_date", sql_type: "DATE" },
+        SortField { name: "username", column: "username", sql_type: "TEXT" },
+    ];
+
+    fn query(limit: Option<i64>, offset: Option<i64>, cursor: Option<&str>, sort: Option<&str>, order: Option<&str>) -> PaginationQuery {
+        PaginationQuery {
+            limit,
+            offset,
+            cursor: cursor.map(String::from),
+            sort: sort.map(String::from),
+            order: order.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_list_params() {
+        let params = ListParams::parse(&query(None, None, None, None, None), FIELDS).unwrap();
+        assert_eq!(params.limit, DEFAULT_LIMIT);
+        assert_eq!(params.sort.name, "creation_date");
+        assert!(!params.descending);
+
+        let params = ListParams::parse(&query(Some(10), None, None, Some("username"), Some("DESC")), FIELDS).unwrap();
+        assert_eq!(params.sort.column, "username");
+        assert!(params.descending);
+
+        assert!(ListParams::parse(&query(Some(0), None, None, None, None), FIELDS).is_err());
+        assert!(ListParams::parse(&query(None, None, None, Some("password_hash"), None), FIELDS).is_err());
+        assert!(ListParams::parse(&query(None, Some(5), Some("abc"), None, None), FIELDS).is_err());
+        assert!(ListParams::parse(&query(None, None, Some("not a cursor"), None, None), FIELDS).is_err());
+    }
+
+    #[test]
+    fn test_build_page() {
+        let id = Uuid::new_v4();
+        let params = ListParams::parse(&query(Some(2), None, None, None, None), FIELDS).unwrap();
+
+        let page = params.build_page(vec![1, 2, 3], 3, |row| (row.to_string(), id));
+        assert_eq!(page.items, vec![1, 2]);
+        let cursor = Cursor::decode(&page.next_cursor.unwrap()).unwrap();
+        assert_eq!((cursor.value.as_str(), cursor.id), ("2", id));
+
+        let page = params.build_page(vec![1, 2], 2, |row| (row.to_string(), id));
+        assert!(page.next_cursor.is_none());
+    }
+
+    #[test]
+    fn test_cursor_only_continues_its_own_list() {
+        let id = Uuid::new_v4();
+        let params = ListParams::parse(&query(Some(1), None, None, Some("username"), None), FIELDS).unwrap();
+        let cursor = params.build_page(vec!["alice", "bob"], 2, |row| (row.to_string(), id)).next_cursor.unwrap();
+
+        assert!(ListParams::parse(&query(None, None, Some(&cursor), Some("username"), None), FIELDS).is_ok());
+        assert!(ListParams::parse(&query(None, None, Some(&cursor), Some("creation_date"), None), FIELDS).is_err());
+        assert!(ListParams::parse(&query(None, None, Some(&cursor), Some("username"), Some("desc")), FIELDS).is_err());
+
+        // A hand-made cursor with a value the column cannot hold
+        let forged = Cursor { sort: "creation_date".to_string(), descending: false, value: "alice".to_string(), id }.encode();
+        assert!(ListParams::parse(&query(None, None, Some(&forged), None, None), FIELDS).is_err());
+        let valid = Cursor { sort: "creation_date".to_string(), descending: false, value: "2026-10-18".to_string(), id }.encode();
+        assert!(ListParams::parse(&query(None, None, Some(&valid), None, None), FIELDS).is_ok());
+    }
+}
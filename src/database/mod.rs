@@ -1,6 +1,18 @@
 // Module declarations
 pub mod connect;
+pub mod list_query;
 pub mod users;
 pub mod apikeys;
 pub mod usage;
-pub mod todos;
\ No newline at end of file
+pub mod todos;
+pub mod todo_shares;
+pub mod todo_lists;
+pub mod todo_series;
+pub mod todo_attachments;
+pub mod todo_reminders;
+pub mod tiers;
+pub mod billing;
+pub mod data_exports;
+pub mod audit_log;
+pub mod invitations;
+pub mod preferences;
@@ -0,0 +1,45 @@
+use sqlx::postgres::PgPool;
+use serde_json::Value;
+use uuid::Uuid;
+
+/// Retrieves the preferences a user has set, without defaults
+///
+/// # Returns
+/// - `None` when the user never set a preference
+pub async fn fetch_user_preferences_from_db(pool: &PgPool, user_id: Uuid) -> Result<Option<Value>, sqlx::Error> {
+    sqlx::query_scalar!(
+        "SELECT preferences FROM user_preferences WHERE user_id = $1",
+        user_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Sets and resets preferences of a user, leaving the other stored preferences untouched
+///
+/// # Arguments
+/// - `set`: Object with the preferences to store (validated at application layer)
+/// - `reset`: Names of the preferences to remove, so they fall back to their default
+///
+/// # Concurrency
+/// - The merge happens in a single statement, so concurrent updates of different preferences are never lost
+pub async fn update_user_preferences_in_db(
+    pool: &PgPool,
+    user_id: Uuid,
+    set: &Value,
+    reset: &[String],
+) -> Result<Value, sqlx::Error> {
+    sqlx::query_scalar!(
+        "INSERT INTO user_preferences (user_id, preferences)
+        VALUES ($1, $2::jsonb - $3::text[])
+        ON CONFLICT (user_id) DO UPDATE
+        SET preferences = (user_preferences.preferences || $2::jsonb) - $3::text[],
+            updated_at = NOW()
+        RETURNING preferences",
+        user_id,
+        set,
+        reset
+    )
+    .fetch_one(pool)
+    .await
+}
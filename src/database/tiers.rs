@@ -0,0 +1,163 @@
+use sqlx::postgres::PgPool;
+use uuid::Uuid;
+use crate::models::tier::{Tier, TierInsertBody, TierUpdateBody};
+
+/// Retrieves all tiers, ordered by level
+///
+/// # Security
+/// - Only exposes the public tier configuration
+pub async fn fetch_all_tiers_from_db(pool: &PgPool) -> Result<Vec<Tier>, sqlx::Error> {
+    sqlx::query_as!(
+        Tier,
+        "SELECT id, level, name, description, requests_per_day, requests_per_month, attachment_quota_mb, creation_date
+        FROM tiers ORDER BY level"
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Retrieves a tier by its ID
+///
+/// # Security
+/// - Parameterized query prevents SQL injection
+pub async fn fetch_tier_by_id_from_db(pool: &PgPool, id: Uuid) -> Result<Option<Tier>, sqlx::Error> {
+    sqlx::query_as!(
+        Tier,
+        "SELECT id, level, name, description, requests_per_day, requests_per_month, attachment_quota_mb, creation_date
+        FROM tiers WHERE id = $1",
+        id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Retrieves a tier by its level
+///
+/// # Security
+/// - Parameterized query prevents SQL injection
+pub async fn fetch_tier_by_level_from_db(pool: &PgPool, level: i32) -> Result<Option<Tier>, sqlx::Error> {
+    sqlx::query_as!(
+        Tier,
+        "SELECT id, level, name, description, requests_per_day, requests_per_month, attachment_quota_mb, creation_date
+        FROM tiers WHERE level = $1",
+        level
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Retrieves the levels of all existing tiers
+///
+/// # Security
+/// - Used to validate tier levels assigned to users
+pub async fn fetch_tier_levels_from_db(pool: &PgPool) -> Result<Vec<i32>, sqlx::Error> {
+    sqlx::query_scalar!("SELECT level FROM tiers ORDER BY level")
+        .fetch_all(pool)
+        .await
+}
+
+/// Inserts a new tier into the database
+///
+/// # Security
+/// - Uniqueness of name and level is enforced by database constraints
+pub async fn insert_tier_into_db(pool: &PgPool, tier: TierInsertBody) -> Result<Tier, sqlx::Error> {
+    sqlx::query_as!(
+        Tier,
+        "INSERT INTO tiers (level, name, description, requests_per_day, requests_per_month, attachment_quota_mb)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id, level, name, description, requests_per_day, requests_per_month, attachment_quota_mb, creation_date",
+        tier.level,
+        tier.name,
+        tier.description,
+        tier.requests_per_day,
+        tier.requests_per_month,
+        tier.attachment_quota_mb
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Updates a tier, moving its users along when the level changes
+///
+/// # Security
+/// - Runs in a transaction, so users never reference a level that does not exist
+pub async fn update_tier_in_db(pool: &PgPool, id: Uuid, update: TierUpdateBody) -> Result<Option<Tier>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let current_level = sqlx::query_scalar!("SELECT level FROM tiers WHERE id = $1 FOR UPDATE", id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+    let Some(current_level) = current_level else {
+        return Ok(None);
+    };
+
+    let tier = sqlx::query_as!(
+        Tier,
+        "UPDATE tiers SET
+            level = COALESCE($2, level),
+            name = COALESCE($3, name),
+            description = CASE WHEN $4 THEN $5 ELSE description END,
+            requests_per_day = COALESCE($6, requests_per_day),
+            requests_per_month = CASE WHEN $7 THEN $8 ELSE requests_per_month END,
+            attachment_quota_mb = CASE WHEN $9 THEN $10 ELSE attachment_quota_mb END
+        WHERE id = $1
+        RETURNING id, level, name, description, requests_per_day, requests_per_month, attachment_quota_mb, creation_date",
+        id,
+        update.level,
+        update.name,
+        update.description.is_some(),
+        update.description.flatten(),
+        update.requests_per_day,
+        update.requests_per_month.is_some(),
+        update.requests_per_month.flatten(),
+        update.attachment_quota_mb.is_some(),
+        update.attachment_quota_mb.flatten()
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if tier.level != current_level {
+        sqlx::query!(
+            "UPDATE users SET tier_level = $1 WHERE tier_level = $2",
+            tier.level,
+            current_level
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+    Ok(Some(tier))
+}
+
+/// Counts the users assigned to a tier level
+///
+/// # Security
+/// - Used to prevent deleting tiers that are still in use
+pub async fn count_users_with_tier_level_from_db(pool: &PgPool, level: i32) -> Result<i64, sqlx::Error> {
+    let count = sqlx::query_scalar!("SELECT COUNT(*) FROM users WHERE tier_level = $1", level)
+        .fetch_one(pool)
+        .await?;
+
+    Ok(count.unwrap_or(0))
+}
+
+/// Deletes a tier from the database, unless users are still assigned to it or have held it
+///
+/// Tiers in the tier history of users are kept, as periods that have not been billed yet need them.
+///
+/// # Security
+/// - The usage check is part of the delete, so a concurrent assignment cannot orphan users
+pub async fn delete_tier_from_db(pool: &PgPool, id: Uuid) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query!(
+        "DELETE FROM tiers WHERE id = $1
+        AND NOT EXISTS (SELECT 1 FROM users WHERE users.tier_level = tiers.level)
+        AND NOT EXISTS (SELECT 1 FROM user_tier_history WHERE user_tier_history.tier_id = tiers.id)",
+        id
+    )
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected())
+}
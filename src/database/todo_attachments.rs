@@ -0,0 +1,140 @@
+use sqlx::postgres::PgPool;
+use uuid::Uuid;
+use crate::models::todo::TodoAttachment;
+
+/// Stores the metadata of an uploaded attachment, if it fits in the storage quota of the todo owner
+///
+/// # Arguments
+/// - `attachment`: The attachment, its `user_id` is the owner of the todo
+/// - `quota`: The amount of bytes all attachments of the owner may take up together
+///
+/// # Returns
+/// - `Ok(false)` when the attachment does not fit in the quota, nothing is stored then
+///
+/// # Concurrency
+/// - The owner is locked while the quota is checked, so parallel uploads cannot exceed it together
+pub async fn insert_todo_attachment_into_db(
+    pool: &PgPool,
+    attachment: &TodoAttachment,
+    quota: i64,
+) -> Result<bool, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query!("SELECT id FROM users WHERE id = $1 FOR UPDATE", attachment.user_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+    let used = sqlx::query_scalar!(
+        r#"SELECT COALESCE(SUM(size), 0)::BIGINT AS "used!" FROM todo_attachments WHERE user_id = $1"#,
+        attachment.user_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    if used + attachment.size > quota {
+        return Ok(false);
+    }
+
+    sqlx::query!(
+        "INSERT INTO todo_attachments (id, todo_id, user_id, uploaded_by, file_name, content_type, size, object_key, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+        attachment.id,
+        attachment.todo_id,
+        attachment.user_id,
+        attachment.uploaded_by,
+        attachment.file_name,
+        attachment.content_type,
+        attachment.size,
+        attachment.object_key,
+        attachment.created_at
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(true)
+}
+
+/// Retrieves the attachment quota of the tier of a user, in MB, `None` when the tier uses the default
+pub async fn fetch_attachment_quota_mb_from_db(pool: &PgPool, user_id: Uuid) -> Result<Option<i32>, sqlx::Error> {
+    let quota = sqlx::query_scalar!(
+        "SELECT t.attachment_quota_mb FROM users u JOIN tiers t ON t.level = u.tier_level WHERE u.id = $1",
+        user_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(quota.flatten())
+}
+
+/// Retrieves the storage all attachments of a user take up, in bytes
+pub async fn fetch_attachment_storage_used_from_db(pool: &PgPool, user_id: Uuid) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"SELECT COALESCE(SUM(size), 0)::BIGINT AS "used!" FROM todo_attachments WHERE user_id = $1"#,
+        user_id
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Retrieves the attachments of a todo, oldest first
+///
+/// # Security
+/// - Access to the todo is verified at application layer
+pub async fn fetch_todo_attachments_from_db(pool: &PgPool, todo_id: Uuid) -> Result<Vec<TodoAttachment>, sqlx::Error> {
+    sqlx::query_as!(
+        TodoAttachment,
+        "SELECT id, todo_id, user_id, uploaded_by, file_name, content_type, size, object_key, created_at
+        FROM todo_attachments WHERE todo_id = $1
+        ORDER BY created_at",
+        todo_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Retrieves a single attachment of a todo
+///
+/// # Security
+/// - Combines ID and todo_id in WHERE clause, access to the todo is verified at application layer
+pub async fn fetch_todo_attachment_from_db(
+    pool: &PgPool,
+    id: Uuid,
+    todo_id: Uuid,
+) -> Result<Option<TodoAttachment>, sqlx::Error> {
+    sqlx::query_as!(
+        TodoAttachment,
+        "SELECT id, todo_id, user_id, uploaded_by, file_name, content_type, size, object_key, created_at
+        FROM todo_attachments WHERE id = $1 AND todo_id = $2",
+        id,
+        todo_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Retrieves all attachments of the todos a user owns, for the data export
+pub async fn fetch_todo_attachments_by_user_from_db(pool: &PgPool, user_id: Uuid) -> Result<Vec<TodoAttachment>, sqlx::Error> {
+    sqlx::query_as!(
+        TodoAttachment,
+        "SELECT id, todo_id, user_id, uploaded_by, file_name, content_type, size, object_key, created_at
+        FROM todo_attachments WHERE user_id = $1
+        ORDER BY created_at",
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Deletes an attachment and returns its object key, to remove the file from storage
+///
+/// # Security
+/// - Combines ID and todo_id in WHERE clause, access to the todo is verified at application layer
+pub async fn delete_todo_attachment_from_db(pool: &PgPool, id: Uuid, todo_id: Uuid) -> Result<Option<String>, sqlx::Error> {
+    sqlx::query_scalar!(
+        "DELETE FROM todo_attachments WHERE id = $1 AND todo_id = $2 RETURNING object_key",
+        id,
+        todo_id
+    )
+    .fetch_optional(pool)
+    .await
+}
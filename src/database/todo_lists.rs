@@ -0,0 +1,207 @@
+use sqlx::postgres::PgPool;
+use uuid::Uuid;
+use crate::models::todo::TodoList;
+
+/// Creates a list at the end of the lists of a user
+///
+/// # Security
+/// - The name is unique per user (case-insensitive), a duplicate fails on the unique index
+pub async fn insert_todo_list_into_db(
+    pool: &PgPool,
+    user_id: Uuid,
+    name: &str,
+    description: Option<&str>,
+) -> Result<TodoList, sqlx::Error> {
+    sqlx::query_as!(
+        TodoList,
+        r#"INSERT INTO todo_lists (user_id, name, description, position)
+        VALUES ($1, $2, $3, (SELECT COALESCE(MAX(position) + 1, 0) FROM todo_lists WHERE user_id = $1))
+        RETURNING id, user_id, name, description, position, creation_date,
+            0::BIGINT AS "todos_total!", 0::BIGINT AS "todos_completed!""#,
+        user_id,
+        name,
+        description
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Retrieves all lists of a user in their manual order, with the amount of (completed) todos
+///
+/// # Security
+/// - Uses WHERE clause with user_id to ensure data isolation
+pub async fn fetch_todo_lists_from_db(pool: &PgPool, user_id: Uuid) -> Result<Vec<TodoList>, sqlx::Error> {
+    sqlx::query_as!(
+        TodoList,
+        r#"SELECT id, user_id, name, description, position, creation_date,
+            (SELECT COUNT(*) FROM todos t WHERE t.list_id = todo_lists.id) AS "todos_total!",
+            (SELECT COUNT(*) FROM todos t WHERE t.list_id = todo_lists.id AND COALESCE(t.completed, FALSE)) AS "todos_completed!"
+        FROM todo_lists WHERE user_id = $1
+        ORDER BY position, creation_date"#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Retrieves the lists of a user followed by the lists shared with the user, with the amount of (completed) todos
+///
+/// # Security
+/// - Only lists owned by or shared with the user are included
+pub async fn fetch_accessible_todo_lists_from_db(pool: &PgPool, user_id: Uuid) -> Result<Vec<TodoList>, sqlx::Error> {
+    sqlx::query_as!(
+        TodoList,
+        r#"SELECT id, user_id, name, description, position, creation_date,
+            (SELECT COUNT(*) FROM todos t WHERE t.list_id = todo_lists.id) AS "todos_total!",
+            (SELECT COUNT(*) FROM todos t WHERE t.list_id = todo_lists.id AND COALESCE(t.completed, FALSE)) AS "todos_completed!"
+        FROM todo_lists
+        WHERE user_id = $1 OR EXISTS (SELECT 1 FROM todo_list_shares s WHERE s.list_id = todo_lists.id AND s.user_id = $1)
+        ORDER BY user_id <> $1, position, creation_date"#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Retrieves a single list the user owns or that is shared with the user
+///
+/// # Security
+/// - Returns None for lists of other users that are not shared with the user
+/// - Whether the user may change or share the list is verified at application layer
+pub async fn fetch_accessible_todo_list_by_id_from_db(pool: &PgPool, id: Uuid, user_id: Uuid) -> Result<Option<TodoList>, sqlx::Error> {
+    sqlx::query_as!(
+        TodoList,
+        r#"SELECT id, user_id, name, description, position, creation_date,
+            (SELECT COUNT(*) FROM todos t WHERE t.list_id = todo_lists.id) AS "todos_total!",
+            (SELECT COUNT(*) FROM todos t WHERE t.list_id = todo_lists.id AND COALESCE(t.completed, FALSE)) AS "todos_completed!"
+        FROM todo_lists
+        WHERE id = $1
+            AND (user_id = $2 OR EXISTS (SELECT 1 FROM todo_list_shares s WHERE s.list_id = $1 AND s.user_id = $2))"#,
+        id,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Retrieves a single list of a user
+///
+/// # Security
+/// - Combines ID and user_id in WHERE clause to prevent unauthorized access
+pub async fn fetch_todo_list_by_id_from_db(pool: &PgPool, id: Uuid, user_id: Uuid) -> Result<Option<TodoList>, sqlx::Error> {
+    sqlx::query_as!(
+        TodoList,
+        r#"SELECT id, user_id, name, description, position, creation_date,
+            (SELECT COUNT(*) FROM todos t WHERE t.list_id = todo_lists.id) AS "todos_total!",
+            (SELECT COUNT(*) FROM todos t WHERE t.list_id = todo_lists.id AND COALESCE(t.completed, FALSE)) AS "todos_completed!"
+        FROM todo_lists WHERE id = $1 AND user_id = $2"#,
+        id,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Updates the name, description and position of a list
+///
+/// # Arguments
+/// - `name`, `description`: The new values (validated at application layer)
+/// - `position`: The new position, the lists from this position on move down, or `None` to keep it
+///
+/// # Security
+/// - Combines ID and user_id in WHERE clause to prevent unauthorized access
+///
+/// # Concurrency
+/// - The lists of the user are locked while positions are shifted
+pub async fn update_todo_list_in_db(
+    pool: &PgPool,
+    id: Uuid,
+    user_id: Uuid,
+    name: &str,
+    description: Option<&str>,
+    position: Option<i32>,
+) -> Result<Option<TodoList>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let lists = sqlx::query_scalar!("SELECT id FROM todo_lists WHERE user_id = $1 FOR UPDATE", user_id)
+        .fetch_all(&mut *tx)
+        .await?;
+    if !lists.contains(&id) {
+        return Ok(None);
+    }
+
+    if let Some(position) = position {
+        sqlx::query!(
+            "UPDATE todo_lists SET position = position + 1 WHERE user_id = $1 AND id <> $2 AND position >= $3",
+            user_id,
+            id,
+            position
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    let list = sqlx::query_as!(
+        TodoList,
+        r#"UPDATE todo_lists
+        SET name = $3, description = $4, position = COALESCE($5, position)
+        WHERE id = $1 AND user_id = $2
+        RETURNING id, user_id, name, description, position, creation_date,
+            (SELECT COUNT(*) FROM todos t WHERE t.list_id = todo_lists.id) AS "todos_total!",
+            (SELECT COUNT(*) FROM todos t WHERE t.list_id = todo_lists.id AND COALESCE(t.completed, FALSE)) AS "todos_completed!""#,
+        id,
+        user_id,
+        name,
+        description,
+        position
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(list)
+}
+
+/// Deletes a list with all of its todos
+///
+/// # Returns
+/// - `None` when the user has no such list
+/// - The object keys of the attachments of the removed todos otherwise, to delete the files from storage
+///
+/// # Security
+/// - Requires both ID and user_id for deletion
+///
+/// # Concurrency
+/// - The todos of the list are locked first, so no attachment can be added while the keys are collected
+pub async fn delete_todo_list_from_db(pool: &PgPool, id: Uuid, user_id: Uuid) -> Result<Option<Vec<String>>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let todos = sqlx::query_scalar!(
+        "SELECT id FROM todos WHERE list_id = (SELECT id FROM todo_lists WHERE id = $1 AND user_id = $2) FOR UPDATE",
+        id,
+        user_id
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let object_keys = sqlx::query_scalar!(
+        "SELECT object_key FROM todo_attachments WHERE todo_id = ANY($1)",
+        &todos
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let result = sqlx::query!(
+        "DELETE FROM todo_lists WHERE id = $1 AND user_id = $2",
+        id,
+        user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+    if result.rows_affected() == 0 {
+        return Ok(None);
+    }
+
+    tx.commit().await?;
+    Ok(Some(object_keys))
+}
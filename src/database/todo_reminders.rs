@@ -0,0 +1,146 @@
+use sqlx::postgres::PgPool;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use crate::models::todo::TodoReminderRow;
+use crate::utils::reminders::MAX_REMINDER_LEAD_MINUTES;
+
+/// Retrieves a batch of open todos of active users with a reminder to send, in order of due date
+///
+/// Only todos with a lead time that has passed and whose reminder is not claimed yet are returned,
+/// using the lead times and opt-out of the user. Claims of instances that stopped before sending
+/// (claimed before `stale_before`) no longer count. Lead times of a user that are no whole number of
+/// minutes between 1 and `MAX_REMINDER_LEAD_MINUTES` are left out, rather than failing the batch.
+///
+/// # Arguments
+/// - `until`: The latest due date that can have a reminder now (now plus the longest lead time)
+/// - `defaults`: The lead times and whether reminders are sent, for users that did not set these preferences
+/// - `after`: The due date and ID of the last todo of the previous batch, for keyset pagination
+/// - `limit`: The size of the batch
+pub async fn fetch_upcoming_todo_reminders_from_db(
+    pool: &PgPool,
+    until: DateTime<Utc>,
+    defaults: (&[i32], bool),
+    stale_before: DateTime<Utc>,
+    after: (DateTime<Utc>, Uuid),
+    limit: i64,
+) -> Result<Vec<TodoReminderRow>, sqlx::Error> {
+    let (default_lead_minutes, default_enabled) = defaults;
+    let (after_due_at, after_id) = after;
+    sqlx::query_as!(
+        TodoReminderRow,
+        r#"SELECT t.id AS todo_id, t.task, t.due_at AS "due_at!", t.due_timezone,
+            u.id AS user_id, u.email, u.language_code::TEXT AS language_code,
+            COALESCE(p.preferences, '{}'::JSONB) AS "preferences!",
+            ARRAY(SELECT r.lead_minutes FROM todo_reminders r
+                WHERE r.todo_id = t.id AND r.due_at = t.due_at
+                AND (r.status = 'sent' OR r.claimed_at >= $3)) AS "claimed!"
+        FROM todos t
+        JOIN users u ON u.id = t.user_id
+        LEFT JOIN user_preferences p ON p.user_id = u.id
+        CROSS JOIN LATERAL (
+            SELECT CASE WHEN jsonb_typeof(p.preferences->'todo_reminder_lead_minutes') = 'array'
+                THEN ARRAY(SELECT lead.minutes::INT FROM (
+                    SELECT CASE WHEN jsonb_typeof(value) = 'number' THEN value::NUMERIC END AS minutes
+                    FROM jsonb_array_elements(p.preferences->'todo_reminder_lead_minutes')
+                ) lead WHERE lead.minutes BETWEEN 1 AND $8 AND lead.minutes = TRUNC(lead.minutes))
+                ELSE $4::INT[]
+            END AS minutes
+        ) leads
+        WHERE t.due_at > NOW() AND t.due_at <= $1
+        AND t.completed IS NOT TRUE
+        AND u.status = 'active'
+        AND COALESCE((p.preferences->>'email_todo_reminders')::BOOLEAN, $5)
+        AND EXISTS (
+            SELECT 1 FROM UNNEST(leads.minutes) AS lead(minutes)
+            WHERE t.due_at - make_interval(mins => lead.minutes) <= NOW()
+            AND NOT EXISTS (
+                SELECT 1 FROM todo_reminders r
+                WHERE r.todo_id = t.id AND r.due_at = t.due_at AND r.lead_minutes = lead.minutes
+                AND (r.status = 'sent' OR r.claimed_at >= $3)
+            )
+        )
+        AND (t.due_at, t.id) > ($2, $6)
+        ORDER BY t.due_at, t.id
+        LIMIT $7"#,
+        until,
+        after_due_at,
+        stale_before,
+        default_lead_minutes,
+        default_enabled,
+        after_id,
+        limit,
+        MAX_REMINDER_LEAD_MINUTES as i32
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Claims the reminders of a todo for its current due date, and returns the lead times that were claimed
+///
+/// A claim that is still 'sending' since before `stale_before` belongs to an instance that stopped
+/// before it could send the reminder, so it is taken over. Should that instance have sent the email
+/// after all, the reminder is sent twice rather than not at all.
+///
+/// # Concurrency
+/// - A reminder is claimed by one instance only, the others get nothing back for it
+pub async fn claim_todo_reminders_in_db(
+    pool: &PgPool,
+    todo_id: Uuid,
+    due_at: DateTime<Utc>,
+    lead_minutes: &[i32],
+    stale_before: DateTime<Utc>,
+) -> Result<Vec<i32>, sqlx::Error> {
+    sqlx::query_scalar!(
+        "INSERT INTO todo_reminders (todo_id, due_at, lead_minutes)
+        SELECT $1, $2, UNNEST($3::INT[])
+        ON CONFLICT (todo_id, due_at, lead_minutes) DO UPDATE SET claimed_at = NOW()
+        WHERE todo_reminders.status = 'sending' AND todo_reminders.claimed_at < $4
+        RETURNING lead_minutes",
+        todo_id,
+        due_at,
+        lead_minutes,
+        stale_before
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Marks claimed reminders as sent
+pub async fn complete_todo_reminders_in_db(
+    pool: &PgPool,
+    todo_id: Uuid,
+    due_at: DateTime<Utc>,
+    lead_minutes: &[i32],
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "UPDATE todo_reminders SET status = 'sent', sent_at = NOW()
+        WHERE todo_id = $1 AND due_at = $2 AND lead_minutes = ANY($3)",
+        todo_id,
+        due_at,
+        lead_minutes
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Releases claimed reminders that could not be sent, so that they are retried
+pub async fn release_todo_reminders_in_db(
+    pool: &PgPool,
+    todo_id: Uuid,
+    due_at: DateTime<Utc>,
+    lead_minutes: &[i32],
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "DELETE FROM todo_reminders
+        WHERE todo_id = $1 AND due_at = $2 AND lead_minutes = ANY($3) AND status = 'sending'",
+        todo_id,
+        due_at,
+        lead_minutes
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,137 @@
+use sqlx::postgres::PgPool;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use crate::models::todo::{TodoBody, TodoSeries};
+
+/// Retrieves a series of a user, with its latest occurrence and skipped occurrences
+///
+/// # Security
+/// - Combines ID and user_id in WHERE clause to prevent unauthorized access
+pub async fn fetch_todo_series_by_id_from_db(pool: &PgPool, id: Uuid, user_id: Uuid) -> Result<Option<TodoSeries>, sqlx::Error> {
+    sqlx::query_as!(
+        TodoSeries,
+        r#"SELECT id, user_id, rrule, dtstart, timezone, task, description, priority, tags, list_id, created_at,
+            (SELECT MAX(occurrence_at) FROM todos WHERE series_id = todo_series.id) AS latest_occurrence_at,
+            ARRAY(SELECT occurrence_at FROM todo_series_skips WHERE series_id = todo_series.id
+                ORDER BY occurrence_at) AS "skipped!"
+        FROM todo_series WHERE id = $1 AND user_id = $2"#,
+        id,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Retrieves all series of a user
+///
+/// # Security
+/// - Uses WHERE clause with user_id to ensure data isolation
+pub async fn fetch_todo_series_from_db(pool: &PgPool, user_id: Uuid) -> Result<Vec<TodoSeries>, sqlx::Error> {
+    sqlx::query_as!(
+        TodoSeries,
+        r#"SELECT id, user_id, rrule, dtstart, timezone, task, description, priority, tags, list_id, created_at,
+            (SELECT MAX(occurrence_at) FROM todos WHERE series_id = todo_series.id) AS latest_occurrence_at,
+            ARRAY(SELECT occurrence_at FROM todo_series_skips WHERE series_id = todo_series.id
+                ORDER BY occurrence_at) AS "skipped!"
+        FROM todo_series WHERE user_id = $1
+        ORDER BY created_at"#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Updates the rule and template of a series
+///
+/// # Arguments
+/// - `template`: The new rule (`recurrence`) and template, validated at application layer.
+///   They apply to the occurrences created from now on.
+///
+/// # Security
+/// - Combines ID and user_id in WHERE clause to prevent unauthorized access
+pub async fn update_todo_series_in_db(
+    pool: &PgPool,
+    id: Uuid,
+    user_id: Uuid,
+    template: &TodoBody,
+) -> Result<Option<TodoSeries>, sqlx::Error> {
+    let tags: Vec<String> = template.tags.iter().map(|tag| tag.trim().to_string()).collect();
+
+    sqlx::query_as!(
+        TodoSeries,
+        r#"UPDATE todo_series
+        SET rrule = $3, task = $4, description = $5, priority = $6, tags = $7
+        WHERE id = $1 AND user_id = $2
+        RETURNING id, user_id, rrule, dtstart, timezone, task, description, priority, tags, list_id, created_at,
+            (SELECT MAX(occurrence_at) FROM todos WHERE series_id = todo_series.id) AS latest_occurrence_at,
+            ARRAY(SELECT occurrence_at FROM todo_series_skips WHERE series_id = todo_series.id
+                ORDER BY occurrence_at) AS "skipped!""#,
+        id,
+        user_id,
+        template.recurrence.as_deref().map(str::trim),
+        template.task.trim(),
+        template.description.as_deref().map(str::trim).filter(|d| !d.is_empty()),
+        template.priority,
+        &tags
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Skips a single occurrence of a series, skipping it again changes nothing
+///
+/// # Security
+/// - Ownership of the series is verified at application layer
+pub async fn insert_todo_series_skip_into_db(
+    pool: &PgPool,
+    series_id: Uuid,
+    occurrence_at: DateTime<Utc>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "INSERT INTO todo_series_skips (series_id, occurrence_at) VALUES ($1, $2)
+        ON CONFLICT DO NOTHING",
+        series_id,
+        occurrence_at
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Stops a series, the todos it already created stay as regular todos
+///
+/// # Security
+/// - Requires both ID and user_id for deletion
+pub async fn delete_todo_series_from_db(pool: &PgPool, id: Uuid, user_id: Uuid) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query!(
+        "DELETE FROM todo_series WHERE id = $1 AND user_id = $2",
+        id,
+        user_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Retrieves the todo created for an occurrence of a series, with whether it is completed
+///
+/// # Security
+/// - Ownership of the series is verified at application layer
+pub async fn fetch_todo_occurrence_from_db(
+    pool: &PgPool,
+    series_id: Uuid,
+    occurrence_at: DateTime<Utc>,
+) -> Result<Option<(Uuid, bool)>, sqlx::Error> {
+    let todo = sqlx::query!(
+        r#"SELECT id, COALESCE(completed, FALSE) AS "completed!"
+        FROM todos WHERE series_id = $1 AND occurrence_at = $2"#,
+        series_id,
+        occurrence_at
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(todo.map(|todo| (todo.id, todo.completed)))
+}
@@ -0,0 +1,199 @@
+use sqlx::postgres::PgPool;
+use uuid::Uuid;
+use crate::models::todo::{TodoListShare, TodoShare};
+
+/// Shares a todo with a user, or changes the permission of an existing share
+///
+/// # Security
+/// - Ownership of the todo and the status of the user are verified at application layer
+///
+/// # Concurrency
+/// - Upserts on (todo_id, user_id), so sharing twice never creates two shares
+pub async fn upsert_todo_share_in_db(
+    pool: &PgPool,
+    todo_id: Uuid,
+    user_id: Uuid,
+    permission: &str,
+    shared_by: Uuid,
+) -> Result<TodoShare, sqlx::Error> {
+    sqlx::query_as!(
+        TodoShare,
+        r#"WITH share AS (
+            INSERT INTO todo_shares (todo_id, user_id, permission, shared_by)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (todo_id, user_id)
+            DO UPDATE SET permission = EXCLUDED.permission, created_at = NOW()
+            RETURNING todo_id, user_id, permission, shared_by, created_at
+        )
+        SELECT share.todo_id, share.user_id, users.username, share.permission, share.shared_by, share.created_at
+        FROM share JOIN users ON users.id = share.user_id"#,
+        todo_id,
+        user_id,
+        permission,
+        shared_by
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Retrieves all users a todo is shared with, in the order it was shared
+pub async fn fetch_todo_shares_from_db(pool: &PgPool, todo_id: Uuid) -> Result<Vec<TodoShare>, sqlx::Error> {
+    sqlx::query_as!(
+        TodoShare,
+        "SELECT s.todo_id, s.user_id, u.username, s.permission, s.shared_by, s.created_at
+        FROM todo_shares s JOIN users u ON u.id = s.user_id
+        WHERE s.todo_id = $1
+        ORDER BY s.created_at, s.user_id",
+        todo_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Retrieves the share of a todo with a single user, if any
+pub async fn fetch_todo_share_from_db(pool: &PgPool, todo_id: Uuid, user_id: Uuid) -> Result<Option<TodoShare>, sqlx::Error> {
+    sqlx::query_as!(
+        TodoShare,
+        "SELECT s.todo_id, s.user_id, u.username, s.permission, s.shared_by, s.created_at
+        FROM todo_shares s JOIN users u ON u.id = s.user_id
+        WHERE s.todo_id = $1 AND s.user_id = $2",
+        todo_id,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Revokes the access of a user to a todo
+///
+/// # Returns
+/// The amount of revoked shares (0 or 1)
+pub async fn delete_todo_share_from_db(pool: &PgPool, todo_id: Uuid, user_id: Uuid) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query!(
+        "DELETE FROM todo_shares WHERE todo_id = $1 AND user_id = $2",
+        todo_id,
+        user_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Retrieves the active user with this username that the owner can share with
+///
+/// # Security
+/// - Only users in the same tenant as the owner are returned (or without a tenant, like the owner),
+///   so users of other tenants cannot be found by probing usernames
+pub async fn fetch_share_recipient_from_db(pool: &PgPool, username: &str, owner_id: Uuid) -> Result<Option<Uuid>, sqlx::Error> {
+    sqlx::query_scalar!(
+        "SELECT recipient.id
+        FROM users recipient JOIN users owner ON owner.id = $2
+        WHERE recipient.username = $1 AND recipient.status = 'active'
+            AND recipient.tenant IS NOT DISTINCT FROM owner.tenant",
+        username,
+        owner_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Retrieves the permission of a user on a todo it does not own, through a share of the todo or of its list
+///
+/// # Returns
+/// - `None` when the todo is not shared with the user
+/// - "editor" when either share allows editing, "viewer" otherwise
+pub async fn fetch_todo_permission_from_db(pool: &PgPool, todo_id: Uuid, user_id: Uuid) -> Result<Option<String>, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"SELECT permission AS "permission!" FROM (
+            SELECT permission FROM todo_shares WHERE todo_id = $1 AND user_id = $2
+            UNION ALL
+            SELECT s.permission FROM todo_list_shares s JOIN todos t ON t.list_id = s.list_id
+            WHERE t.id = $1 AND s.user_id = $2
+        ) shares
+        ORDER BY permission = 'editor' DESC
+        LIMIT 1"#,
+        todo_id,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Shares a list with a user, or changes the permission of an existing share
+///
+/// # Security
+/// - Ownership of the list and the tenant and status of the user are verified at application layer
+///
+/// # Concurrency
+/// - Upserts on (list_id, user_id), so sharing twice never creates two shares
+pub async fn upsert_todo_list_share_in_db(
+    pool: &PgPool,
+    list_id: Uuid,
+    user_id: Uuid,
+    permission: &str,
+    shared_by: Uuid,
+) -> Result<TodoListShare, sqlx::Error> {
+    sqlx::query_as!(
+        TodoListShare,
+        r#"WITH share AS (
+            INSERT INTO todo_list_shares (list_id, user_id, permission, shared_by)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (list_id, user_id)
+            DO UPDATE SET permission = EXCLUDED.permission, created_at = NOW()
+            RETURNING list_id, user_id, permission, shared_by, created_at
+        )
+        SELECT share.list_id, share.user_id, users.username, share.permission, share.shared_by, share.created_at
+        FROM share JOIN users ON users.id = share.user_id"#,
+        list_id,
+        user_id,
+        permission,
+        shared_by
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Retrieves all users a list is shared with, in the order it was shared
+pub async fn fetch_todo_list_shares_from_db(pool: &PgPool, list_id: Uuid) -> Result<Vec<TodoListShare>, sqlx::Error> {
+    sqlx::query_as!(
+        TodoListShare,
+        "SELECT s.list_id, s.user_id, u.username, s.permission, s.shared_by, s.created_at
+        FROM todo_list_shares s JOIN users u ON u.id = s.user_id
+        WHERE s.list_id = $1
+        ORDER BY s.created_at, s.user_id",
+        list_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Retrieves the share of a list with a single user, if any
+pub async fn fetch_todo_list_share_from_db(pool: &PgPool, list_id: Uuid, user_id: Uuid) -> Result<Option<TodoListShare>, sqlx::Error> {
+    sqlx::query_as!(
+        TodoListShare,
+        "SELECT s.list_id, s.user_id, u.username, s.permission, s.shared_by, s.created_at
+        FROM todo_list_shares s JOIN users u ON u.id = s.user_id
+        WHERE s.list_id = $1 AND s.user_id = $2",
+        list_id,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Revokes the access of a user to a list and its todos
+///
+/// # Returns
+/// The amount of revoked shares (0 or 1)
+pub async fn delete_todo_list_share_from_db(pool: &PgPool, list_id: Uuid, user_id: Uuid) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query!(
+        "DELETE FROM todo_list_shares WHERE list_id = $1 AND user_id = $2",
+        list_id,
+        user_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
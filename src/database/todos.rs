@@ -1,6 +1,34 @@
 use sqlx::postgres::PgPool;
+use sqlx::{Postgres, Transaction};
 use uuid::Uuid;
+use chrono::NaiveDate;
+use crate::database::list_query::{ListParams, ListQuery, SortField};
+use crate::models::pagination::Page;
 use crate::models::todo::*;
+use chrono::{DateTime, Utc};
+
+/// Fields the todo list can be sorted on, the first one is the default
+///
+/// Todos without a due date sort after all dated todos.
+pub const TODO_SORT_FIELDS: &[SortField] = &[
+    SortField { name: "creation_date", column: "creation_date", sql_type: "DATE" },
+    SortField { name: "due_at", column: "COALESCE(due_at, 'infinity')", sql_type: "TIMESTAMPTZ" },
+    SortField { name: "priority", column: "priority", sql_type: "SMALLINT" },
+    SortField { name: "position", column: "position", sql_type: "INT" },
+];
+
+// Columns of a todo for the dynamically built list query, including its tags
+const TODO_LIST_SELECT: &str = "SELECT id, user_id, task, description, creation_date, completion_date, completed,
+    due_at, due_timezone, priority, list_id, parent_id, position, series_id, occurrence_at,
+    ARRAY(SELECT t.name FROM todo_tag_links l JOIN todo_tags t ON t.id = l.tag_id
+        WHERE l.todo_id = todos.id ORDER BY LOWER(t.name)) AS tags,
+    (SELECT COUNT(*) FROM todos s WHERE s.parent_id = todos.id) AS subtasks_total,
+    (SELECT COUNT(*) FROM todos s WHERE s.parent_id = todos.id AND COALESCE(s.completed, FALSE)) AS subtasks_completed
+    FROM todos";
+
+// The users a todo is shared with, directly or through its list, left open so that the owner can be added
+const TODO_SHARED_WITH: &str = "(SELECT s.user_id FROM todo_shares s WHERE s.todo_id = todos.id
+    UNION ALL SELECT ls.user_id FROM todo_list_shares ls WHERE ls.list_id = todos.list_id";
 
 /// Inserts a new Todo into the database with robust input validation and ownership enforcement
 ///
@@ -12,14 +40,21 @@ use crate::models::todo::*;
 /// # Security
 /// - Uses parameterized queries to prevent SQL injection
 /// - Trims input to prevent whitespace abuse
+/// - The list and parent are verified to belong to the user at application layer
+///
+/// # Recurrence
+/// - With a recurrence rule, a series is created with the todo as template and first occurrence
+///
+/// # Concurrency
+/// - The todo, its tags and its series are inserted in a single transaction
+/// - The todo is added at the end of its list (or parent), concurrent inserts can share a position
 pub async fn insert_todo_into_db(
     pool: &PgPool,
-    task: String,
-    description: Option<String>,
+    todo: &TodoBody,
     user_id: Uuid,
 ) -> Result<Todo, sqlx::Error> {
     // Sanitize and validate task
-    let task = task.trim();
+    let task = todo.task.trim();
     if task.is_empty() {
         return Err(sqlx::Error::Protocol("Task cannot be empty".into()));
     }
@@ -28,30 +63,132 @@ pub async fn insert_todo_into_db(
     }
 
     // Sanitize and validate optional description
-    let description = description.map(|d| d.trim().to_string())
+    let description = todo.description.as_deref().map(str::trim)
         .filter(|d| !d.is_empty());
-    if let Some(desc) = &description {
+    if let Some(desc) = description {
         if desc.len() > 500 {
             return Err(sqlx::Error::Protocol("Description exceeds maximum length of 500 characters".into()));
         }
     }
 
+    let mut tx = pool.begin().await?;
+
+    // Recurring todos need a due date to repeat from (verified at application layer)
+    let series = match (&todo.recurrence, todo.due_at) {
+        (Some(rrule), Some(due_at)) => {
+            let tags: Vec<String> = todo.tags.iter().map(|tag| tag.trim().to_string()).collect();
+            let series_id = sqlx::query_scalar!(
+                "INSERT INTO todo_series (user_id, rrule, dtstart, timezone, task, description, priority, tags, list_id)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                RETURNING id",
+                user_id,
+                rrule.trim(),
+                due_at,
+                todo.due_timezone.as_deref().unwrap_or("UTC"),
+                task,
+                description,
+                todo.priority,
+                &tags,
+                todo.list_id
+            )
+            .fetch_one(&mut *tx)
+            .await?;
+            Some((series_id, due_at))
+        }
+        _ => None,
+    };
+
     // Insert with ownership enforcement
-    let row = sqlx::query_as!(
-        Todo,
-        "INSERT INTO todos (task, description, user_id) 
-        VALUES ($1, $2, $3) 
-        RETURNING id, user_id, task, description, creation_date, completion_date, completed",
+    let id = sqlx::query_scalar!(
+        "INSERT INTO todos (task, description, user_id, due_at, due_timezone, priority, list_id, parent_id,
+            series_id, occurrence_at, position)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, (
+            SELECT COALESCE(MAX(position) + 1, 0) FROM todos
+            WHERE user_id = $3 AND list_id IS NOT DISTINCT FROM $7 AND parent_id IS NOT DISTINCT FROM $8
+        ))
+        RETURNING id",
         task,
         description,
-        user_id
+        user_id,
+        todo.due_at,
+        todo.due_timezone,
+        todo.priority,
+        todo.list_id,
+        todo.parent_id,
+        series.map(|(series_id, _)| series_id),
+        series.map(|(_, occurrence_at)| occurrence_at)
     )
-    .fetch_one(pool)
+    .fetch_one(&mut *tx)
     .await?;
 
+    replace_todo_tags(&mut tx, id, user_id, &todo.tags).await?;
+    let row = fetch_todo_in_tx(&mut tx, id).await?;
+    tx.commit().await?;
+
     Ok(row)
 }
 
+/// Creates an occurrence of a series as a new todo, from the template in the series
+///
+/// # Arguments
+/// - `occurrence_at`: The scheduled time of the occurrence, which is also its due date
+/// - `previous_id`: The occurrence it follows up on, whose shares are copied to the new occurrence
+///
+/// # Returns
+/// The new todo, or `None` when the occurrence already exists
+///
+/// # Concurrency
+/// - Unique per series and occurrence, so completing an occurrence twice creates its successor once
+pub async fn insert_todo_occurrence_into_db(
+    pool: &PgPool,
+    series: &TodoSeries,
+    occurrence_at: DateTime<Utc>,
+    previous_id: Option<Uuid>,
+) -> Result<Option<Todo>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let Some(id) = sqlx::query_scalar!(
+        "INSERT INTO todos (task, description, user_id, due_at, due_timezone, priority, list_id,
+            series_id, occurrence_at, position)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $4, (
+            SELECT COALESCE(MAX(position) + 1, 0) FROM todos
+            WHERE user_id = $3 AND list_id IS NOT DISTINCT FROM $7 AND parent_id IS NULL
+        ))
+        ON CONFLICT (series_id, occurrence_at) DO NOTHING
+        RETURNING id",
+        series.task,
+        series.description,
+        series.user_id,
+        occurrence_at,
+        series.timezone,
+        series.priority,
+        series.list_id,
+        series.id
+    )
+    .fetch_optional(&mut *tx)
+    .await? else {
+        return Ok(None);
+    };
+
+    replace_todo_tags(&mut tx, id, series.user_id, &series.tags).await?;
+
+    if let Some(previous_id) = previous_id {
+        sqlx::query!(
+            "INSERT INTO todo_shares (todo_id, user_id, permission, shared_by)
+            SELECT $1, user_id, permission, shared_by FROM todo_shares WHERE todo_id = $2",
+            id,
+            previous_id
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    let row = fetch_todo_in_tx(&mut tx, id).await?;
+    tx.commit().await?;
+
+    Ok(Some(row))
+}
+
 /// Retrieves all Todos for a specific user with strict ownership filtering
 ///
 /// # Security
@@ -60,8 +197,14 @@ pub async fn insert_todo_into_db(
 pub async fn fetch_all_todos_from_db(pool: &PgPool, user_id: Uuid) -> Result<Vec<Todo>, sqlx::Error> {
     let todos = sqlx::query_as!(
         Todo,
-        "SELECT id, user_id, task, description, creation_date, completion_date, completed 
-        FROM todos WHERE user_id = $1",
+        r#"SELECT id, user_id, task, description, creation_date, completion_date, completed,
+            due_at, due_timezone, priority, list_id, parent_id, position, series_id, occurrence_at,
+            ARRAY(SELECT t.name FROM todo_tag_links l JOIN todo_tags t ON t.id = l.tag_id
+                WHERE l.todo_id = todos.id ORDER BY LOWER(t.name)) AS "tags!",
+            (SELECT COUNT(*) FROM todos s WHERE s.parent_id = todos.id) AS "subtasks_total!",
+            (SELECT COUNT(*) FROM todos s WHERE s.parent_id = todos.id AND COALESCE(s.completed, FALSE)) AS "subtasks_completed!"
+        FROM todos WHERE user_id = $1
+        ORDER BY creation_date, id"#,
         user_id
     )
     .fetch_all(pool)
@@ -70,16 +213,82 @@ pub async fn fetch_all_todos_from_db(pool: &PgPool, user_id: Uuid) -> Result<Vec
     Ok(todos)
 }
 
-/// Safely retrieves a single Todo by ID with ownership verification
+/// Retrieves a page of the Todos a user owns or that are shared with the user (directly or through a list), filtered and sorted
+///
+/// # Arguments
+/// - `completed`: Only completed (`true`) or open (`false`) todos, or all todos when `None`
+///
+/// # Security
+/// - Only todos owned by or shared with the user are included
+/// - Filter values are bound, sort columns come from `TODO_SORT_FIELDS` only
+pub async fn fetch_todos_page_from_db(
+    pool: &PgPool,
+    user_id: Uuid,
+    completed: Option<bool>,
+    filter: &TodoListFilter,
+    params: &ListParams,
+) -> Result<Page<Todo>, sqlx::Error> {
+    let mut count = ListQuery::new("SELECT COUNT(*) FROM todos");
+    let mut list = ListQuery::new(TODO_LIST_SELECT);
+
+    for query in [&mut count, &mut list] {
+        match filter.shared {
+            Some(true) => query.filter_expr("", Some(user_id), &format!(" IN {})", TODO_SHARED_WITH)),
+            Some(false) => query.filter("user_id", "=", Some(user_id)),
+            // The owner and everyone the todo is shared with
+            None => query.filter_expr("", Some(user_id), &format!(" IN {} UNION ALL SELECT todos.user_id)", TODO_SHARED_WITH)),
+        };
+        query
+            .filter("COALESCE(completed, FALSE)", "=", completed)
+            .filter("priority", "=", filter.priority)
+            .filter("due_at", ">=", filter.due_from)
+            .filter("due_at", "<=", filter.due_to)
+            .filter("list_id", "=", filter.list_id)
+            .filter("parent_id", "=", filter.parent_id)
+            .filter_expr(
+                "EXISTS (SELECT 1 FROM todo_tag_links l JOIN todo_tags t ON t.id = l.tag_id
+                    WHERE l.todo_id = todos.id AND LOWER(t.name) = LOWER(",
+                filter.tag.as_ref().map(|tag| tag.trim().to_string()),
+                "))",
+            );
+    }
+
+    let total: i64 = count.builder().build_query_scalar().fetch_one(pool).await?;
+
+    list.paginate(params, "id");
+    let rows = list.builder().build_query_as::<Todo>().fetch_all(pool).await?;
+
+    Ok(params.build_page(rows, total, |todo| {
+        let value = match params.sort.name {
+            "due_at" => todo.due_at.map(|due_at| due_at.to_rfc3339()).unwrap_or_else(|| "infinity".to_string()),
+            "priority" => todo.priority.to_string(),
+            "position" => todo.position.to_string(),
+            _ => todo.creation_date.to_string(),
+        };
+        (value, todo.id)
+    }))
+}
+
+/// Safely retrieves a single Todo by ID that the user owns or that is shared with the user, directly or through its list
 ///
 /// # Security
 /// - Combines ID and user_id in WHERE clause to prevent unauthorized access
 /// - Returns Option<Todo> to avoid exposing existence of other users' todos
+/// - Whether the user may change the todo is verified at application layer
 pub async fn fetch_todo_by_id_from_db(pool: &PgPool, id: Uuid, user_id: Uuid) -> Result<Option<Todo>, sqlx::Error> {
     let todo = sqlx::query_as!(
         Todo,
-        "SELECT id, user_id, task, description, creation_date, completion_date, completed 
-        FROM todos WHERE id = $1 AND user_id = $2",
+        r#"SELECT id, user_id, task, description, creation_date, completion_date, completed,
+            due_at, due_timezone, priority, list_id, parent_id, position, series_id, occurrence_at,
+            ARRAY(SELECT t.name FROM todo_tag_links l JOIN todo_tags t ON t.id = l.tag_id
+                WHERE l.todo_id = todos.id ORDER BY LOWER(t.name)) AS "tags!",
+            (SELECT COUNT(*) FROM todos s WHERE s.parent_id = todos.id) AS "subtasks_total!",
+            (SELECT COUNT(*) FROM todos s WHERE s.parent_id = todos.id AND COALESCE(s.completed, FALSE)) AS "subtasks_completed!"
+        FROM todos
+        WHERE id = $1
+            AND (user_id = $2
+                OR EXISTS (SELECT 1 FROM todo_shares WHERE todo_id = $1 AND user_id = $2)
+                OR EXISTS (SELECT 1 FROM todo_list_shares WHERE list_id = todos.list_id AND user_id = $2))"#,
         id,
         user_id
     )
@@ -89,19 +298,360 @@ pub async fn fetch_todo_by_id_from_db(pool: &PgPool, id: Uuid, user_id: Uuid) ->
     Ok(todo)
 }
 
-/// Securely deletes a Todo by ID with ownership confirmation
+/// Updates a Todo with ownership verification, tracking its completion date
+///
+/// # Arguments
+/// - `current`: The todo as read before the new values were merged into it, its owner is kept
+/// - `todo`: The new values of the todo (validated at application layer), its tags replace the current ones
+/// - `completed`: Completes or reopens the todo, or leaves it as is when `None`
+/// - `today`: The date a completed todo gets, in the time zone of the user
+///
+/// # Security
+/// - Combines ID and user_id in WHERE clause to prevent unauthorized access
+/// - Returns Option<Todo> to avoid exposing existence of other users' todos
+///
+/// # Concurrency
+/// - The todo and its tags are updated in a single transaction
+/// - Returns `None` without updating when the todo changed since `current` was read, so that
+///   concurrent updates of other fields are not overwritten with stale values
+pub async fn update_todo_in_db(
+    pool: &PgPool,
+    current: &Todo,
+    todo: &TodoBody,
+    completed: Option<bool>,
+    today: NaiveDate,
+) -> Result<Option<Todo>, sqlx::Error> {
+    let (id, user_id) = (current.id, current.user_id);
+    let mut tx = pool.begin().await?;
+
+    let locked = sqlx::query_scalar!("SELECT id FROM todos WHERE id = $1 AND user_id = $2 FOR UPDATE", id, user_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+    if locked.is_none() {
+        return Ok(None);
+    }
+
+    // Read after taking the lock, so changes committed meanwhile (including tags) are seen
+    let latest = fetch_todo_in_tx(&mut tx, id).await?;
+    if !unchanged_since(&latest, current) {
+        return Ok(None);
+    }
+
+    // Completing an already completed todo keeps its original completion date
+    let updated = sqlx::query_scalar!(
+        "UPDATE todos
+        SET task = $3,
+            description = $4,
+            due_at = $7,
+            due_timezone = $8,
+            priority = $9,
+            completed = COALESCE($5, completed),
+            completion_date = CASE
+                WHEN $5::boolean IS NULL THEN completion_date
+                WHEN $5 THEN COALESCE(completion_date, $6)
+                ELSE NULL
+            END
+        WHERE id = $1 AND user_id = $2
+        RETURNING id",
+        id,
+        user_id,
+        todo.task,
+        todo.description,
+        completed,
+        today,
+        todo.due_at,
+        todo.due_timezone,
+        todo.priority
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    if updated.is_none() {
+        return Ok(None);
+    }
+
+    replace_todo_tags(&mut tx, id, user_id, &todo.tags).await?;
+    let row = fetch_todo_in_tx(&mut tx, id).await?;
+    tx.commit().await?;
+
+    Ok(Some(row))
+}
+
+// Whether none of the fields an update merges into has changed since the todo was read
+fn unchanged_since(latest: &Todo, read: &Todo) -> bool {
+    latest.task == read.task
+        && latest.description == read.description
+        && latest.due_at == read.due_at
+        && latest.due_timezone == read.due_timezone
+        && latest.priority == read.priority
+        && latest.completed == read.completed
+        && latest.tags == read.tags
+}
+
+/// Moves a Todo to another list and/or position, taking its subtasks along to the new list
+///
+/// # Arguments
+/// - `list_id`: The list to move to, `None` for no list (verified to belong to the user at application layer)
+/// - `position`: The new position within the list (or parent for subtasks), `None` for the end
+///
+/// # Security
+/// - Only the owner can move a todo
+///
+/// # Concurrency
+/// - The todos of the list (or parent) are locked while positions are shifted
+pub async fn move_todo_in_db(
+    pool: &PgPool,
+    id: Uuid,
+    user_id: Uuid,
+    list_id: Option<Uuid>,
+    position: Option<i32>,
+) -> Result<Option<Todo>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let Some(parent_id) = sqlx::query_scalar!(
+        "SELECT parent_id FROM todos WHERE id = $1 AND user_id = $2 FOR UPDATE",
+        id,
+        user_id
+    )
+    .fetch_optional(&mut *tx)
+    .await? else {
+        return Ok(None);
+    };
+
+    let siblings = sqlx::query_scalar!(
+        "SELECT position FROM todos
+        WHERE user_id = $1 AND list_id IS NOT DISTINCT FROM $2 AND parent_id IS NOT DISTINCT FROM $3 AND id <> $4
+        ORDER BY position
+        FOR UPDATE",
+        user_id,
+        list_id,
+        parent_id,
+        id
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let position = match position {
+        Some(position) => {
+            // Make room, so the todo ends up right before the todo that held the position
+            sqlx::query!(
+                "UPDATE todos SET position = position + 1
+                WHERE user_id = $1 AND list_id IS NOT DISTINCT FROM $2 AND parent_id IS NOT DISTINCT FROM $3
+                    AND id <> $4 AND position >= $5",
+                user_id,
+                list_id,
+                parent_id,
+                id,
+                position
+            )
+            .execute(&mut *tx)
+            .await?;
+            position
+        }
+        None => siblings.last().map_or(0, |last| last + 1),
+    };
+
+    sqlx::query!(
+        "UPDATE todos SET list_id = $2, position = $3 WHERE id = $1",
+        id,
+        list_id,
+        position
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!("UPDATE todos SET list_id = $2 WHERE parent_id = $1", id, list_id)
+        .execute(&mut *tx)
+        .await?;
+
+    let row = fetch_todo_in_tx(&mut tx, id).await?;
+    tx.commit().await?;
+
+    Ok(Some(row))
+}
+
+/// Securely deletes a Todo by ID with ownership confirmation, its subtasks and attachments go along
+///
+/// # Returns
+/// - `None` when the user has no such todo
+/// - The object keys of the removed attachments otherwise, to delete the files from storage
 ///
 /// # Security
 /// - Requires both ID and user_id for deletion
-/// - Returns affected row count without exposing existence of other users' todos
-pub async fn delete_todo_from_db(pool: &PgPool, id: Uuid, user_id: Uuid) -> Result<u64, sqlx::Error> {
-    let result = sqlx::query!(
-        "DELETE FROM todos WHERE id = $1 AND user_id = $2",
+/// - Does not expose existence of other users' todos
+///
+/// # Concurrency
+/// - The todo and its subtasks are locked first, so no attachment can be added while the keys are collected
+pub async fn delete_todo_from_db(pool: &PgPool, id: Uuid, user_id: Uuid) -> Result<Option<Vec<String>>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let todos = sqlx::query_scalar!(
+        "SELECT id FROM todos
+        WHERE (id = $1 AND user_id = $2) OR parent_id = (SELECT id FROM todos WHERE id = $1 AND user_id = $2)
+        FOR UPDATE",
         id,
         user_id
     )
-    .execute(pool)
+    .fetch_all(&mut *tx)
+    .await?;
+    if !todos.contains(&id) {
+        return Ok(None);
+    }
+
+    let object_keys = sqlx::query_scalar!(
+        "SELECT object_key FROM todo_attachments WHERE todo_id = ANY($1)",
+        &todos
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    sqlx::query!("DELETE FROM todos WHERE id = $1 AND user_id = $2", id, user_id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+    Ok(Some(object_keys))
+}
+
+// Links a todo to exactly the given tags, creating the tags the user does not have yet.
+// Tags are trimmed and deduplicated case-insensitively, the first spelling wins for new tags.
+async fn replace_todo_tags(
+    tx: &mut Transaction<'_, Postgres>,
+    todo_id: Uuid,
+    user_id: Uuid,
+    tags: &[String],
+) -> Result<(), sqlx::Error> {
+    let mut names: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags.iter().map(|tag| tag.trim()).filter(|tag| !tag.is_empty()) {
+        if !names.iter().any(|name| name.to_lowercase() == tag.to_lowercase()) {
+            names.push(tag.to_string());
+        }
+    }
+    let lowered: Vec<String> = names.iter().map(|name| name.to_lowercase()).collect();
+
+    sqlx::query!("DELETE FROM todo_tag_links WHERE todo_id = $1", todo_id)
+        .execute(&mut **tx)
+        .await?;
+
+    if names.is_empty() {
+        return Ok(());
+    }
+
+    sqlx::query!(
+        "INSERT INTO todo_tags (user_id, name)
+        SELECT $1, name FROM UNNEST($2::text[]) AS name
+        ON CONFLICT (user_id, LOWER(name)) DO NOTHING",
+        user_id,
+        &names
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    sqlx::query!(
+        "INSERT INTO todo_tag_links (todo_id, tag_id)
+        SELECT $1, id FROM todo_tags WHERE user_id = $2 AND LOWER(name) = ANY($3)",
+        todo_id,
+        user_id,
+        &lowered
+    )
+    .execute(&mut **tx)
     .await?;
 
-    Ok(result.rows_affected())
-}
\ No newline at end of file
+    Ok(())
+}
+
+// Reads a todo back within the transaction that wrote it
+async fn fetch_todo_in_tx(tx: &mut Transaction<'_, Postgres>, id: Uuid) -> Result<Todo, sqlx::Error> {
+    sqlx::query_as!(
+        Todo,
+        r#"SELECT id, user_id, task, description, creation_date, completion_date, completed,
+            due_at, due_timezone, priority, list_id, parent_id, position, series_id, occurrence_at,
+            ARRAY(SELECT t.name FROM todo_tag_links l JOIN todo_tags t ON t.id = l.tag_id
+                WHERE l.todo_id = todos.id ORDER BY LOWER(t.name)) AS "tags!",
+            (SELECT COUNT(*) FROM todos s WHERE s.parent_id = todos.id) AS "subtasks_total!",
+            (SELECT COUNT(*) FROM todos s WHERE s.parent_id = todos.id AND COALESCE(s.completed, FALSE)) AS "subtasks_completed!"
+        FROM todos WHERE id = $1"#,
+        id
+    )
+    .fetch_one(&mut **tx)
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::pagination::PaginationQuery;
+
+    fn query(cursor: Option<&str>, sort: &str) -> PaginationQuery {
+        PaginationQuery { limit: Some(1), offset: None, cursor: cursor.map(String::from), sort: Some(sort.to_string()), order: None }
+    }
+
+    #[test]
+    fn cursors_do_not_carry_over_to_another_sort() {
+        let by_priority = ListParams::parse(&query(None, "priority"), TODO_SORT_FIELDS).unwrap();
+        let cursor = by_priority
+            .build_page(vec![3_i16, 1], 2, |priority| (priority.to_string(), Uuid::new_v4()))
+            .next_cursor
+            .unwrap();
+
+        assert!(ListParams::parse(&query(Some(&cursor), "priority"), TODO_SORT_FIELDS).is_ok());
+        // "3" is no timestamp, so this has to be refused before it reaches the query
+        assert_eq!(
+            ListParams::parse(&query(Some(&cursor), "due_at"), TODO_SORT_FIELDS).err().as_deref(),
+            Some("Invalid cursor")
+        );
+    }
+
+    fn todo(task: &str, priority: i16) -> Todo {
+        Todo {
+            id: Uuid::new_v4(),
+            task: task.to_string(),
+            description: None,
+            user_id: Uuid::new_v4(),
+            creation_date: NaiveDate::from_ymd_opt(2026, 10, 18).unwrap(),
+            completion_date: None,
+            completed: Some(false),
+            due_at: None,
+            due_timezone: None,
+            priority,
+            tags: vec!["home".to_string()],
+            list_id: None,
+            parent_id: None,
+            position: 0,
+            subtasks_total: 0,
+            subtasks_completed: 0,
+            series_id: None,
+            occurrence_at: None,
+        }
+    }
+
+    fn update(task: Option<&str>, priority: Option<i16>) -> TodoUpdateBody {
+        TodoUpdateBody {
+            task: task.map(String::from),
+            description: None,
+            completed: None,
+            due_at: None,
+            due_timezone: None,
+            priority,
+            tags: None,
+        }
+    }
+
+    #[test]
+    fn updates_from_a_stale_read_are_refused() {
+        let read = todo("File taxes", 0);
+        assert!(unchanged_since(&read, &read));
+
+        // One editor raised the priority, the other still merges into the todo as it was read
+        let latest = Todo { priority: 3, ..todo("File taxes", 0) };
+        assert!(!unchanged_since(&latest, &read));
+        let moved = Todo { position: 4, subtasks_total: 2, ..todo("File taxes", 0) };
+        assert!(unchanged_since(&moved, &read), "fields an update leaves alone do not count");
+
+        // Merging again into the latest read keeps the other editor's change
+        let renamed = update(Some("File the taxes"), None);
+        assert_eq!(renamed.merge_into(&read).priority, 0);
+        let merged = renamed.merge_into(&latest);
+        assert_eq!((merged.task.as_str(), merged.priority), ("File the taxes", 3));
+    }
+}
@@ -1,5 +1,10 @@
 use sqlx::postgres::PgPool;
 use uuid::Uuid;
+use chrono::{DateTime, NaiveDate, Utc};
+use crate::models::usage::{UsageResponseDaily, UsageQuotaRow, UsageExportRow};
+
+/// Advisory lock key that keeps multiple instances from rolling up usage at the same time.
+const USAGE_ROLLUP_LOCK_KEY: i64 = 260_001;
 
 /// Records API usage with validation and security protections
 ///
@@ -39,6 +44,10 @@ use uuid::Uuid;
 
 /// Safely retrieves usage count for a user within a specified time period
 ///
+/// The count is answered from the hourly aggregates for everything that has
+/// already been rolled up, plus the raw rows that arrived after the last rollup.
+/// The start of the period is rounded down to the full hour.
+///
 /// # Security
 /// - Uses parameterized query with interval casting to prevent SQL injection
 /// - Explicit user ownership check
@@ -54,10 +63,23 @@ pub async fn fetch_usage_count_from_db(
     interval: &str,
 ) -> Result<i64, sqlx::Error> {
     let count: i64 = sqlx::query_scalar(
-        r#"SELECT COALESCE(COUNT(*), 0) 
-        FROM usage 
-        WHERE user_id = $1 
-        AND creation_date > NOW() - CAST($2 AS INTERVAL)"#
+        r#"SELECT (
+            COALESCE((
+                SELECT SUM(h.count)
+                FROM usage_hourly h
+                WHERE h.user_id = $1
+                AND h.bucket >= date_trunc('hour', NOW() - CAST($2 AS INTERVAL))
+                AND h.bucket < s.rolled_up_until
+            ), 0)
+            + (
+                SELECT COUNT(*)
+                FROM usage u
+                WHERE u.user_id = $1
+                AND u.creation_date >= GREATEST(s.rolled_up_until, NOW() - CAST($2 AS INTERVAL))
+            )
+        )::BIGINT
+        FROM usage_rollup_state s
+        WHERE s.id = TRUE"#
     )
     .bind(user_id)
     .bind(interval)
@@ -65,4 +87,320 @@ pub async fn fetch_usage_count_from_db(
     .await?;
 
     Ok(count)
-}
\ No newline at end of file
+}
+
+/// Retrieves the daily usage totals of a user from the daily aggregates
+///
+/// # Security
+/// - Explicit user ownership check
+/// - Days are clamped by the caller
+pub async fn fetch_daily_usage_from_db(
+    pool: &PgPool,
+    user_id: Uuid,
+    days: i32,
+) -> Result<Vec<UsageResponseDaily>, sqlx::Error> {
+    sqlx::query_as!(
+        UsageResponseDaily,
+        r#"SELECT day, SUM(count)::BIGINT AS "count!"
+        FROM usage_daily
+        WHERE user_id = $1
+        AND day > CURRENT_DATE - $2::INT
+        GROUP BY day
+        ORDER BY day"#,
+        user_id,
+        days
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Retrieves the complete daily usage history of a user, per endpoint
+///
+/// # Security
+/// - Explicit user ownership check
+/// - Only used for the personal data export
+pub async fn fetch_usage_export_from_db(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<UsageExportRow>, sqlx::Error> {
+    sqlx::query_as!(
+        UsageExportRow,
+        "SELECT day, endpoint, count FROM usage_daily WHERE user_id = $1 ORDER BY day, endpoint",
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Rolls the raw usage rows up into the hourly and daily aggregates
+///
+/// Only complete hours are rolled up. A grace period of five minutes is kept,
+/// because usage records are written in batches.
+///
+/// # Concurrency
+/// - Guarded by a transaction scoped advisory lock, so only one instance rolls up at a time
+/// - Aggregates are recomputed rather than incremented, which makes a rerun harmless
+///
+/// # Returns
+/// - `Ok(None)` if another instance currently holds the lock
+/// - `Ok(Some(DateTime<Utc>))` with the moment up to which usage has been rolled up
+pub async fn rollup_usage_in_db(pool: &PgPool) -> Result<Option<DateTime<Utc>>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let locked = sqlx::query_scalar!("SELECT pg_try_advisory_xact_lock($1)", USAGE_ROLLUP_LOCK_KEY)
+        .fetch_one(&mut *tx)
+        .await?
+        .unwrap_or(false);
+    if !locked {
+        return Ok(None);
+    }
+
+    let from = sqlx::query_scalar!(
+        "SELECT rolled_up_until FROM usage_rollup_state WHERE id = TRUE FOR UPDATE"
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let until = sqlx::query_scalar!(
+        r#"SELECT date_trunc('hour', NOW() - INTERVAL '5 minutes') AS "until!""#
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if until <= from {
+        tx.commit().await?;
+        return Ok(Some(from));
+    }
+
+    sqlx::query!(
+        r#"INSERT INTO usage_hourly (user_id, bucket, endpoint, count)
+        SELECT user_id, date_trunc('hour', creation_date), endpoint, COUNT(*)
+        FROM usage
+        WHERE creation_date >= $1 AND creation_date < $2
+        AND user_id IS NOT NULL  -- Usage of purged users is anonymized
+        GROUP BY 1, 2, 3
+        ON CONFLICT (user_id, bucket, endpoint) DO UPDATE SET count = EXCLUDED.count"#,
+        from,
+        until
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    // Recompute every (UTC) day touched by this rollup from the hourly aggregates
+    sqlx::query!(
+        r#"INSERT INTO usage_daily (user_id, day, endpoint, count)
+        SELECT user_id, (bucket AT TIME ZONE 'UTC')::DATE, endpoint, SUM(count)
+        FROM usage_hourly
+        WHERE bucket >= date_trunc('day', $1 AT TIME ZONE 'UTC') AT TIME ZONE 'UTC'
+        AND bucket < $2
+        GROUP BY 1, 2, 3
+        ON CONFLICT (user_id, day, endpoint) DO UPDATE SET count = EXCLUDED.count"#,
+        from,
+        until
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        "UPDATE usage_rollup_state SET rolled_up_until = $1 WHERE id = TRUE",
+        until
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(Some(until))
+}
+
+/// Applies the retention policy to the raw usage rows and the hourly aggregates
+///
+/// Raw rows are never removed before they have been rolled up.
+///
+/// # Arguments
+/// - `raw_cutoff`: Raw rows created before this moment are removed
+/// - `hourly_cutoff`: Hourly aggregates before this moment are removed
+/// - `archive`: Move the raw rows into `usage_archive` instead of deleting them
+///
+/// # Returns
+/// The amount of raw rows that were deleted or archived
+pub async fn purge_usage_from_db(
+    pool: &PgPool,
+    raw_cutoff: DateTime<Utc>,
+    hourly_cutoff: DateTime<Utc>,
+    archive: bool,
+) -> Result<u64, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let rows_affected = if archive {
+        sqlx::query!(
+            r#"WITH moved AS (
+                DELETE FROM usage
+                WHERE creation_date < LEAST($1, (SELECT rolled_up_until FROM usage_rollup_state WHERE id = TRUE))
+                RETURNING id, endpoint, user_id, creation_date
+            )
+            INSERT INTO usage_archive (id, endpoint, user_id, creation_date)
+            SELECT id, endpoint, user_id, creation_date FROM moved"#,
+            raw_cutoff
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected()
+    } else {
+        sqlx::query!(
+            r#"DELETE FROM usage
+            WHERE creation_date < LEAST($1, (SELECT rolled_up_until FROM usage_rollup_state WHERE id = TRUE))"#,
+            raw_cutoff
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected()
+    };
+
+    sqlx::query!(
+        "DELETE FROM usage_hourly WHERE bucket < $1",
+        hourly_cutoff
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(rows_affected)
+}
+
+/// Retrieves the moment up to which the raw usage has been rolled up into the aggregates
+pub async fn fetch_usage_rolled_up_until_from_db(pool: &PgPool) -> Result<DateTime<Utc>, sqlx::Error> {
+    sqlx::query_scalar!("SELECT rolled_up_until FROM usage_rollup_state WHERE id = TRUE")
+        .fetch_one(pool)
+        .await
+}
+
+/// Retrieves the usage count of a user since the start (UTC) of the given day
+///
+/// Completed days are answered from the daily aggregates, the current day from
+/// the hourly aggregates and everything after the last rollup from the raw rows.
+///
+/// # Security
+/// - Explicit user ownership check
+pub async fn fetch_usage_count_since_from_db(
+    pool: &PgPool,
+    user_id: Uuid,
+    since: NaiveDate,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"WITH s AS (SELECT rolled_up_until FROM usage_rollup_state WHERE id = TRUE),
+        p AS (SELECT ($2::DATE)::TIMESTAMP AT TIME ZONE 'UTC' AS since)
+        SELECT (
+            COALESCE((
+                SELECT SUM(d.count)
+                FROM usage_daily d, s
+                WHERE d.user_id = $1
+                AND d.day >= $2
+                AND d.day < (s.rolled_up_until AT TIME ZONE 'UTC')::DATE
+            ), 0)
+            + COALESCE((
+                SELECT SUM(h.count)
+                FROM usage_hourly h, s, p
+                WHERE h.user_id = $1
+                AND h.bucket >= GREATEST(p.since, date_trunc('day', s.rolled_up_until AT TIME ZONE 'UTC') AT TIME ZONE 'UTC')
+                AND h.bucket < s.rolled_up_until
+            ), 0)
+            + (
+                SELECT COUNT(*)
+                FROM usage u, s, p
+                WHERE u.user_id = $1
+                AND u.creation_date >= GREATEST(p.since, s.rolled_up_until)
+            )
+        )::BIGINT AS "count!""#,
+        user_id,
+        since
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Retrieves the usage since the start of the period for every active user whose tier has a monthly quota
+///
+/// Uses the same aggregate logic as `fetch_usage_count_since_from_db`, for all users at once.
+pub async fn fetch_monthly_quota_usage_from_db(
+    pool: &PgPool,
+    period_start: NaiveDate,
+) -> Result<Vec<UsageQuotaRow>, sqlx::Error> {
+    sqlx::query_as!(
+        UsageQuotaRow,
+        r#"WITH s AS (SELECT rolled_up_until FROM usage_rollup_state WHERE id = TRUE),
+        p AS (SELECT ($1::DATE)::TIMESTAMP AT TIME ZONE 'UTC' AS since),
+        totals AS (
+            SELECT x.user_id, SUM(x.count) AS count
+            FROM (
+                SELECT d.user_id, d.count
+                FROM usage_daily d, s
+                WHERE d.day >= $1
+                AND d.day < (s.rolled_up_until AT TIME ZONE 'UTC')::DATE
+                UNION ALL
+                SELECT h.user_id, h.count
+                FROM usage_hourly h, s, p
+                WHERE h.bucket >= GREATEST(p.since, date_trunc('day', s.rolled_up_until AT TIME ZONE 'UTC') AT TIME ZONE 'UTC')
+                AND h.bucket < s.rolled_up_until
+                UNION ALL
+                SELECT u.user_id, COUNT(*)
+                FROM usage u, s, p
+                WHERE u.creation_date >= GREATEST(p.since, s.rolled_up_until)
+                GROUP BY u.user_id
+            ) x
+            GROUP BY x.user_id
+        )
+        SELECT u.id AS user_id, u.email, u.language_code::TEXT AS language_code, t.requests_per_month AS "requests_per_month!",
+               COALESCE(totals.count, 0)::BIGINT AS "count!"
+        FROM users u
+        JOIN tiers t ON t.level = u.tier_level
+        JOIN totals ON totals.user_id = u.id
+        WHERE u.status = 'active' AND t.requests_per_month IS NOT NULL"#,
+        period_start
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Claims a quota threshold notification for a user and period
+///
+/// # Returns
+/// - `Ok(true)` if the notification was claimed by this call
+/// - `Ok(false)` if it had already been sent (e.g. by another instance)
+pub async fn insert_quota_notification_into_db(
+    pool: &PgPool,
+    user_id: Uuid,
+    period_start: NaiveDate,
+    threshold: i32,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!(
+        r#"INSERT INTO usage_quota_notifications (user_id, period_start, threshold)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (user_id, period_start, threshold) DO NOTHING"#,
+        user_id,
+        period_start,
+        threshold
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() == 1)
+}
+
+/// Releases a claimed quota threshold notification, so that it will be retried
+pub async fn delete_quota_notification_from_db(
+    pool: &PgPool,
+    user_id: Uuid,
+    period_start: NaiveDate,
+    threshold: i32,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "DELETE FROM usage_quota_notifications WHERE user_id = $1 AND period_start = $2 AND threshold = $3",
+        user_id,
+        period_start,
+        threshold
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
@@ -5,6 +5,17 @@ use regex::Regex;
 use sqlx::Error;
 use validator::Validate;
 use chrono::{DateTime, Utc, NaiveDate};
+use crate::database::list_query::{ListParams, ListQuery, SortField};
+use crate::models::pagination::Page;
+
+/// Fields the user list can be sorted on, the first one is the default
+pub const USER_SORT_FIELDS: &[SortField] = &[
+    SortField { name: "creation_date", column: "creation_date", sql_type: "DATE" },
+    SortField { name: "username", column: "username", sql_type: "TEXT" },
+    SortField { name: "email", column: "email", sql_type: "TEXT" },
+    SortField { name: "role_level", column: "role_level", sql_type: "INT" },
+    SortField { name: "tier_level", column: "tier_level", sql_type: "INT" },
+];
 
 /// Retrieves all users with security considerations
 ///
@@ -18,34 +29,115 @@ pub async fn fetch_all_users_from_db(pool: &PgPool) -> Result<Vec<UserGetRespons
         UserGetResponse,
         "SELECT id, username, email, role_level, tier_level, creation_date, 
         profile_picture_url, first_name, last_name, country_code, language_code, 
-        birthday, description 
+        birthday, description, tenant
         FROM users"
     )
     .fetch_all(pool)
     .await
 }
 
+/// Retrieves a page of users matching the filters, with the total amount of matches
+///
+/// # Security
+/// - Requires admin privileges (enforced at application layer)
+/// - Excludes sensitive fields like password_hash and totp_secret
+/// - Filter values are bound, sort columns come from `USER_SORT_FIELDS` only
+pub async fn fetch_users_page_from_db(
+    pool: &PgPool,
+    filter: &UserListFilter,
+    params: &ListParams,
+) -> Result<Page<UserGetResponse>, sqlx::Error> {
+    let mut count = ListQuery::new("SELECT COUNT(*) FROM users");
+    let mut list = ListQuery::new(
+        "SELECT id, username, email, role_level, tier_level, creation_date,
+        profile_picture_url, first_name, last_name, country_code, language_code,
+        birthday, description, tenant
+        FROM users",
+    );
+
+    for query in [&mut count, &mut list] {
+        match filter.status.as_deref() {
+            Some("all") => {}
+            status => { query.filter("status", "=", Some(status.unwrap_or("active").to_string())); }
+        }
+        query
+            .filter("role_level", "=", filter.role_level)
+            .filter("tier_level", "=", filter.tier_level)
+            .filter("country_code", "=", filter.country_code.as_ref().map(|code| code.to_uppercase()))
+            .filter("creation_date", ">=", filter.created_from)
+            .filter("creation_date", "<=", filter.created_to)
+            .filter("tenant", "=", filter.tenant.clone());
+    }
+
+    let total: i64 = count.builder().build_query_scalar().fetch_one(pool).await?;
+
+    list.paginate(params, "id");
+    let rows = list.builder().build_query_as::<UserGetResponse>().fetch_all(pool).await?;
+
+    Ok(params.build_page(rows, total, |user| {
+        let value = match params.sort.name {
+            "username" => user.username.clone(),
+            "email" => user.email.clone(),
+            "role_level" => user.role_level.to_string(),
+            "tier_level" => user.tier_level.to_string(),
+            _ => user.creation_date.map(|date| date.to_string()).unwrap_or_default(),
+        };
+        (value, user.id)
+    }))
+}
 
-/// Retrieves all active users with security considerations
+/// Searches users by (partial) username, email, name or description, best matches first
+///
+/// Substring matches and trigram similarity (typos) are both accepted. The rank combines the
+/// trigram similarity of the best matching field with the full-text rank over all fields.
 ///
 /// # Security
 /// - Requires admin privileges (enforced at application layer)
 /// - Excludes sensitive fields like password_hash and totp_secret
-/// - Limits maximum results in production (enforced at application layer)
-pub async fn fetch_all_active_users_from_db(pool: &PgPool) -> Result<Vec<UserGetResponse>, sqlx::Error> {
-    sqlx::query_as!(
-        UserGetResponse,
-        "SELECT id, username, email, role_level, tier_level, creation_date, 
-        profile_picture_url, first_name, last_name, country_code, language_code, 
-        birthday, description 
+/// - The search terms are bound, LIKE wildcards in them are escaped
+pub async fn search_users_in_db(
+    pool: &PgPool,
+    query: &str,
+    limit: i64,
+) -> Result<Vec<UserSearchResult>, sqlx::Error> {
+    let pattern = format!(
+        "%{}%",
+        query.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+    );
+
+    sqlx::query_as::<_, UserSearchResult>(
+        r#"SELECT id, username, email, role_level, tier_level, creation_date,
+            profile_picture_url, first_name, last_name, country_code, language_code,
+            birthday, description, tenant,
+            (GREATEST(
+                similarity(username, $1),
+                similarity(email, $1),
+                similarity(COALESCE(first_name, '') || ' ' || COALESCE(last_name, ''), $1),
+                similarity(COALESCE(description, ''), $1)
+            ) + ts_rank(
+                to_tsvector('simple', username || ' ' || email || ' ' || COALESCE(first_name, '') || ' ' || COALESCE(last_name, '') || ' ' || COALESCE(description, '')),
+                plainto_tsquery('simple', $1)
+            ))::REAL AS rank
         FROM users
-        WHERE status = 'active'"
+        WHERE username ILIKE $2
+        OR email ILIKE $2
+        OR (COALESCE(first_name, '') || ' ' || COALESCE(last_name, '')) ILIKE $2
+        OR description ILIKE $2
+        OR username % $1
+        OR email % $1
+        OR (COALESCE(first_name, '') || ' ' || COALESCE(last_name, '')) % $1
+        OR to_tsvector('simple', username || ' ' || email || ' ' || COALESCE(first_name, '') || ' ' || COALESCE(last_name, '') || ' ' || COALESCE(description, ''))
+            @@ plainto_tsquery('simple', $1)
+        ORDER BY rank DESC, username
+        LIMIT $3"#,
     )
+    .bind(query)
+    .bind(pattern)
+    .bind(limit)
     .fetch_all(pool)
     .await
 }
 
-
 /// Safely retrieves user by allowed fields using whitelist validation
 ///
 /// # Allowed Fields
@@ -56,7 +148,6 @@ pub async fn fetch_all_active_users_from_db(pool: &PgPool) -> Result<Vec<UserGet
 /// # Security
 /// - Only whitelisted fields
 /// - No sensitive data returned
-#[allow(dead_code)]
 pub async fn fetch_user_by_field_from_db(
     pool: &PgPool,
     field: &str,
@@ -76,7 +167,7 @@ pub async fn fetch_user_by_field_from_db(
                 r#"
                 SELECT id, username, email, role_level, tier_level, creation_date, 
                        profile_picture_url, first_name, last_name, country_code, 
-                       language_code, birthday, description
+                       language_code, birthday, description, tenant
                 FROM users
                 WHERE id = $1
                 "#,
@@ -91,7 +182,7 @@ pub async fn fetch_user_by_field_from_db(
                 r#"
                 SELECT id, username, email, role_level, tier_level, creation_date, 
                        profile_picture_url, first_name, last_name, country_code, 
-                       language_code, birthday, description
+                       language_code, birthday, description, tenant
                 FROM users
                 WHERE email = $1
                 "#,
@@ -106,7 +197,7 @@ pub async fn fetch_user_by_field_from_db(
                 r#"
                 SELECT id, username, email, role_level, tier_level, creation_date, 
                        profile_picture_url, first_name, last_name, country_code, 
-                       language_code, birthday, description
+                       language_code, birthday, description, tenant
                 FROM users
                 WHERE username = $1
                 "#,
@@ -150,7 +241,7 @@ pub async fn fetch_active_user_by_field_from_db(
                 r#"
                 SELECT id, username, email, role_level, tier_level, creation_date, 
                        profile_picture_url, first_name, last_name, country_code, 
-                       language_code, birthday, description
+                       language_code, birthday, description, tenant
                 FROM users
                 WHERE id = $1 AND status = 'active'
                 "#,
@@ -165,7 +256,7 @@ pub async fn fetch_active_user_by_field_from_db(
                 r#"
                 SELECT id, username, email, role_level, tier_level, creation_date, 
                        profile_picture_url, first_name, last_name, country_code, 
-                       language_code, birthday, description
+                       language_code, birthday, description, tenant
                 FROM users
                 WHERE email = $1 AND status = 'active'
                 "#,
@@ -180,7 +271,7 @@ pub async fn fetch_active_user_by_field_from_db(
                 r#"
                 SELECT id, username, email, role_level, tier_level, creation_date, 
                        profile_picture_url, first_name, last_name, country_code, 
-                       language_code, birthday, description
+                       language_code, birthday, description, tenant
                 FROM users
                 WHERE username = $1 AND status = 'active'
                 "#,
@@ -208,7 +299,8 @@ pub async fn fetch_user_by_email_from_db(
         r#"SELECT id, username, email, password_hash, totp_secret, 
            role_level, tier_level, creation_date, profile_picture_url, 
            first_name, last_name, country_code, language_code, 
-           birthday, description, verification_code, verification_expires_at
+           birthday, description, tenant, verification_code, verification_expires_at,
+           tokens_valid_after
            FROM users WHERE email = $1"#,
         email
     )
@@ -230,7 +322,8 @@ pub async fn fetch_active_user_by_email_from_db(
         r#"SELECT id, username, email, password_hash, totp_secret, 
            role_level, tier_level, creation_date, profile_picture_url, 
            first_name, last_name, country_code, language_code, 
-           birthday, description, verification_code, verification_expires_at
+           birthday, description, tenant, verification_code, verification_expires_at,
+           tokens_valid_after
            FROM users 
            WHERE email = $1 AND status = 'active'"#,
         email
@@ -254,7 +347,8 @@ pub async fn fetch_pending_user_by_email_from_db(
         r#"SELECT id, username, email, password_hash, totp_secret, 
            role_level, tier_level, creation_date, profile_picture_url, 
            first_name, last_name, country_code, language_code, 
-           birthday, description, verification_code, verification_expires_at
+           birthday, description, tenant, verification_code, verification_expires_at,
+           tokens_valid_after
            FROM users 
            WHERE email = $1 AND status = 'pending'"#,
         email
@@ -265,17 +359,209 @@ pub async fn fetch_pending_user_by_email_from_db(
 
 
 
-/// Securely deletes a user by ID
+/// Schedules a user for deletion, after which it can be restored during the grace period
 ///
 /// # Security
 /// - Requires authentication and authorization
-/// - Parameterized query prevents SQL injection
+/// - The user can no longer log in or use its tokens, as only active users are accepted
+/// - Returns `None` when the user does not exist or has already been deleted
+pub async fn soft_delete_user_in_db(pool: &PgPool, id: Uuid, grace_days: i32) -> Result<Option<UserDeletion>, sqlx::Error> {
+    sqlx::query_as!(
+        UserDeletion,
+        r#"UPDATE users
+        SET status = 'deleted', status_before_deletion = status, deleted_at = NOW(),
+            purge_after = NOW() + make_interval(days => $2)
+        WHERE id = $1 AND status <> 'deleted'
+        RETURNING id, email, language_code::TEXT AS language_code, purge_after AS "purge_after!""#,
+        id,
+        grace_days
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Restores a deleted user whose grace period has not passed yet
+///
+/// # Security
+/// - Requires admin privileges (enforced at application layer)
 /// - Returns affected rows without sensitive data
-pub async fn delete_user_from_db(pool: &PgPool, id: Uuid) -> Result<u64, sqlx::Error> {
-    let result = sqlx::query!("DELETE FROM users WHERE id = $1", id)
-        .execute(pool)
+pub async fn restore_user_in_db(pool: &PgPool, id: Uuid) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query!(
+        "UPDATE users
+        SET status = COALESCE(status_before_deletion, 'active'), status_before_deletion = NULL,
+            deleted_at = NULL, purge_after = NULL
+        WHERE id = $1 AND status = 'deleted' AND purge_after > NOW() AND purge_started_at IS NULL",
+        id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Suspends a user, invalidating its sessions and API keys
+///
+/// Suspending an already suspended user replaces the reason and end date.
+///
+/// # Security
+/// - Requires admin privileges (enforced at application layer)
+/// - Tokens issued before the suspension are rejected, also after reinstatement
+/// - All API keys of the user are disabled
+/// - Returns `None` when there is no active or suspended user with this ID
+pub async fn suspend_user_in_db(
+    pool: &PgPool,
+    id: Uuid,
+    reason: &str,
+    until: Option<DateTime<Utc>>,
+    suspended_by: Uuid,
+) -> Result<Option<UserSuspension>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let suspension = sqlx::query_as!(
+        UserSuspension,
+        r#"UPDATE users
+        SET status = 'suspended', disabled = TRUE, suspension_reason = $2,
+            suspended_at = NOW(), suspended_until = $3, suspended_by = $4, tokens_valid_after = NOW()
+        WHERE id = $1 AND status IN ('active', 'suspended')
+        RETURNING id, email, language_code::TEXT AS language_code, suspension_reason AS "suspension_reason!",
+            suspended_at AS "suspended_at!", suspended_until"#,
+        id,
+        reason,
+        until,
+        suspended_by
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    if suspension.is_some() {
+        sqlx::query!("UPDATE apikeys SET disabled = TRUE WHERE user_id = $1 AND disabled = FALSE", id)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(suspension)
+}
+
+/// Reinstates a suspended user
+///
+/// # Security
+/// - Requires admin privileges (enforced at application layer)
+/// - Disabled API keys stay disabled, the user has to create new ones
+pub async fn reinstate_user_in_db(pool: &PgPool, id: Uuid) -> Result<Option<UserReinstatement>, sqlx::Error> {
+    sqlx::query_as!(
+        UserReinstatement,
+        "UPDATE users
+        SET status = 'active', disabled = FALSE, suspension_reason = NULL,
+            suspended_at = NULL, suspended_until = NULL, suspended_by = NULL
+        WHERE id = $1 AND status = 'suspended'
+        RETURNING id, email, language_code::TEXT AS language_code",
+        id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Reinstates all users whose suspension has ended
+///
+/// # Concurrency
+/// - A single update, so every user is reinstated (and returned) by one instance only
+pub async fn reinstate_expired_suspensions_in_db(pool: &PgPool) -> Result<Vec<UserReinstatement>, sqlx::Error> {
+    sqlx::query_as!(
+        UserReinstatement,
+        "UPDATE users
+        SET status = 'active', disabled = FALSE, suspension_reason = NULL,
+            suspended_at = NULL, suspended_until = NULL, suspended_by = NULL
+        WHERE status = 'suspended' AND suspended_until <= NOW()
+        RETURNING id, email, language_code::TEXT AS language_code"
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Retrieves the suspension of a user by email, together with its password hash
+///
+/// # Security
+/// - Only used after the active user lookup failed, to explain why
+/// - The password hash allows callers to only reveal the suspension to the user itself
+pub async fn fetch_user_suspension_by_email_from_db(
+    pool: &PgPool,
+    email: &str,
+) -> Result<Option<(UserSuspension, String)>, sqlx::Error> {
+    let row = sqlx::query!(
+        r#"SELECT id, email, language_code::TEXT AS language_code, password_hash, suspension_reason AS "suspension_reason!",
+                  suspended_at AS "suspended_at!", suspended_until
+        FROM users
+        WHERE email = $1 AND status = 'suspended'"#,
+        email
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.map(|row| (
+        UserSuspension {
+            id: row.id,
+            email: row.email,
+            language_code: row.language_code,
+            suspension_reason: row.suspension_reason,
+            suspended_at: row.suspended_at,
+            suspended_until: row.suspended_until,
+        },
+        row.password_hash,
+    )))
+}
+
+/// Claims a deleted user whose grace period has passed, so that it can be purged
+///
+/// Purges that got stuck (e.g. after a crash) are claimed again after 30 minutes.
+///
+/// # Concurrency
+/// - `FOR UPDATE SKIP LOCKED` ensures every user is claimed by one instance only
+pub async fn claim_user_for_purge_in_db(pool: &PgPool) -> Result<Option<UserPurge>, sqlx::Error> {
+    sqlx::query_as!(
+        UserPurge,
+        "UPDATE users SET purge_started_at = NOW()
+        WHERE id = (
+            SELECT id FROM users
+            WHERE status = 'deleted' AND purge_after <= NOW()
+            AND (purge_started_at IS NULL OR purge_started_at < NOW() - INTERVAL '30 minutes')
+            ORDER BY purge_after
+            LIMIT 1
+            FOR UPDATE SKIP LOCKED
+        )
+        RETURNING id, email, language_code::TEXT AS language_code, profile_picture_url"
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Permanently deletes a deleted user and all its data
+///
+/// Todos, API keys, usage aggregates and exports are removed through their foreign keys;
+/// raw usage rows and billing statements are kept, but anonymized.
+///
+/// # Security
+/// - Only users with status = 'deleted' can be purged
+/// - Returns affected rows without sensitive data
+pub async fn purge_user_from_db(pool: &PgPool, id: Uuid) -> Result<u64, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query!("UPDATE usage_archive SET user_id = NULL WHERE user_id = $1", id)
+        .execute(&mut *tx)
         .await?;
 
+    // Statements are financial records, they are kept without the link to the user
+    sqlx::query!("UPDATE billing_statements SET user_id = NULL WHERE user_id = $1", id)
+        .execute(&mut *tx)
+        .await?;
+
+    let result = sqlx::query!("DELETE FROM users WHERE id = $1 AND status = 'deleted'", id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
     Ok(result.rows_affected())
 }
 
@@ -331,6 +617,98 @@ pub async fn insert_user_into_db(
     Ok(row)
 }
 
+/// Retrieves which of the given usernames and email addresses are already in use
+///
+/// # Returns
+/// - `(username, email)` of every existing user that matches either of them
+/// - `(username, "")` of every username that is still blocked after a username change
+pub async fn fetch_taken_usernames_and_emails_from_db(
+    pool: &PgPool,
+    usernames: &[String],
+    emails: &[String],
+) -> Result<Vec<(String, String)>, Error> {
+    let rows = sqlx::query!(
+        r#"SELECT username AS "username!", email AS "email!" FROM users WHERE username = ANY($1) OR email = ANY($2)
+        UNION ALL
+        SELECT username, '' FROM username_history WHERE username = ANY($1) AND reserved_until > NOW()"#,
+        usernames,
+        emails
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(|row| (row.username, row.email)).collect())
+}
+
+/// Inserts the users of a bulk import
+///
+/// Imported users are created as active, so they can sign in right away (or once they chose
+/// a password through their invitation). Every user is inserted within its own savepoint. In transactional mode the first failure
+/// rolls back the whole import, otherwise the failed users are skipped.
+///
+/// # Returns
+/// - A result per attempted user; in transactional mode the list ends at the first failure
+///   and none of the users have been created
+pub async fn insert_imported_users_into_db(
+    pool: &PgPool,
+    users: &[crate::models::user_import::UserImportInsert],
+    transactional: bool,
+) -> Result<Vec<Result<UserInsertResponse, String>>, Error> {
+    let mut tx = pool.begin().await?;
+    let mut results = Vec::with_capacity(users.len());
+
+    for import in users {
+        let user = &import.user;
+        let mut savepoint = sqlx::Connection::begin(&mut *tx).await?;
+
+        let result = sqlx::query_as!(
+            UserInsertResponse,
+            r#"INSERT INTO users
+               (username, email, password_hash, totp_secret, role_level, tier_level, creation_date,
+                first_name, last_name, country_code, language_code, birthday, description, status)
+               VALUES ($1, $2, $3, $4, 1, 1, NOW()::timestamp, $5, $6, $7, $8, $9, $10, 'active')
+               RETURNING id, username, email, totp_secret, role_level, tier_level, creation_date,
+                         first_name, last_name, country_code, language_code, birthday, description,
+                         profile_picture_url"#,
+            user.username,
+            user.email,
+            import.password_hash,
+            import.totp_secret,
+            user.first_name,
+            user.last_name,
+            user.country_code,
+            user.language_code,
+            user.birthday,
+            user.description,
+        )
+        .fetch_one(&mut *savepoint)
+        .await;
+
+        match result {
+            Ok(row) => {
+                savepoint.commit().await?;
+                results.push(Ok(row));
+            }
+            Err(e) => {
+                savepoint.rollback().await?;
+                results.push(Err(match e {
+                    Error::Database(err) if err.is_unique_violation() => "Username or email already exists.".to_string(),
+                    _ => "Could not create the user.".to_string(),
+                }));
+
+                if transactional {
+                    tx.rollback().await?;
+                    return Ok(results);
+                }
+            }
+        }
+    }
+
+    tx.commit().await?;
+
+    Ok(results)
+}
+
 /// Inserts a new pending user for registration (with email verification).
 ///
 /// # Arguments
@@ -381,10 +759,10 @@ pub async fn insert_pending_user_into_db(
         r#"
         INSERT INTO users 
             (username, email, password_hash, role_level, tier_level, creation_date, status, 
-             verification_code, verification_expires_at,
+             verification_code, verification_expires_at, verification_sent_at,
              first_name, last_name, country_code, language_code, birthday, description, totp_secret)
         VALUES 
-            ($1, $2, $3, 1, 1, NOW()::timestamp, 'pending', $4, $5, 
+            ($1, $2, $3, 1, 1, NOW()::timestamp, 'pending', $4, $5, NOW(),
              $6, $7, $8, $9, $10, $11, $12)
         RETURNING id
         "#,
@@ -569,6 +947,7 @@ pub async fn update_user_in_db(
     maybe_set_opt!(description);
     maybe_set_opt!(role_level);
     maybe_set_opt!(tier_level);
+    maybe_set_optopt!(tenant);
 
     if !has_updates {
         return Ok(());
@@ -645,6 +1024,70 @@ pub async fn update_user_password_in_db(
 }
 
 
+/// Replaces the verification code of a pending user, which also resets the failed attempts
+///
+/// # Security
+/// - A new code is only issued once the cooldown since the previous one has passed
+///
+/// # Returns
+/// - The language code of the pending user, to send the code in its language
+/// - `Ok(None)` if there is no pending user with this email, or the cooldown has not passed yet
+pub async fn renew_verification_code_in_db(
+    pool: &PgPool,
+    email: &str,
+    verification_code: &str,
+    verification_expires_at: DateTime<Utc>,
+    cooldown_seconds: i64,
+) -> Result<Option<Option<String>>, sqlx::Error> {
+    sqlx::query_scalar!(
+        "UPDATE users
+         SET verification_code = $2,
+             verification_expires_at = $3,
+             verification_attempts = 0,
+             verification_sent_at = NOW()
+         WHERE email = $1 AND status = 'pending'
+         AND (verification_sent_at IS NULL OR verification_sent_at <= NOW() - make_interval(secs => $4))
+         RETURNING language_code::TEXT",
+        email.trim().to_lowercase(),
+        verification_code,
+        verification_expires_at,
+        cooldown_seconds as f64
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Counts an attempt to verify the email address of a pending user
+///
+/// # Concurrency
+/// - The counter is incremented atomically, so parallel guesses are all counted
+///
+/// # Returns
+/// - `Ok(i32)` with the amount of attempts made with the current code, including this one
+pub async fn record_verification_attempt_in_db(pool: &PgPool, user_id: Uuid) -> Result<i32, sqlx::Error> {
+    sqlx::query_scalar!(
+        "UPDATE users SET verification_attempts = verification_attempts + 1
+         WHERE id = $1 AND status = 'pending'
+         RETURNING verification_attempts",
+        user_id
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Deletes the pending users whose verification code has expired, freeing their username and email
+///
+/// # Returns
+/// - `Ok(u64)` with the amount of deleted users
+pub async fn delete_expired_pending_users_from_db(pool: &PgPool) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query!(
+        "DELETE FROM users WHERE status = 'pending' AND verification_expires_at < NOW()"
+    )
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected())
+}
+
 /// Activates a user by setting status to 'active' and clearing verification fields.
 ///
 /// # Arguments
@@ -662,7 +1105,8 @@ pub async fn activate_user_in_db(
         "UPDATE users
          SET status = 'active',
              verification_code = NULL,
-             verification_expires_at = NULL
+             verification_expires_at = NULL,
+             verification_attempts = 0
          WHERE id = $1",
         user_id
     )
@@ -749,4 +1193,83 @@ pub async fn check_user_exists_in_db(
     .await?;
 
     Ok(user_by_username.is_some())
-}
\ No newline at end of file
+}
+
+/// Retrieves the moment a user last changed its username
+///
+/// # Returns
+/// - `None` when the username was never changed
+pub async fn fetch_last_username_change_from_db(pool: &PgPool, user_id: Uuid) -> Result<Option<DateTime<Utc>>, Error> {
+    sqlx::query_scalar!(
+        r#"SELECT MAX(changed_at) AS "changed_at" FROM username_history WHERE user_id = $1"#,
+        user_id
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Checks whether a username was recently given up by another user and is still blocked from reuse
+///
+/// # Arguments
+/// - `user_id`: The user that wants the username, which may take back its own previous usernames
+pub async fn is_username_blocked_in_db(pool: &PgPool, username: &str, user_id: Option<Uuid>) -> Result<bool, Error> {
+    let blocked = sqlx::query_scalar!(
+        r#"SELECT EXISTS (
+            SELECT 1 FROM username_history
+            WHERE username = $1 AND reserved_until > NOW() AND user_id IS DISTINCT FROM $2
+        ) AS "blocked!""#,
+        username,
+        user_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(blocked)
+}
+
+/// Changes the username of an active user and keeps the previous one in the history
+///
+/// # Concurrency
+/// - Only changes the username when it still is `previous_username`, so concurrent changes
+///   cannot both pass the cooldown check
+/// - A username taken in the meantime fails on the unique constraint
+///
+/// # Returns
+/// - The moment of the change, or `None` when the user is not active or its username changed meanwhile
+pub async fn change_username_in_db(
+    pool: &PgPool,
+    user_id: Uuid,
+    previous_username: &str,
+    username: &str,
+    reserved_until: DateTime<Utc>,
+) -> Result<Option<DateTime<Utc>>, Error> {
+    let mut tx = pool.begin().await?;
+
+    let result = sqlx::query!(
+        "UPDATE users SET username = $3 WHERE id = $1 AND username = $2 AND status = 'active'",
+        user_id,
+        previous_username,
+        username
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Ok(None);
+    }
+
+    let changed_at = sqlx::query_scalar!(
+        "INSERT INTO username_history (user_id, username, reserved_until)
+        VALUES ($1, $2, $3)
+        RETURNING changed_at",
+        user_id,
+        previous_username,
+        reserved_until
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Some(changed_at))
+}
@@ -10,6 +10,8 @@ use std::sync::Arc;
 
 use crate::models::user::User;
 use crate::database::apikeys::delete_apikey_from_db;
+use crate::models::audit::AuditEvent;
+use crate::utils::audit::{record_audit_event, AuditContext};
 use crate::routes::AppState;
 
 // --- Route Handler ---
@@ -33,10 +35,11 @@ use crate::routes::AppState;
         (status = 500, description = "Internal server error", body = String)
     )
 )]
-#[instrument(skip(state))]
+#[instrument(skip(state, audit))]
 pub async fn delete_apikey_by_id(
     State(state): State<Arc<AppState>>,
     Extension(user): Extension<User>,
+    audit: AuditContext,
     Path(id): Path<String>, // Use Path extractor here
 ) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, Json<serde_json::Value>)> {
     // Parse the id string to UUID
@@ -58,6 +61,8 @@ pub async fn delete_apikey_by_id(
                     Json(json!({ "error": format!("API key with ID '{}' not found.", id) })),
                 ))
             } else {
+                let event = AuditEvent::new("apikey.delete", Some(user.id)).target("apikey", uuid);
+                record_audit_event(&state.database, &audit, event).await;
                 Ok((
                     StatusCode::OK,
                     Json(json!({ "success": format!("API key with ID '{}' deleted.", id) })),
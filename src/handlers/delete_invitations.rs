@@ -0,0 +1,77 @@
+use axum::{
+    extract::{Extension, State, Path},
+    Json,
+    http::StatusCode,
+};
+use uuid::Uuid;
+use serde_json::json;
+use tracing::{error, instrument};
+use std::sync::Arc;
+
+use crate::models::documentation::{ErrorResponse, SuccessResponse};
+use crate::database::invitations::revoke_invitation_in_db;
+use crate::models::user::User;
+use crate::models::audit::AuditEvent;
+use crate::utils::audit::{record_audit_event, AuditContext};
+use crate::routes::AppState;
+
+// --- Route Handler ---
+
+// Revoke a pending invitation
+#[utoipa::path(
+    delete,
+    path = "/invitations/{id}",
+    tag = "invitation",
+    security(
+        ("jwt_token" = [])
+    ),
+    responses(
+        (status = 200, description = "Invitation revoked successfully", body = SuccessResponse),
+        (status = 400, description = "Invalid UUID format", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 404, description = "No pending invitation with this ID", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    params(
+        ("id" = String, Path, description = "Invitation ID")
+    )
+)]
+#[instrument(skip(state, current_user, audit))]
+pub async fn delete_invitation_by_id(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<User>,
+    audit: AuditContext,
+    Path(id): Path<String>,
+) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, Json<serde_json::Value>)> {
+    let uuid = match Uuid::parse_str(&id) {
+        Ok(uuid) => uuid,
+        Err(_) => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({ "error": "Invalid UUID format." })),
+            ));
+        }
+    };
+
+    match revoke_invitation_in_db(&state.database, uuid).await {
+        Ok(0) => Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": format!("No pending invitation with ID '{}' found.", id) })),
+        )),
+        Ok(_) => {
+            let event = AuditEvent::new("invitation.revoke", Some(current_user.id)).target("invitation", uuid);
+            record_audit_event(&state.database, &audit, event).await;
+            Ok((
+                StatusCode::OK,
+                Json(json!({ "success": format!("Invitation with ID '{}' revoked.", id) })),
+            ))
+        }
+        Err(e) => {
+            error!("Error revoking invitation: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Could not revoke the invitation." })),
+            ))
+        }
+    }
+}
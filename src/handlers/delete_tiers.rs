@@ -0,0 +1,101 @@
+use axum::{
+    extract::{Extension, State, Path},
+    Json,
+    http::StatusCode,
+};
+use uuid::Uuid;
+use serde_json::json;
+use tracing::instrument; // For logging
+use std::sync::Arc;
+
+use crate::models::documentation::{ErrorResponse, SuccessResponse};
+use crate::database::tiers::{fetch_tier_by_id_from_db, count_users_with_tier_level_from_db, delete_tier_from_db};
+use crate::middlewares::auth::invalidate_rate_limit_cache;
+use crate::models::user::User;
+use crate::models::audit::AuditEvent;
+use crate::utils::audit::{record_audit_event, AuditContext};
+use crate::routes::AppState;
+
+// --- Route Handler ---
+
+// Delete a tier by id, as long as no users are assigned to it
+#[utoipa::path(
+    delete,
+    path = "/tiers/{id}",
+    tag = "tier",
+    security(
+        ("jwt_token" = [])
+    ),
+    responses(
+        (status = 200, description = "Tier deleted successfully", body = SuccessResponse),
+        (status = 400, description = "Invalid UUID format", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 404, description = "Tier not found", body = ErrorResponse),
+        (status = 409, description = "Tier is still assigned to users, or has been held by users", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Tier ID")
+    )
+)]
+#[instrument(skip(state, current_user, audit))]
+pub async fn delete_tier_by_id(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<User>,
+    audit: AuditContext,
+    Path(id): Path<String>,
+) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, Json<serde_json::Value>)> {
+    let uuid = match Uuid::parse_str(&id) {
+        Ok(uuid) => uuid,
+        Err(_) => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({ "error": "Invalid UUID format." })),
+            ));
+        }
+    };
+
+    let tier = match fetch_tier_by_id_from_db(&state.database, uuid).await {
+        Ok(Some(tier)) => tier,
+        Ok(None) => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(json!({ "error": format!("Tier with ID '{}' not found.", id) })),
+            ));
+        }
+        Err(_err) => {
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Could not delete the tier." })),
+            ));
+        }
+    };
+
+    match delete_tier_from_db(&state.database, uuid).await {
+        Ok(0) => {
+            // The tier exists, so it is still assigned to users or users have held it
+            let users = count_users_with_tier_level_from_db(&state.database, tier.level).await.unwrap_or(0);
+            let error = if users > 0 {
+                format!("Tier '{}' is still assigned to {} user(s). Move them to another tier first.", tier.name, users)
+            } else {
+                format!("Tier '{}' has been held by users and is kept for their billing.", tier.name)
+            };
+            Err((StatusCode::CONFLICT, Json(json!({ "error": error }))))
+        }
+        Ok(_) => {
+            invalidate_rate_limit_cache();
+            let event = AuditEvent::new("tier.delete", Some(current_user.id))
+                .target("tier", uuid)
+                .change(Some(json!(tier)), None);
+            record_audit_event(&state.database, &audit, event).await;
+            Ok((
+                StatusCode::OK,
+                Json(json!({ "success": format!("Tier with ID '{}' deleted.", id) })),
+            ))
+        }
+        Err(_err) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "error": "Could not delete the tier." })),
+        )),
+    }
+}
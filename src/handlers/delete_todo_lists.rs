@@ -0,0 +1,67 @@
+use axum::{
+    extract::{State, Extension, Path},
+    Json,
+    http::StatusCode,
+};
+use uuid::Uuid;
+use serde_json::json;
+use tracing::{error, instrument};
+use std::sync::Arc;
+
+use crate::models::user::User;
+use crate::models::documentation::{ErrorResponse, SuccessResponse};
+use crate::database::todo_lists::delete_todo_list_from_db;
+use crate::utils::attachments::delete_attachment_objects;
+use crate::routes::AppState;
+
+// --- Route Handler ---
+
+// Delete a list with all of its todos
+#[utoipa::path(
+    delete,
+    path = "/todos/lists/{id}",
+    tag = "todo",
+    security(
+        ("jwt_token" = [])
+    ),
+    params(
+        ("id" = String, Path, description = "List ID")
+    ),
+    responses(
+        (status = 200, description = "List and its todos deleted successfully", body = SuccessResponse),
+        (status = 400, description = "Invalid UUID format", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 404, description = "List not found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(state, user))]
+pub async fn delete_todo_list_by_id(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<User>,
+    Path(id): Path<String>,
+) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, Json<serde_json::Value>)> {
+    let uuid = Uuid::parse_str(&id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(json!({ "error": "Invalid UUID format." }))))?;
+
+    match delete_todo_list_from_db(&state.database, uuid, user.id).await {
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": format!("List with ID '{}' not found.", id) })),
+        )),
+        Ok(Some(object_keys)) => {
+            delete_attachment_objects(&state.storage, &object_keys).await;
+            Ok((
+                StatusCode::OK,
+                Json(json!({ "success": format!("List with ID '{}' deleted.", id) })),
+            ))
+        }
+        Err(e) => {
+            error!("Error deleting todo list {}: {}", uuid, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Could not delete the list." })),
+            ))
+        }
+    }
+}
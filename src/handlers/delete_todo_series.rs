@@ -0,0 +1,63 @@
+use axum::{
+    extract::{State, Extension, Path},
+    Json,
+    http::StatusCode,
+};
+use uuid::Uuid;
+use serde_json::json;
+use tracing::{error, instrument};
+use std::sync::Arc;
+
+use crate::models::user::User;
+use crate::models::documentation::{ErrorResponse, SuccessResponse};
+use crate::database::todo_series::delete_todo_series_from_db;
+use crate::routes::AppState;
+
+// --- Route Handler ---
+
+// Stop a recurring series, the todos it created stay
+#[utoipa::path(
+    delete,
+    path = "/todos/series/{id}",
+    tag = "todo",
+    security(
+        ("jwt_token" = [])
+    ),
+    params(
+        ("id" = String, Path, description = "Series ID")
+    ),
+    responses(
+        (status = 200, description = "Series stopped, its todos no longer recur", body = SuccessResponse),
+        (status = 400, description = "Invalid UUID format", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 404, description = "Series not found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(state, user))]
+pub async fn delete_todo_series_by_id(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<User>,
+    Path(id): Path<String>,
+) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, Json<serde_json::Value>)> {
+    let uuid = Uuid::parse_str(&id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(json!({ "error": "Invalid UUID format." }))))?;
+
+    match delete_todo_series_from_db(&state.database, uuid, user.id).await {
+        Ok(0) => Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": format!("Series with ID '{}' not found.", id) })),
+        )),
+        Ok(_) => Ok((
+            StatusCode::OK,
+            Json(json!({ "success": format!("Series with ID '{}' stopped.", id) })),
+        )),
+        Err(e) => {
+            error!("Error deleting todo series {}: {}", uuid, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Could not stop the series." })),
+            ))
+        }
+    }
+}
@@ -11,6 +11,7 @@ use std::sync::Arc;
 use crate::models::user::User;
 use crate::models::documentation::{ErrorResponse, SuccessResponse};
 use crate::database::todos::delete_todo_from_db;
+use crate::utils::attachments::delete_attachment_objects;
 use crate::routes::AppState;
 
 // --- Route Handler ---
@@ -52,18 +53,16 @@ pub async fn delete_todo_by_id(
     };
 
     match delete_todo_from_db(&state.database, uuid, user.id).await {
-        Ok(rows_affected) => {
-            if rows_affected == 0 {
-                Err((
-                    StatusCode::NOT_FOUND,
-                    Json(json!({ "error": format!("Todo with ID '{}' not found.", id) })),
-                ))
-            } else {
-                Ok((
-                    StatusCode::OK,
-                    Json(json!({ "success": format!("Todo with ID '{}' deleted.", id) })),
-                ))
-            }
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": format!("Todo with ID '{}' not found.", id) })),
+        )),
+        Ok(Some(object_keys)) => {
+            delete_attachment_objects(&state.storage, &object_keys).await;
+            Ok((
+                StatusCode::OK,
+                Json(json!({ "success": format!("Todo with ID '{}' deleted.", id) })),
+            ))
         }
         Err(_err) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -1,21 +1,30 @@
 use axum::{
-    extract::{State, Path},
+    extract::{State, Path, Extension},
     Json,
 
     http::StatusCode,
 };
 use uuid::Uuid;
 use serde_json::json;
-use tracing::instrument; // For logging
+use tracing::{error, instrument}; // For logging
 use std::sync::Arc;
 
-use crate::models::documentation::{ErrorResponse, SuccessResponse};
-use crate::database::users::delete_user_from_db;
+use crate::core::config::get_env_u64;
+use crate::models::documentation::ErrorResponse;
+use crate::models::user::{User, UserDeleteBody, UserDeletion};
+use crate::database::users::soft_delete_user_in_db;
+use crate::mail::MailerState;
+use crate::i18n::catalog::{t, t_with};
+use crate::i18n::locale::Locale;
+use crate::mail::send::send_mail;
+use crate::utils::auth::verify_hash;
+use crate::models::audit::AuditEvent;
+use crate::utils::audit::{record_audit_event, AuditContext};
 use crate::routes::AppState;
 
 // --- Route Handler ---
 
-// Delete a user by id
+// Delete a user by id, the user is purged after the grace period
 #[utoipa::path(
     delete,
     path = "/users/{id}",
@@ -24,7 +33,7 @@ use crate::routes::AppState;
         ("jwt_token" = [])
     ),
     responses(
-        (status = 200, description = "User deleted successfully", body = SuccessResponse),
+        (status = 200, description = "User scheduled for deletion", body = UserDeletion),
         (status = 400, description = "Invalid UUID format", body = ErrorResponse),
         (status = 401, description = "Unauthorized", body = serde_json::Value),
         (status = 404, description = "User not found", body = ErrorResponse),
@@ -34,11 +43,13 @@ use crate::routes::AppState;
         ("id" = Uuid, Path, description = "User ID")
     )
 )]
-#[instrument(skip(state))]
+#[instrument(skip(state, admin, audit))]
 pub async fn delete_user_by_id(
     State(state): State<Arc<AppState>>,
+    Extension(admin): Extension<User>,
+    audit: AuditContext,
     Path(id): Path<String>, // Use Path extractor here
-) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Json<UserDeletion>, (StatusCode, Json<serde_json::Value>)> {
     let uuid = match Uuid::parse_str(&id) {
         Ok(uuid) => uuid,
         Err(_) => {
@@ -49,23 +60,92 @@ pub async fn delete_user_by_id(
         }
     };
 
-    match delete_user_from_db(&state.database, uuid).await {
-        Ok(rows_affected) => {
-            if rows_affected == 0 {
-                Err((
-                    StatusCode::NOT_FOUND,
-                    Json(json!({ "error": format!("User with ID '{}' not found.", id) })),
-                ))
-            } else {
-                Ok((
-                    StatusCode::OK,
-                    Json(json!({ "success": format!("User with ID '{}' deleted.", id) })),
-                ))
-            }
+    match soft_delete_user_in_db(&state.database, uuid, grace_days()).await {
+        Ok(Some(deletion)) => {
+            let event = AuditEvent::new("user.delete", Some(admin.id))
+                .target("user", deletion.id)
+                .change(None, Some(json!({ "purge_after": deletion.purge_after })));
+            record_audit_event(&state.database, &audit, event).await;
+            notify_deletion(&state.mail, &deletion).await;
+            Ok(Json(deletion))
         }
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": format!("User with ID '{}' not found.", id) })),
+        )),
         Err(_err) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({ "error": "Could not delete the user." })),
         )),
     }
 }
+
+// Delete the account of the current user, confirmed with its password
+#[utoipa::path(
+    delete,
+    path = "/users/current",
+    tag = "user",
+    security(
+        ("jwt_token" = [])
+    ),
+    request_body = UserDeleteBody,
+    responses(
+        (status = 200, description = "Account scheduled for deletion", body = UserDeletion),
+        (status = 401, description = "Unauthorized or incorrect password", body = serde_json::Value),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(state, user, audit, body))]
+pub async fn delete_current_user(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<User>,
+    audit: AuditContext,
+    Json(body): Json<UserDeleteBody>,
+) -> Result<Json<UserDeletion>, (StatusCode, Json<serde_json::Value>)> {
+    // A stolen token alone must not be enough to delete an account
+    match verify_hash(&body.password, &user.password_hash).await {
+        Ok(true) => {}
+        _ => return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({ "error": "Incorrect password." })),
+        )),
+    }
+
+    match soft_delete_user_in_db(&state.database, user.id, grace_days()).await {
+        Ok(Some(deletion)) => {
+            let event = AuditEvent::new("user.delete", Some(user.id))
+                .target("user", deletion.id)
+                .change(None, Some(json!({ "purge_after": deletion.purge_after })));
+            record_audit_event(&state.database, &audit, event).await;
+            notify_deletion(&state.mail, &deletion).await;
+            Ok(Json(deletion))
+        }
+        Ok(None) => Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({ "error": "User not found." })),
+        )),
+        Err(_err) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "error": "Could not delete the account." })),
+        )),
+    }
+}
+
+// --- Helper Functions ---
+
+// Days a deleted user can still be restored
+fn grace_days() -> i32 {
+    get_env_u64("USER_DELETION_GRACE_DAYS", 30).min(3650) as i32
+}
+
+// Lets the user know its account will be purged, failing to do so does not undo the deletion
+async fn notify_deletion(mail: &MailerState, deletion: &UserDeletion) {
+    let locale = Locale::for_user(deletion.language_code.as_deref(), Locale::fallback());
+    let purge_after = deletion.purge_after.format("%Y-%m-%d %H:%M UTC").to_string();
+    let subject = t(locale, "mail.deletion.subject");
+    let body = t_with(locale, "mail.deletion.body", &[("purge_after", &purge_after)]);
+
+    if let Err(e) = send_mail(mail, &deletion.email, &subject, &body).await {
+        error!("Failed to send the deletion notice to user {}: {}", deletion.id, e);
+    }
+}
@@ -0,0 +1,148 @@
+use axum::{
+    extract::{Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::json;
+use tracing::instrument;
+use std::sync::Arc;
+
+use crate::core::config::get_env_u64;
+use crate::models::audit::{AuditLogEntry, AuditLogExportQuery, AuditLogFilter, AuditLogVerification};
+use crate::models::pagination::{Page, PaginationQuery};
+use crate::database::audit_log::{fetch_audit_log_export_from_db, fetch_audit_log_page_from_db, AUDIT_LOG_SORT_FIELDS};
+use crate::database::list_query::ListParams;
+use crate::utils::audit::{audit_log_to_csv, verify_audit_chain};
+use crate::routes::AppState;
+
+// Get a page of audit log entries
+#[utoipa::path(
+    get,
+    path = "/audit/all",
+    tag = "audit",
+    security(
+        ("jwt_token" = [])
+    ),
+    params(PaginationQuery, AuditLogFilter),
+    responses(
+        (status = 200, description = "Successfully fetched a page of audit log entries", body = Page<AuditLogEntry>),
+        (status = 400, description = "Invalid pagination or filter parameters", body = serde_json::Value),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 500, description = "Internal server error")
+    )
+)]
+#[instrument(skip(state))]
+pub async fn get_audit_log(
+    State(state): State<Arc<AppState>>,
+    Query(pagination): Query<PaginationQuery>,
+    Query(filter): Query<AuditLogFilter>,
+) -> Result<Json<Page<AuditLogEntry>>, (StatusCode, Json<serde_json::Value>)> {
+    let params = ListParams::parse(&pagination, AUDIT_LOG_SORT_FIELDS).map_err(|e| (
+        StatusCode::BAD_REQUEST,
+        Json(json!({ "error": e })),
+    ))?;
+
+    match fetch_audit_log_page_from_db(&state.database, &filter, &params).await {
+        Ok(page) => Ok(Json(page)),
+        Err(_err) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "error": "Could not fetch the audit log." })),
+        )),
+    }
+}
+
+// Download the audit log entries matching the filters as CSV or JSON
+#[utoipa::path(
+    get,
+    path = "/audit/export",
+    tag = "audit",
+    security(
+        ("jwt_token" = [])
+    ),
+    params(AuditLogFilter, AuditLogExportQuery),
+    responses(
+        (status = 200, description = "The entries as a downloadable file, in chain order", content((String = "text/csv"), (Vec<AuditLogEntry> = "application/json"))),
+        (status = 400, description = "Invalid filter or export format", body = serde_json::Value),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 413, description = "More entries match than a single export may hold", body = serde_json::Value),
+        (status = 500, description = "Internal server error")
+    )
+)]
+#[instrument(skip(state))]
+pub async fn get_audit_log_export(
+    State(state): State<Arc<AppState>>,
+    Query(filter): Query<AuditLogFilter>,
+    Query(query): Query<AuditLogExportQuery>,
+) -> Result<Response, (StatusCode, Json<serde_json::Value>)> {
+    let format = query.format.unwrap_or_else(|| "json".to_string()).to_lowercase();
+    if format != "csv" && format != "json" {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": "Format must be 'csv' or 'json'." })),
+        ));
+    }
+
+    // One entry more than allowed tells a complete export apart from a truncated one
+    let max_rows = get_env_u64("AUDIT_EXPORT_MAX_ROWS", 10000).max(1) as i64;
+    let entries = fetch_audit_log_export_from_db(&state.database, &filter, max_rows + 1).await.map_err(|_err| (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(json!({ "error": "Could not fetch the audit log." })),
+    ))?;
+    if entries.len() as i64 > max_rows {
+        return Err((
+            StatusCode::PAYLOAD_TOO_LARGE,
+            Json(json!({
+                "error": format!("More than {} entries match, narrow the export down with 'from' and 'to'.", max_rows)
+            })),
+        ));
+    }
+
+    let (content_type, body) = if format == "csv" {
+        let csv = audit_log_to_csv(&entries).map_err(|_err| (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "error": "Could not export the audit log." })),
+        ))?;
+        ("text/csv; charset=utf-8", csv)
+    } else {
+        ("application/json", json!(entries).to_string())
+    };
+
+    let disposition = format!(
+        "attachment; filename=\"audit-log-{}.{}\"",
+        chrono::Utc::now().format("%Y%m%d%H%M%S"),
+        format
+    );
+
+    Ok((
+        [(header::CONTENT_TYPE, content_type.to_string()), (header::CONTENT_DISPOSITION, disposition)],
+        body,
+    ).into_response())
+}
+
+// Verify that no audit log entry has been changed or removed
+#[utoipa::path(
+    get,
+    path = "/audit/verify",
+    tag = "audit",
+    security(
+        ("jwt_token" = [])
+    ),
+    responses(
+        (status = 200, description = "Result of verifying the hash chain", body = AuditLogVerification),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 500, description = "Internal server error")
+    )
+)]
+#[instrument(skip(state))]
+pub async fn get_audit_log_verify(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<AuditLogVerification>, (StatusCode, Json<serde_json::Value>)> {
+    match verify_audit_chain(&state.database).await {
+        Ok(verification) => Ok(Json(verification)),
+        Err(_err) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "error": "Could not verify the audit log." })),
+        )),
+    }
+}
@@ -0,0 +1,193 @@
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use uuid::Uuid;
+use serde_json::json;
+use tracing::instrument;
+use std::sync::Arc;
+
+use crate::models::user::User;
+use crate::models::billing::{BillingPrice, BillingStatement, BillingStatementsQuery, BillingStatementExportQuery};
+use crate::database::billing::{fetch_billing_prices_from_db, fetch_billing_statements_by_user_from_db, fetch_billing_statement_by_id_from_db};
+use crate::utils::billing::{statement_to_csv, statement_to_html};
+use crate::routes::AppState;
+
+// --- Route Handlers ---
+
+// Get the price list of all tiers
+#[utoipa::path(
+    get,
+    path = "/billing/prices",
+    tag = "billing",
+    security(
+        ("jwt_token" = [])
+    ),
+    responses(
+        (status = 200, description = "Successfully fetched all prices", body = [BillingPrice]),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 500, description = "Internal server error")
+    )
+)]
+#[instrument(skip(state))]
+pub async fn get_billing_prices(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<BillingPrice>>, (StatusCode, Json<serde_json::Value>)> {
+    match fetch_billing_prices_from_db(&state.database).await {
+        Ok(prices) => Ok(Json(prices)),
+        Err(_err) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "error": "Could not fetch the prices." })),
+        )),
+    }
+}
+
+// Get all statements of the current user, or of another user for admins
+#[utoipa::path(
+    get,
+    path = "/billing/statements",
+    tag = "billing",
+    security(
+        ("jwt_token" = [])
+    ),
+    params(BillingStatementsQuery),
+    responses(
+        (status = 200, description = "Successfully fetched all statements", body = [BillingStatement]),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 403, description = "Not allowed", body = serde_json::Value),
+        (status = 500, description = "Internal server error")
+    )
+)]
+#[instrument(skip(state))]
+pub async fn get_billing_statements(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<User>,
+    Query(query): Query<BillingStatementsQuery>,
+) -> Result<Json<Vec<BillingStatement>>, (StatusCode, Json<serde_json::Value>)> {
+    let user_id = query.user_id.unwrap_or(user.id);
+    if user_id != user.id && user.role_level != 2 {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({ "error": "Not allowed" })),
+        ));
+    }
+
+    match fetch_billing_statements_by_user_from_db(&state.database, user_id).await {
+        Ok(statements) => Ok(Json(statements)),
+        Err(_err) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "error": "Could not fetch the statements." })),
+        )),
+    }
+}
+
+// Get a single statement by id
+#[utoipa::path(
+    get,
+    path = "/billing/statements/{id}",
+    tag = "billing",
+    security(
+        ("jwt_token" = [])
+    ),
+    params(
+        ("id" = String, Path, description = "Statement ID")
+    ),
+    responses(
+        (status = 200, description = "Successfully fetched statement by ID", body = BillingStatement),
+        (status = 400, description = "Invalid UUID format"),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 404, description = "Statement not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+#[instrument(skip(state))]
+pub async fn get_billing_statement_by_id(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<User>,
+    Path(id): Path<String>,
+) -> Result<Json<BillingStatement>, (StatusCode, Json<serde_json::Value>)> {
+    fetch_statement(&state, &user, &id).await.map(Json)
+}
+
+// Download a statement as CSV or HTML
+#[utoipa::path(
+    get,
+    path = "/billing/statements/{id}/export",
+    tag = "billing",
+    security(
+        ("jwt_token" = [])
+    ),
+    params(
+        ("id" = String, Path, description = "Statement ID"),
+        BillingStatementExportQuery
+    ),
+    responses(
+        (status = 200, description = "The statement as a downloadable file", content((String = "text/csv"), (String = "text/html"))),
+        (status = 400, description = "Invalid UUID format or export format"),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 404, description = "Statement not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+#[instrument(skip(state))]
+pub async fn get_billing_statement_export(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<User>,
+    Path(id): Path<String>,
+    Query(query): Query<BillingStatementExportQuery>,
+) -> Result<Response, (StatusCode, Json<serde_json::Value>)> {
+    let format = query.format.unwrap_or_else(|| "html".to_string()).to_lowercase();
+    let statement = fetch_statement(&state, &user, &id).await?;
+
+    let (content_type, extension, body) = match format.as_str() {
+        "csv" => ("text/csv; charset=utf-8", "csv", statement_to_csv(&statement)),
+        "html" => ("text/html; charset=utf-8", "html", statement_to_html(&statement)),
+        _ => return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": "Format must be 'csv' or 'html'." })),
+        )),
+    };
+
+    let disposition = format!(
+        "attachment; filename=\"statement-{}.{}\"",
+        statement.period_start.format("%Y-%m"),
+        extension
+    );
+
+    Ok((
+        [(header::CONTENT_TYPE, content_type.to_string()), (header::CONTENT_DISPOSITION, disposition)],
+        body,
+    ).into_response())
+}
+
+// Fetches a statement, which is only visible to its owner and admins
+async fn fetch_statement(
+    state: &AppState,
+    user: &User,
+    id: &str,
+) -> Result<BillingStatement, (StatusCode, Json<serde_json::Value>)> {
+    let uuid = match Uuid::parse_str(id) {
+        Ok(uuid) => uuid,
+        Err(_) => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({ "error": "Invalid UUID format." })),
+            ));
+        }
+    };
+
+    match fetch_billing_statement_by_id_from_db(&state.database, uuid).await {
+        // Statements of other users are reported as not found, to avoid leaking their existence
+        Ok(Some(statement)) if statement.user_id == Some(user.id) || user.role_level == 2 => Ok(statement),
+        Ok(_) => Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": format!("Statement with ID '{}' not found", id) })),
+        )),
+        Err(_err) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "error": "Could not fetch the details of the statement." })),
+        )),
+    }
+}
@@ -0,0 +1,84 @@
+use axum::{extract::{Extension, Path, State}, Json, http::StatusCode};
+use serde_json::json;
+use tracing::{error, instrument};
+use uuid::Uuid;
+use std::sync::Arc;
+
+use crate::core::config::{get_env_u64, get_env_with_default};
+use crate::database::data_exports::fetch_data_export_by_id_from_db;
+use crate::models::data_export::DataExportResponse;
+use crate::models::user::User;
+use crate::storage::presign_url::generate_presigned_url;
+use crate::routes::AppState;
+
+// --- Route Handlers ---
+
+// Get the state of a data export of the current user
+#[utoipa::path(
+    get,
+    path = "/users/current/export/{id}",
+    tag = "user",
+    security(
+        ("jwt_token" = [])
+    ),
+    params(
+        ("id" = String, Path, description = "Export ID")
+    ),
+    responses(
+        (status = 200, description = "Successfully fetched the export, with a download link once completed", body = DataExportResponse),
+        (status = 400, description = "Invalid UUID format", body = serde_json::Value),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 404, description = "Export not found", body = serde_json::Value),
+        (status = 500, description = "Internal server error", body = serde_json::Value)
+    )
+)]
+#[instrument(skip(state, user))]
+pub async fn get_data_export_by_id(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<User>,
+    Path(id): Path<String>,
+) -> Result<Json<DataExportResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let uuid = match Uuid::parse_str(&id) {
+        Ok(uuid) => uuid,
+        Err(_) => return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": "Invalid UUID format." })),
+        )),
+    };
+
+    let export = match fetch_data_export_by_id_from_db(&state.database, uuid, user.id).await {
+        Ok(Some(export)) => export,
+        Ok(None) => return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": format!("Export with ID '{}' not found", id) })),
+        )),
+        Err(e) => {
+            error!("Error fetching data export: {}", e);
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Could not fetch the export." })),
+            ));
+        }
+    };
+
+    // Every request gets a fresh link, as the previous one may have expired
+    let download_url = match (export.status.as_str(), export.object_key.as_deref()) {
+        ("completed", Some(object_key)) => {
+            let bucket = get_env_with_default("STORAGE_BUCKET_DATA_EXPORTS", "data-exports");
+            let expiry = get_env_u64("DATA_EXPORT_URL_EXPIRY_SECONDS", 86400).min(604800);
+            match generate_presigned_url(&state.storage, &bucket, object_key, expiry).await {
+                Ok(url) => Some(url),
+                Err(e) => {
+                    error!("Error generating the download link of a data export: {}", e);
+                    return Err((
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        Json(json!({ "error": "Could not generate the download link." })),
+                    ));
+                }
+            }
+        }
+        _ => None,
+    };
+
+    Ok(Json(DataExportResponse { export, download_url }))
+}
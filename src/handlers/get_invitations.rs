@@ -0,0 +1,143 @@
+use axum::{extract::{Path, Query, State}, Json, http::StatusCode};
+use serde_json::json;
+use tracing::{error, instrument};
+use uuid::Uuid;
+use std::sync::Arc;
+
+use crate::database::invitations::{fetch_invitations_from_db, fetch_invitation_by_id_from_db};
+use crate::models::invitation::{Invitation, InvitationListQuery, InvitationPreviewResponse, InvitationTokenQuery};
+use crate::utils::auth::decode_invitation_token;
+use crate::routes::AppState;
+
+// --- Route Handlers ---
+
+// Get all invitations
+#[utoipa::path(
+    get,
+    path = "/invitations/all",
+    tag = "invitation",
+    security(
+        ("jwt_token" = [])
+    ),
+    params(InvitationListQuery),
+    responses(
+        (status = 200, description = "Successfully fetched all invitations", body = [Invitation]),
+        (status = 400, description = "Invalid status", body = serde_json::Value),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 500, description = "Internal server error", body = serde_json::Value)
+    )
+)]
+#[instrument(skip(state))]
+pub async fn get_all_invitations(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<InvitationListQuery>,
+) -> Result<Json<Vec<Invitation>>, (StatusCode, Json<serde_json::Value>)> {
+    if let Some(status) = query.status.as_deref() {
+        if !["pending", "accepted", "revoked", "expired"].contains(&status) {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({ "error": "Status must be one of: pending, accepted, revoked, expired" }))
+            ));
+        }
+    }
+
+    match fetch_invitations_from_db(&state.database, query.status.as_deref()).await {
+        Ok(invitations) => Ok(Json(invitations)),
+        Err(e) => {
+            error!("Error fetching invitations: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Could not fetch the invitations." }))
+            ))
+        }
+    }
+}
+
+// Get an invitation by ID
+#[utoipa::path(
+    get,
+    path = "/invitations/{id}",
+    tag = "invitation",
+    security(
+        ("jwt_token" = [])
+    ),
+    params(
+        ("id" = String, Path, description = "Invitation ID")
+    ),
+    responses(
+        (status = 200, description = "Successfully fetched the invitation", body = Invitation),
+        (status = 400, description = "Invalid UUID format", body = serde_json::Value),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 404, description = "Invitation not found", body = serde_json::Value),
+        (status = 500, description = "Internal server error", body = serde_json::Value)
+    )
+)]
+#[instrument(skip(state))]
+pub async fn get_invitation_by_id(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<Invitation>, (StatusCode, Json<serde_json::Value>)> {
+    let uuid = Uuid::parse_str(&id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(json!({ "error": "Invalid UUID format." }))))?;
+
+    match fetch_invitation_by_id_from_db(&state.database, uuid).await {
+        Ok(Some(invitation)) => Ok(Json(invitation)),
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": format!("Invitation with ID '{}' not found.", id) }))
+        )),
+        Err(e) => {
+            error!("Error fetching invitation: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Could not fetch the invitation." }))
+            ))
+        }
+    }
+}
+
+// Check the link of an invitation before accepting it
+#[utoipa::path(
+    get,
+    path = "/invitations/accept",
+    tag = "invitation",
+    params(InvitationTokenQuery),
+    responses(
+        (status = 200, description = "The invitation can be accepted", body = InvitationPreviewResponse),
+        (status = 400, description = "Invalid link", body = serde_json::Value),
+        (status = 410, description = "The invitation has expired, been revoked or already been accepted", body = serde_json::Value),
+        (status = 429, description = "Too many requests", body = serde_json::Value),
+        (status = 500, description = "Internal server error", body = serde_json::Value)
+    )
+)]
+#[instrument(skip(state, query))]
+pub async fn get_invitation_preview(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<InvitationTokenQuery>,
+) -> Result<Json<InvitationPreviewResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let claims = decode_invitation_token(&query.token)
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(json!({ "error": e }))))?;
+    let id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(json!({ "error": "Invalid invitation link." }))))?;
+
+    match fetch_invitation_by_id_from_db(&state.database, id).await {
+        Ok(Some(invitation))
+            if invitation.status == "pending"
+                && invitation.token_nonce == claims.nonce
+                && invitation.expires_at > chrono::Utc::now() =>
+        {
+            Ok(Json(InvitationPreviewResponse { email: invitation.email, expires_at: invitation.expires_at }))
+        }
+        Ok(_) => Err((
+            StatusCode::GONE,
+            Json(json!({ "error": "This invitation is no longer valid." }))
+        )),
+        Err(e) => {
+            error!("Error fetching invitation: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Could not fetch the invitation." }))
+            ))
+        }
+    }
+}
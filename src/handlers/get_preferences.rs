@@ -0,0 +1,31 @@
+use axum::{extract::{Extension, State}, Json};
+use tracing::instrument;
+use std::sync::Arc;
+
+use crate::models::preference::UserPreferences;
+use crate::models::user::User;
+use crate::utils::preferences::load_user_preferences;
+use crate::routes::AppState;
+
+// --- Route Handlers ---
+
+// Get the preferences of the current user
+#[utoipa::path(
+    get,
+    path = "/users/current/preferences",
+    tag = "user",
+    security(
+        ("jwt_token" = [])
+    ),
+    responses(
+        (status = 200, description = "The preferences of the current user, with defaults for everything that was not set", body = UserPreferences),
+        (status = 401, description = "Unauthorized", body = serde_json::Value)
+    )
+)]
+#[instrument(skip(state))]
+pub async fn get_current_user_preferences(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<User>,
+) -> Json<UserPreferences> {
+    Json(load_user_preferences(&state.database, current_user.id).await)
+}
@@ -0,0 +1,89 @@
+use axum::{
+    extract::{State, Path},
+    Json,
+    http::StatusCode,
+};
+use uuid::Uuid;
+use serde_json::json;
+use tracing::instrument; // For logging
+use std::sync::Arc;
+
+use crate::models::tier::Tier;
+use crate::database::tiers::{fetch_all_tiers_from_db, fetch_tier_by_id_from_db};
+use crate::routes::AppState;
+
+// --- Route Handlers ---
+
+// Get all tiers
+#[utoipa::path(
+    get,
+    path = "/tiers/all",
+    tag = "tier",
+    security(
+        ("jwt_token" = [])
+    ),
+    responses(
+        (status = 200, description = "Successfully fetched all tiers", body = [Tier]),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 500, description = "Internal server error")
+    )
+)]
+#[instrument(skip(state))]
+pub async fn get_all_tiers(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<Tier>>, (StatusCode, Json<serde_json::Value>)> {
+    match fetch_all_tiers_from_db(&state.database).await {
+        Ok(tiers) => Ok(Json(tiers)),
+        Err(_err) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "error": "Could not fetch the tiers." })),
+        )),
+    }
+}
+
+// Get a single tier by id
+#[utoipa::path(
+    get,
+    path = "/tiers/{id}",
+    tag = "tier",
+    security(
+        ("jwt_token" = [])
+    ),
+    params(
+        ("id" = String, Path, description = "Tier ID")
+    ),
+    responses(
+        (status = 200, description = "Successfully fetched tier by ID", body = Tier),
+        (status = 400, description = "Invalid UUID format"),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 404, description = "Tier not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+#[instrument(skip(state))]
+pub async fn get_tiers_by_id(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<Tier>, (StatusCode, Json<serde_json::Value>)> {
+    let uuid = match Uuid::parse_str(&id) {
+        Ok(uuid) => uuid,
+        Err(_) => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({ "error": "Invalid UUID format." })),
+            ));
+        }
+    };
+
+    match fetch_tier_by_id_from_db(&state.database, uuid).await {
+        Ok(Some(tier)) => Ok(Json(tier)),
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": format!("Tier with ID '{}' not found", id) })),
+        )),
+        Err(_err) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "error": "Could not fetch the details of the tier." })),
+        )),
+    }
+}
@@ -0,0 +1,91 @@
+use axum::{
+    extract::{State, Extension, Path},
+    Json,
+    http::StatusCode,
+};
+use uuid::Uuid;
+use serde_json::json;
+use tracing::{error, instrument};
+use std::sync::Arc;
+
+use crate::models::todo::TodoList;
+use crate::models::user::User;
+use crate::database::todo_lists::{fetch_accessible_todo_list_by_id_from_db, fetch_accessible_todo_lists_from_db};
+use crate::routes::AppState;
+
+// --- Route Handlers ---
+
+// Get all lists of the current user, followed by the lists shared with the user
+#[utoipa::path(
+    get,
+    path = "/todos/lists/all",
+    tag = "todo",
+    security(
+        ("jwt_token" = [])
+    ),
+    responses(
+        (status = 200, description = "Successfully fetched all lists, own lists first in their manual order", body = [TodoList]),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 500, description = "Internal server error", body = serde_json::Value)
+    )
+)]
+#[instrument(skip(state, user))]
+pub async fn get_all_todo_lists(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<User>,
+) -> Result<Json<Vec<TodoList>>, (StatusCode, Json<serde_json::Value>)> {
+    match fetch_accessible_todo_lists_from_db(&state.database, user.id).await {
+        Ok(lists) => Ok(Json(lists)),
+        Err(e) => {
+            error!("Error fetching the todo lists of user {}: {}", user.id, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Could not fetch the lists." })),
+            ))
+        }
+    }
+}
+
+// Get a single list by id, owned by or shared with the user
+#[utoipa::path(
+    get,
+    path = "/todos/lists/{id}",
+    tag = "todo",
+    security(
+        ("jwt_token" = [])
+    ),
+    params(
+        ("id" = String, Path, description = "List ID")
+    ),
+    responses(
+        (status = 200, description = "Successfully fetched list by ID", body = TodoList),
+        (status = 400, description = "Invalid UUID format", body = serde_json::Value),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 404, description = "List not found", body = serde_json::Value),
+        (status = 500, description = "Internal server error", body = serde_json::Value)
+    )
+)]
+#[instrument(skip(state, user))]
+pub async fn get_todo_list_by_id(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<User>,
+    Path(id): Path<String>,
+) -> Result<Json<TodoList>, (StatusCode, Json<serde_json::Value>)> {
+    let uuid = Uuid::parse_str(&id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(json!({ "error": "Invalid UUID format." }))))?;
+
+    match fetch_accessible_todo_list_by_id_from_db(&state.database, uuid, user.id).await {
+        Ok(Some(list)) => Ok(Json(list)),
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": format!("List with ID '{}' not found.", id) })),
+        )),
+        Err(e) => {
+            error!("Error fetching todo list {}: {}", uuid, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Could not fetch the details of the list." })),
+            ))
+        }
+    }
+}
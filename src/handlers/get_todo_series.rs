@@ -0,0 +1,113 @@
+use axum::{
+    extract::{State, Extension, Path, Query},
+    Json,
+    http::StatusCode,
+};
+use chrono::Utc;
+use tokio::task;
+use uuid::Uuid;
+use serde_json::json;
+use tracing::{error, instrument};
+use std::sync::Arc;
+
+use crate::models::todo::{TodoOccurrence, TodoSeries, TodoSeriesPreviewQuery};
+use crate::models::user::User;
+use crate::database::todo_series::fetch_todo_series_by_id_from_db;
+use crate::routes::AppState;
+
+// --- Route Handlers ---
+
+// Get a recurring series by id
+#[utoipa::path(
+    get,
+    path = "/todos/series/{id}",
+    tag = "todo",
+    security(
+        ("jwt_token" = [])
+    ),
+    params(
+        ("id" = String, Path, description = "Series ID")
+    ),
+    responses(
+        (status = 200, description = "Successfully fetched series by ID", body = TodoSeries),
+        (status = 400, description = "Invalid UUID format", body = serde_json::Value),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 404, description = "Series not found", body = serde_json::Value),
+        (status = 500, description = "Internal server error", body = serde_json::Value)
+    )
+)]
+#[instrument(skip(state, user))]
+pub async fn get_todo_series_by_id(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<User>,
+    Path(id): Path<String>,
+) -> Result<Json<TodoSeries>, (StatusCode, Json<serde_json::Value>)> {
+    fetch_series(&state, &id, user.id).await.map(Json)
+}
+
+// Preview the upcoming occurrences of a recurring series
+#[utoipa::path(
+    get,
+    path = "/todos/series/{id}/preview",
+    tag = "todo",
+    security(
+        ("jwt_token" = [])
+    ),
+    params(
+        ("id" = String, Path, description = "Series ID"),
+        TodoSeriesPreviewQuery
+    ),
+    responses(
+        (status = 200, description = "The occurrences after the latest created one, skipped ones included", body = [TodoOccurrence]),
+        (status = 400, description = "Invalid UUID format or limit", body = serde_json::Value),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 404, description = "Series not found", body = serde_json::Value),
+        (status = 500, description = "Internal server error", body = serde_json::Value)
+    )
+)]
+#[instrument(skip(state, user))]
+pub async fn get_todo_series_preview(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<User>,
+    Path(id): Path<String>,
+    Query(query): Query<TodoSeriesPreviewQuery>,
+) -> Result<Json<Vec<TodoOccurrence>>, (StatusCode, Json<serde_json::Value>)> {
+    let limit = query.limit.unwrap_or(10);
+    if !(1..=100).contains(&limit) {
+        return Err((StatusCode::BAD_REQUEST, Json(json!({ "error": "Limit must be between 1 and 100" }))));
+    }
+
+    let series = fetch_series(&state, &id, user.id).await?;
+    let after = series.latest_occurrence_at.unwrap_or_else(Utc::now);
+
+    // Expanding the rule can take a while, so it stays off the async runtime
+    let series_id = series.id;
+    task::spawn_blocking(move || series.occurrences_after(after, limit))
+        .await
+        .map(Json)
+        .map_err(|e| {
+            error!("Error expanding todo series {}: {}", series_id, e);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "Could not preview the series." })))
+        })
+}
+
+// Fetches a series of the user, as a 404 otherwise
+async fn fetch_series(state: &AppState, id: &str, user_id: Uuid) -> Result<TodoSeries, (StatusCode, Json<serde_json::Value>)> {
+    let uuid = Uuid::parse_str(id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(json!({ "error": "Invalid UUID format." }))))?;
+
+    match fetch_todo_series_by_id_from_db(&state.database, uuid, user_id).await {
+        Ok(Some(series)) => Ok(series),
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": format!("Series with ID '{}' not found.", id) })),
+        )),
+        Err(e) => {
+            error!("Error fetching todo series {}: {}", uuid, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Could not fetch the details of the series." })),
+            ))
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use axum::{
-    extract::{State, Extension, Path},
+    extract::{State, Extension, Path, Query},
     Json,
     http::StatusCode,
 };
@@ -10,12 +10,16 @@ use std::sync::Arc;
 
 use crate::models::todo::*;
 use crate::models::user::*;
-use crate::database::todos::{fetch_all_todos_from_db, fetch_todo_by_id_from_db};
+use crate::models::pagination::{Page, PaginationQuery};
+use crate::models::preference::TodoView;
+use crate::database::list_query::ListParams;
+use crate::database::todos::{fetch_todos_page_from_db, fetch_todo_by_id_from_db, TODO_SORT_FIELDS};
+use crate::utils::preferences::load_user_preferences;
 use crate::routes::AppState;
 
 // --- Route Handlers ---
 
-// Get all todos
+// Get a page of todos
 #[utoipa::path(
     get,
     path = "/todos/all",
@@ -23,8 +27,10 @@ use crate::routes::AppState;
     security(
         ("jwt_token" = [])
     ),
+    params(PaginationQuery, TodoListFilter),
     responses(
-        (status = 200, description = "Successfully fetched all todos", body = [Todo]),
+        (status = 200, description = "Successfully fetched a page of todos", body = Page<Todo>),
+        (status = 400, description = "Invalid pagination or filter parameters", body = serde_json::Value),
         (status = 401, description = "Unauthorized", body = serde_json::Value),
         (status = 500, description = "Internal server error")
     )
@@ -33,9 +39,25 @@ use crate::routes::AppState;
 pub async fn get_all_todos(
     State(state): State<Arc<AppState>>,
     Extension(user): Extension<User>,  // Extract current user from the request extensions
-) -> Result<Json<Vec<Todo>>, (StatusCode, Json<serde_json::Value>)> {
-    match fetch_all_todos_from_db(&state.database, user.id).await {
-        Ok(todos) => Ok(Json(todos)),
+    Query(pagination): Query<PaginationQuery>,
+    Query(filter): Query<TodoListFilter>,
+) -> Result<Json<Page<Todo>>, (StatusCode, Json<serde_json::Value>)> {
+    let params = ListParams::parse(&pagination, TODO_SORT_FIELDS).map_err(|e| (
+        StatusCode::BAD_REQUEST,
+        Json(json!({ "error": e })),
+    ))?;
+
+    // Fall back to the view the user prefers
+    let view = match filter.status.as_deref() {
+        Some(status) => TodoView::parse(status).ok_or_else(|| (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": "Status must be 'all', 'open' or 'completed'." })),
+        ))?,
+        None => load_user_preferences(&state.database, user.id).await.default_todo_view,
+    };
+
+    match fetch_todos_page_from_db(&state.database, user.id, view.completed(), &filter, &params).await {
+        Ok(page) => Ok(Json(page)),
         Err(_err) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({ "error": "Could not fetch the details of the todo." })),
@@ -1,13 +1,16 @@
-use axum::{extract::{Extension, State}, Json};
+use axum::{extract::{Extension, Query, State}, Json};
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use serde_json::json;
 use tracing::instrument;
 use std::sync::Arc;
+use chrono::Utc;
 
 use crate::models::user::*;
 use crate::models::usage::*;
-use crate::database::usage::fetch_usage_count_from_db;
+use crate::database::usage::{fetch_usage_count_from_db, fetch_daily_usage_from_db, fetch_usage_count_since_from_db};
+use crate::database::tiers::fetch_tier_by_level_from_db;
+use crate::utils::quota::quota_period;
 use crate::routes::AppState;
 
 // Get usage for the last 24 hours
@@ -61,3 +64,94 @@ pub async fn get_usage_last_week(
         )),
     }
 }
+
+// Get the daily usage history
+#[utoipa::path(
+    get,
+    path = "/usage/history",
+    tag = "usage",
+    security(
+        ("jwt_token" = [])
+    ),
+    params(UsageHistoryQuery),
+    responses(
+        (status = 200, description = "Successfully fetched the daily usage history", body = [UsageResponseDaily]),
+        (status = 400, description = "Invalid amount of days", body = serde_json::Value),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 500, description = "Internal server error")
+    )
+)]
+#[instrument(skip(state))]
+pub async fn get_usage_history(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<User>,
+    Query(query): Query<UsageHistoryQuery>,
+) -> impl IntoResponse {
+    let days = query.days.unwrap_or(30);
+    if !(1..=365).contains(&days) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": "Days must be between 1 and 365." }))
+        ));
+    }
+
+    match fetch_daily_usage_from_db(&state.database, user.id, days).await {
+        Ok(history) => Ok(Json(history)),
+        Err(_) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "error": "Could not fetch the usage data." }))
+        )),
+    }
+}
+
+// Get the consumption of the monthly quota
+#[utoipa::path(
+    get,
+    path = "/usage/quota",
+    tag = "usage",
+    security(
+        ("jwt_token" = [])
+    ),
+    responses(
+        (status = 200, description = "Successfully fetched the quota consumption of the current period", body = UsageQuotaResponse),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 500, description = "Internal server error")
+    )
+)]
+#[instrument(skip(state))]
+pub async fn get_usage_quota(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<User>,
+) -> impl IntoResponse {
+    let tier = match fetch_tier_by_level_from_db(&state.database, user.tier_level).await {
+        Ok(Some(tier)) => tier,
+        _ => return Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "error": "Could not fetch the tier information." }))
+        )),
+    };
+
+    let (period_start, reset_date) = quota_period(Utc::now().date_naive());
+
+    let requests_this_period = match fetch_usage_count_since_from_db(&state.database, user.id, period_start).await {
+        Ok(count) => count,
+        Err(_) => return Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "error": "Could not fetch the usage data." }))
+        )),
+    };
+
+    let requests_per_month = tier.requests_per_month.map(i64::from);
+
+    Ok(Json(UsageQuotaResponse {
+        period_start,
+        reset_date,
+        requests_this_period,
+        requests_per_month,
+        remaining: requests_per_month.map(|quota| (quota - requests_this_period).max(0)),
+        percentage_used: requests_per_month
+            .filter(|quota| *quota > 0)
+            .map(|quota| (requests_this_period as f64 / quota as f64 * 10000.0).round() / 100.0),
+        requests_per_day: tier.requests_per_day as i64,
+    }))
+}
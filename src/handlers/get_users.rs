@@ -1,21 +1,41 @@
 use axum::{
-    extract::{State, Extension, Path},
+    extract::{State, Extension, Path, Query},
     Json,
-    http::StatusCode,
+    http::{header, HeaderMap, StatusCode},
 };
-use axum::response::IntoResponse;
+use axum::response::{IntoResponse, Response};
+use lazy_static::lazy_static;
+use moka::future::Cache;
 use serde_json::json;
-use tracing::instrument;
+use tracing::{error, instrument};
 use uuid::Uuid;
 use std::sync::Arc;
+use std::time::Duration;
 
-use crate::models::user::{User, UserGetResponse};
-use crate::database::users::{fetch_all_active_users_from_db, fetch_active_user_by_field_from_db};
+use crate::core::config::get_env_u64;
+use crate::models::user::{AvatarQuery, User, UserGetResponse, UserListFilter, UserSearchQuery, UserSearchResult};
+use crate::models::pagination::{Page, PaginationQuery};
+use crate::database::users::{fetch_users_page_from_db, fetch_active_user_by_field_from_db, search_users_in_db, USER_SORT_FIELDS};
+use crate::utils::highlight::highlight;
+use crate::utils::avatar::{etag, etag_matches, initials, initials_avatar_svg, resolve_avatar_key, select_avatar_size};
+use crate::storage::download::{download_from_storage, split_storage_url};
+use crate::database::list_query::ListParams;
 use crate::routes::AppState;
 
 use crate::storage::presign_url::generate_presigned_url;
 
-// Get all users
+lazy_static! {
+    // Pre-signed avatar URLs per object key, reused for half of their validity
+    static ref AVATAR_URL_CACHE: Cache<String, String> = Cache::builder()
+        .time_to_live(Duration::from_secs(avatar_url_expiry_seconds() / 2))
+        .build();
+}
+
+fn avatar_url_expiry_seconds() -> u64 {
+    get_env_u64("AVATAR_URL_EXPIRY_SECONDS", 3600).max(60)
+}
+
+// Get a page of users
 #[utoipa::path(
     get,
     path = "/users/all",
@@ -23,20 +43,31 @@ use crate::storage::presign_url::generate_presigned_url;
     security(
         ("jwt_token" = [])
     ),
+    params(PaginationQuery, UserListFilter),
     responses(
-        (status = 200, description = "Successfully fetched all users", body = [UserGetResponse]),
+        (status = 200, description = "Successfully fetched a page of users", body = Page<UserGetResponse>),
+        (status = 400, description = "Invalid pagination or filter parameters", body = serde_json::Value),
         (status = 401, description = "Unauthorized", body = serde_json::Value),
         (status = 500, description = "Internal server error")
     )
 )]
 #[instrument(skip(state))]
-pub async fn get_all_users(State(state): State<Arc<AppState>>) -> impl IntoResponse {
-    match fetch_all_active_users_from_db(&state.database).await {
-        Ok(users) => {
+pub async fn get_all_users(
+    State(state): State<Arc<AppState>>,
+    Query(pagination): Query<PaginationQuery>,
+    Query(filter): Query<UserListFilter>,
+) -> impl IntoResponse {
+    let params = ListParams::parse(&pagination, USER_SORT_FIELDS).map_err(|e| (
+        StatusCode::BAD_REQUEST,
+        Json(json!({ "error": e })),
+    ))?;
+
+    match fetch_users_page_from_db(&state.database, &filter, &params).await {
+        Ok(page) => {
             // For each user, add the presigned URL if profile_picture_url is present
-            let mut enriched_users = Vec::with_capacity(users.len());
-            for user in users {
-                let mut user_json = serde_json::to_value(&user)
+            let mut enriched_users = Vec::with_capacity(page.items.len());
+            for user in &page.items {
+                let mut user_json = serde_json::to_value(user)
                     .expect("User should serialize to JSON");
 
                 if let Some(ref stored_url) = user.profile_picture_url {
@@ -59,7 +90,13 @@ pub async fn get_all_users(State(state): State<Arc<AppState>>) -> impl IntoRespo
                 enriched_users.push(user_json);
             }
 
-            Ok(Json(enriched_users))
+            Ok(Json(Page {
+                items: enriched_users,
+                total: page.total,
+                limit: page.limit,
+                offset: page.offset,
+                next_cursor: page.next_cursor,
+            }))
         }
         Err(_) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -68,6 +105,75 @@ pub async fn get_all_users(State(state): State<Arc<AppState>>) -> impl IntoRespo
     }
 }
 
+// Search users, for support staff
+#[utoipa::path(
+    get,
+    path = "/users/search",
+    tag = "user",
+    security(
+        ("jwt_token" = [])
+    ),
+    params(UserSearchQuery),
+    responses(
+        (status = 200, description = "Users matching the search, best matches first", body = [UserSearchResult]),
+        (status = 400, description = "Invalid search parameters", body = serde_json::Value),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 500, description = "Internal server error")
+    )
+)]
+#[instrument(skip(state))]
+pub async fn search_users(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<UserSearchQuery>,
+) -> Result<Json<Vec<UserSearchResult>>, (StatusCode, Json<serde_json::Value>)> {
+    let q = query.q.trim();
+    if q.chars().count() < 2 || q.len() > 100 {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": "Search terms must be between 2 and 100 characters." })),
+        ));
+    }
+
+    let limit = query.limit.unwrap_or(20);
+    if !(1..=100).contains(&limit) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": "Limit must be between 1 and 100." })),
+        ));
+    }
+
+    let mut results = search_users_in_db(&state.database, q, limit).await.map_err(|_| (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(json!({ "error": "Could not search the users." })),
+    ))?;
+
+    // Highlight the terms, and the search as a whole for names spanning multiple words
+    let terms: Vec<&str> = std::iter::once(q).chain(q.split_whitespace()).collect();
+    for result in &mut results {
+        let user = &result.user;
+        let full_name = [user.first_name.as_deref(), user.last_name.as_deref()]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<&str>>()
+            .join(" ");
+
+        let fields = [
+            ("username", Some(user.username.as_str()), None),
+            ("email", Some(user.email.as_str()), None),
+            ("name", Some(full_name.as_str()), None),
+            ("description", user.description.as_deref(), Some(160)),
+        ];
+
+        for (field, text, max_len) in fields {
+            if let Some(highlighted) = text.and_then(|text| highlight(text, &terms, max_len)) {
+                result.highlights.insert(field.to_string(), highlighted);
+            }
+        }
+    }
+
+    Ok(Json(results))
+}
+
 // Get a single user by ID or current user
 #[utoipa::path(
     get,
@@ -142,4 +248,127 @@ pub async fn get_users_by_id(
             Json(json!({ "error": "Could not fetch the users details." })),
         )),
     }
-}
\ No newline at end of file
+}
+
+// Get the avatar of a user, in the requested size
+#[utoipa::path(
+    get,
+    path = "/users/{id}/avatar",
+    tag = "user",
+    security(
+        ("jwt_token" = [])
+    ),
+    params(
+        ("id" = String, Path, description = "User ID or 'current'"),
+        AvatarQuery
+    ),
+    responses(
+        (status = 200, description = "The avatar image, or generated initials when no profile picture was uploaded", content((Vec<u8> = "image/webp"), (String = "image/svg+xml"))),
+        (status = 302, description = "Redirect to a pre-signed URL of the avatar image"),
+        (status = 304, description = "The avatar matches the `If-None-Match` header"),
+        (status = 400, description = "Invalid UUID format or mode", body = serde_json::Value),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 404, description = "User not found", body = serde_json::Value),
+        (status = 500, description = "Internal server error")
+    )
+)]
+#[instrument(skip(state, headers))]
+pub async fn get_user_avatar(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Query(query): Query<AvatarQuery>,
+    Extension(current_user): Extension<User>,
+    headers: HeaderMap,
+) -> Result<Response, (StatusCode, Json<serde_json::Value>)> {
+    let user_id = if id == "current" {
+        current_user.id
+    } else {
+        Uuid::parse_str(&id).map_err(|_| (StatusCode::BAD_REQUEST, Json(json!({ "error": "Invalid UUID format." }))))?
+    };
+
+    let mode = query.mode.as_deref().unwrap_or("redirect");
+    if mode != "redirect" && mode != "stream" {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": "Mode must be 'redirect' or 'stream'." })),
+        ));
+    }
+
+    let user = match fetch_active_user_by_field_from_db(&state.database, "id", &user_id.to_string()).await {
+        Ok(Some(user)) => user,
+        Ok(None) => return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": format!("User with ID '{}' not found", user_id) })),
+        )),
+        Err(_) => return Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "error": "Could not fetch the users details." })),
+        )),
+    };
+
+    let size = select_avatar_size(query.size);
+    let if_none_match = headers.get(header::IF_NONE_MATCH).and_then(|value| value.to_str().ok());
+    // Avatars can change at any time, so clients revalidate with the ETag on every use
+    let cache_control = (header::CACHE_CONTROL, "private, no-cache".to_string());
+
+    let stored = user.profile_picture_url.as_deref().and_then(|url| split_storage_url(&state.storage, url));
+    let Some((bucket, stored_key)) = stored else {
+        let svg = initials_avatar_svg(
+            user.id,
+            &initials(user.first_name.as_deref(), user.last_name.as_deref(), &user.username),
+            size,
+        );
+        let tag = etag(svg.as_bytes());
+        if if_none_match.is_some_and(|value| etag_matches(value, &tag)) {
+            return Ok((StatusCode::NOT_MODIFIED, [(header::ETAG, tag), cache_control]).into_response());
+        }
+        return Ok((
+            [(header::CONTENT_TYPE, "image/svg+xml".to_string()), (header::ETAG, tag), cache_control],
+            svg,
+        ).into_response());
+    };
+
+    // Object keys contain the upload time, so a new picture always gets a new key
+    let object_key = resolve_avatar_key(stored_key, size);
+    let tag = etag(object_key.as_bytes());
+    if if_none_match.is_some_and(|value| etag_matches(value, &tag)) {
+        return Ok((StatusCode::NOT_MODIFIED, [(header::ETAG, tag), cache_control]).into_response());
+    }
+
+    if mode == "stream" {
+        let data = download_from_storage(&state.storage, bucket, &object_key).await.map_err(|e| {
+            error!("Avatar download error: {e}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Could not fetch the avatar." })),
+            )
+        })?;
+        return Ok((
+            [(header::CONTENT_TYPE, "image/webp".to_string()), (header::ETAG, tag), cache_control],
+            data,
+        ).into_response());
+    }
+
+    let cache_key = format!("{}/{}", bucket, object_key);
+    let presigned_url = match AVATAR_URL_CACHE.get(&cache_key).await {
+        Some(url) => url,
+        None => {
+            let url = generate_presigned_url(&state.storage, bucket, &object_key, avatar_url_expiry_seconds())
+                .await
+                .map_err(|e| {
+                    error!("Presign error: {e}");
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        Json(json!({ "error": "Failed to generate presigned URL" })),
+                    )
+                })?;
+            AVATAR_URL_CACHE.insert(cache_key, url.clone()).await;
+            url
+        }
+    };
+
+    Ok((
+        StatusCode::FOUND,
+        [(header::LOCATION, presigned_url), (header::ETAG, tag), cache_control],
+    ).into_response())
+}
@@ -0,0 +1,330 @@
+use axum::{
+    body::Bytes,
+    extract::{Extension, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    Json,
+};
+use chrono::{Duration, Utc};
+use rand::Rng;
+use rand::distributions::Alphanumeric;
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::task;
+use tracing::{error, instrument};
+use validator::Validate;
+
+use crate::core::config::get_env_u64;
+use crate::database::users::{fetch_taken_usernames_and_emails_from_db, insert_imported_users_into_db, insert_user_password_reset_code_into_db};
+use crate::i18n::catalog::{t, t_with};
+use crate::i18n::locale::Locale;
+use crate::mail::send::send_mail;
+use crate::models::user::{User, UserInsertBody};
+use crate::models::user_import::{UserImportInsert, UserImportQuery, UserImportResponse, UserImportRowResult};
+use crate::utils::auth::{generate_totp_secret, hash_password};
+use crate::utils::user_import::{parse_import_rows, validation_messages};
+use crate::models::audit::AuditEvent;
+use crate::utils::audit::{record_audit_event, AuditContext};
+use crate::routes::AppState;
+
+// --- Route Handler ---
+
+// Import users from a CSV or JSON file
+#[utoipa::path(
+    post,
+    path = "/users/import",
+    tag = "user",
+    security(
+        ("jwt_token" = [])
+    ),
+    params(UserImportQuery),
+    request_body(
+        content((String = "text/csv"), (Vec<crate::models::user_import::UserImportRow> = "application/json")),
+        description = "The users to import, as CSV (with a header row) or as a JSON array"
+    ),
+    responses(
+        (status = 200, description = "Import report, per row", body = UserImportResponse),
+        (status = 400, description = "Invalid options or unreadable file", body = serde_json::Value),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 422, description = "Transactional import with invalid rows, nothing was created", body = UserImportResponse),
+        (status = 500, description = "Internal server error", body = serde_json::Value)
+    )
+)]
+#[instrument(skip(state, admin, audit, headers, body))]
+pub async fn import_users(
+    State(state): State<Arc<AppState>>,
+    Extension(admin): Extension<User>,
+    audit: AuditContext,
+    Query(query): Query<UserImportQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<UserImportResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let dry_run = query.dry_run.unwrap_or(false);
+    let send_invitations = query.send_invitations.unwrap_or(false);
+    let mode = query.mode.unwrap_or_else(|| "transactional".to_string());
+    if mode != "transactional" && mode != "partial" {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": "Mode must be 'transactional' or 'partial'." }))
+        ));
+    }
+
+    let content_type = headers.get(header::CONTENT_TYPE).and_then(|v| v.to_str().ok()).unwrap_or("");
+    let max_rows = get_env_u64("USER_IMPORT_MAX_ROWS", 100) as usize;
+    let parsed = parse_import_rows(content_type, &body, max_rows)
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(json!({ "error": e }))))?;
+
+    // 1. Validate every row on its own, exactly like a single new user
+    let mut rows = Vec::with_capacity(parsed.len());
+    let mut valid: Vec<Option<UserInsertBody>> = Vec::with_capacity(parsed.len());
+    let mut seen_usernames = HashSet::new();
+    let mut seen_emails = HashSet::new();
+
+    for (index, parsed_row) in parsed.into_iter().enumerate() {
+        let mut result = UserImportRowResult {
+            row: index + 1,
+            username: None,
+            email: None,
+            status: "invalid".to_string(),
+            id: None,
+            errors: Vec::new(),
+        };
+
+        let row = match parsed_row {
+            Ok(row) => row,
+            Err(e) => {
+                result.errors.push(e);
+                rows.push(result);
+                valid.push(None);
+                continue;
+            }
+        };
+
+        // Invited users choose their own password, so they get an unusable one until then
+        let password = match row.password.clone().filter(|p| !p.is_empty()) {
+            Some(password) => password,
+            None if send_invitations => placeholder_password(),
+            None => {
+                result.errors.push("password: A password is required unless invitations are sent.".to_string());
+                String::new()
+            }
+        };
+
+        let user = row.into_insert_body(password);
+        result.username = Some(user.username.clone());
+        result.email = Some(user.email.clone());
+
+        if let Err(errors) = user.validate() {
+            result.errors.extend(validation_messages(&errors));
+        }
+        if !seen_usernames.insert(user.username.clone()) {
+            result.errors.push("username: Appears more than once in the import.".to_string());
+        }
+        if !seen_emails.insert(user.email.clone()) {
+            result.errors.push("email: Appears more than once in the import.".to_string());
+        }
+
+        if result.errors.is_empty() {
+            result.status = "valid".to_string();
+            valid.push(Some(user));
+        } else {
+            valid.push(None);
+        }
+        rows.push(result);
+    }
+
+    // 2. Check the remaining rows against the existing users
+    let usernames: Vec<String> = valid.iter().flatten().map(|u| u.username.clone()).collect();
+    let emails: Vec<String> = valid.iter().flatten().map(|u| u.email.clone()).collect();
+    let taken = fetch_taken_usernames_and_emails_from_db(&state.database, &usernames, &emails).await.map_err(|e| {
+        error!("Error checking the imported users: {}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "Could not import the users." })))
+    })?;
+    let taken_usernames: HashSet<&str> = taken.iter().map(|(username, _)| username.as_str()).collect();
+    let taken_emails: HashSet<&str> = taken.iter().map(|(_, email)| email.as_str()).collect();
+
+    for (result, user) in rows.iter_mut().zip(valid.iter_mut()) {
+        let Some(candidate) = user else { continue };
+        if taken_usernames.contains(candidate.username.as_str()) {
+            result.errors.push("username: Already taken.".to_string());
+        }
+        if taken_emails.contains(candidate.email.as_str()) {
+            result.errors.push("email: Already registered.".to_string());
+        }
+        if !result.errors.is_empty() {
+            result.status = "invalid".to_string();
+            *user = None;
+        }
+    }
+
+    let invalid = rows.iter().filter(|r| r.status == "invalid").count();
+    let mut report = UserImportResponse {
+        dry_run,
+        mode: mode.clone(),
+        total: rows.len(),
+        created: 0,
+        failed: invalid,
+        invitations_sent: 0,
+        rows,
+    };
+
+    if dry_run {
+        return Ok(Json(report));
+    }
+
+    // A transactional import only goes ahead when every row is valid
+    if mode == "transactional" && invalid > 0 {
+        for row in report.rows.iter_mut().filter(|r| r.status == "valid") {
+            row.status = "skipped".to_string();
+        }
+        return Err((StatusCode::UNPROCESSABLE_ENTITY, Json(json!(report))));
+    }
+
+    // 3. Create the users, hashing up to `USER_IMPORT_MAX_ROWS` passwords off the async runtime
+    let (indexes, inserts) = task::spawn_blocking(move || prepare_inserts(valid))
+        .await
+        .map_err(|e| {
+            error!("Error hashing the passwords of imported users: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "Failed to hash password." })))
+        })?
+        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "Failed to hash password." }))))?;
+
+    let transactional = mode == "transactional";
+    let results = insert_imported_users_into_db(&state.database, &inserts, transactional).await.map_err(|e| {
+        error!("Error importing users: {}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "Could not import the users." })))
+    })?;
+
+    // In transactional mode a single failure rolls back every other row as well
+    let rolled_back = transactional && results.iter().any(|r| r.is_err());
+    let mut languages = HashMap::new();
+    for (position, index) in indexes.iter().enumerate() {
+        let row = &mut report.rows[*index];
+        match results.get(position) {
+            Some(Ok(user)) if !rolled_back => {
+                row.status = "created".to_string();
+                row.id = Some(user.id);
+                languages.insert(user.id, user.language_code.clone());
+                report.created += 1;
+            }
+            Some(Err(e)) => {
+                row.status = "failed".to_string();
+                row.errors.push(e.clone());
+                report.failed += 1;
+            }
+            _ => row.status = "skipped".to_string(),
+        }
+    }
+
+    if rolled_back {
+        return Err((StatusCode::UNPROCESSABLE_ENTITY, Json(json!(report))));
+    }
+
+    let created: Vec<_> = report.rows.iter().filter_map(|row| row.id).collect();
+    let event = AuditEvent::new("user.import", Some(admin.id))
+        .change(None, Some(json!({ "mode": mode, "created": created, "failed": report.failed })));
+    record_audit_event(&state.database, &audit, event).await;
+
+    // 4. Invite the created users to choose a password
+    if send_invitations {
+        for row in report.rows.iter_mut().filter(|r| r.status == "created") {
+            let (Some(id), Some(email)) = (row.id, row.email.as_deref()) else { continue };
+            let locale = Locale::for_user(languages.get(&id).and_then(|code| code.as_deref()), Locale::fallback());
+            match invite_user(&state, locale, id, email).await {
+                Ok(()) => report.invitations_sent += 1,
+                Err(e) => {
+                    error!("Failed to invite imported user {}: {}", id, e);
+                    row.errors.push("The invitation could not be sent.".to_string());
+                }
+            }
+        }
+    }
+
+    Ok(Json(report))
+}
+
+// --- Helper Functions ---
+
+// Hashes the passwords of the valid rows, returning the row index of every user to insert
+fn prepare_inserts(
+    valid: Vec<Option<UserInsertBody>>,
+) -> Result<(Vec<usize>, Vec<UserImportInsert>), argon2::password_hash::Error> {
+    let mut indexes = Vec::new();
+    let mut inserts = Vec::new();
+    for (index, user) in valid.into_iter().enumerate() {
+        let Some(user) = user else { continue };
+        let password_hash = hash_password(&user.password)?;
+        let totp_secret = if user.totp.unwrap_or(false) { generate_totp_secret() } else { String::new() };
+
+        indexes.push(index);
+        inserts.push(UserImportInsert { user, password_hash, totp_secret });
+    }
+    Ok((indexes, inserts))
+}
+
+// A random password that satisfies the password rules, but is never shared with anyone
+fn placeholder_password() -> String {
+    let random: String = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(32)
+        .map(char::from)
+        .collect();
+    format!("{}aA1!", random)
+}
+
+// Sends an imported user a code to choose its own password
+async fn invite_user(state: &AppState, locale: Locale, id: uuid::Uuid, email: &str) -> Result<(), String> {
+    let code: String = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(32)
+        .map(char::from)
+        .collect();
+    let expiry_hours = get_env_u64("USER_IMPORT_INVITATION_EXPIRY_HOURS", 168);
+    let expires_at = Utc::now() + Duration::hours(expiry_hours as i64);
+
+    insert_user_password_reset_code_into_db(&state.database, id, &code, expires_at)
+        .await
+        .map_err(|e| format!("Failed to store the invitation code: {}", e))?;
+
+    let subject = t(locale, "mail.import_invitation.subject");
+    let body = t_with(locale, "mail.import_invitation.body", &[("code", &code), ("hours", &expiry_hours.to_string())]);
+    send_mail(&state.mail, email, &subject, &body)
+        .await
+        .map_err(|e| format!("Failed to send the invitation: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::auth::verify_hash;
+
+    fn user(username: &str, password: &str) -> UserInsertBody {
+        UserInsertBody {
+            username: username.to_string(),
+            email: format!("{}@example.com", username),
+            password: password.to_string(),
+            totp: None,
+            first_name: None,
+            last_name: None,
+            country_code: None,
+            language_code: None,
+            birthday: None,
+            description: None,
+            profile_picture_url: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn imported_passwords_are_hashed_for_sign_in() {
+        let password = "Imported-Passw0rd!";
+        let (indexes, inserts) = prepare_inserts(vec![None, Some(user("imported", password))]).unwrap();
+
+        // Invalid rows are left out, but keep their place in the report
+        assert_eq!(indexes, vec![1]);
+        assert_eq!(inserts.len(), 1);
+        assert!(inserts[0].totp_secret.is_empty());
+        // The same check as the login endpoint
+        assert!(verify_hash(password, &inserts[0].password_hash).await.unwrap());
+        assert!(!verify_hash("Another-Passw0rd!", &inserts[0].password_hash).await.unwrap_or(false));
+    }
+}
@@ -10,9 +10,13 @@ use tracing::{error, warn, debug, instrument};
 use std::sync::Arc;
 
 use crate::utils::auth::{encode_jwt, verify_hash};
-use crate::database::{apikeys::fetch_active_apikeys_by_user_id_from_db, users::fetch_active_user_by_email_from_db};
+use crate::database::{apikeys::fetch_active_apikeys_by_user_id_from_db, users::{fetch_active_user_by_email_from_db, fetch_user_suspension_by_email_from_db}};
 use crate::models::auth::LoginData;
+use crate::models::audit::AuditEvent;
+use crate::utils::audit::{record_audit_event, AuditContext};
 use crate::core::config::{get_env_bool, get_env_with_default, get_env_u64};
+use crate::i18n::catalog::t;
+use crate::i18n::locale::Locale;
 use crate::routes::AppState;
 
 /// User sign-in endpoint.
@@ -35,23 +39,50 @@ use crate::routes::AppState;
         (status = 200, description = "Successful sign-in", body = serde_json::Value),
         (status = 400, description = "Bad request", body = serde_json::Value),
         (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 403, description = "The account has been suspended", body = serde_json::Value),
         (status = 500, description = "Internal server error", body = serde_json::Value)
     )
 )]
-#[instrument(skip(state, user_data))]
+#[instrument(skip(state, audit, user_data))]
 pub async fn login(
     State(state): State<Arc<AppState>>,
+    audit: AuditContext,
+    locale: Locale,
     Json(user_data): Json<LoginData>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
     // Fetch the user from the database based on their email.
     let user = match fetch_active_user_by_email_from_db(&state.database, &user_data.email).await {
         Ok(Some(user)) => user,
-        Ok(None) | Err(_) => {
+        Ok(None) => {
+            // Suspended users get to know why, but only once they proved who they are
+            if let Ok(Some((suspension, password_hash))) = fetch_user_suspension_by_email_from_db(&state.database, &user_data.email).await {
+                if verify_hash(&user_data.password, &password_hash).await.unwrap_or(false) {
+                    let event = AuditEvent::new("user.login_failed", None)
+                        .target("user", suspension.id)
+                        .change(None, Some(json!({ "reason": "suspended" })));
+                    record_audit_event(&state.database, &audit, event).await;
+                    return Err((
+                        StatusCode::FORBIDDEN,
+                        Json(json!({ "error": suspension.message(suspension.locale(locale)), "suspended_until": suspension.suspended_until }))
+                    ));
+                }
+            }
+
+            error!("Failed to find user with email: {}", user_data.email);
+            let event = AuditEvent::new("user.login_failed", None)
+                .change(None, Some(json!({ "reason": "unknown_email", "email": user_data.email })));
+            record_audit_event(&state.database, &audit, event).await;
+            return Err((
+                StatusCode::UNAUTHORIZED,
+                Json(json!({ "error": t(locale, "auth.incorrect_credentials") }))
+            ));
+        }
+        Err(_) => {
             // Log the error for failed login attempt
             error!("Failed to find user with email: {}", user_data.email);
             return Err((
                 StatusCode::UNAUTHORIZED,
-                Json(json!({ "error": "Incorrect credentials." }))
+                Json(json!({ "error": t(locale, "auth.incorrect_credentials") }))
             ));
         }
     };
@@ -64,7 +95,7 @@ pub async fn login(
             error!("Error fetching API keys for user: {}", user.id);
             return Err((
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({ "error": "Internal server error." }))
+                Json(json!({ "error": t(locale, "error.internal") }))
             ));
         }
     };
@@ -95,7 +126,7 @@ pub async fn login(
             error!("Password verification failed for email: {}", user_data.email);
             return Err((
                 StatusCode::UNAUTHORIZED,
-                Json(json!({ "error": "Incorrect credentials." }))
+                Json(json!({ "error": t(locale, "auth.incorrect_credentials") }))
             ));
         }
     };
@@ -106,9 +137,13 @@ pub async fn login(
     if !credentials_valid {
         // Log invalid credentials attempt
         error!("Invalid credentials for user: {}", user_data.email);
+        let event = AuditEvent::new("user.login_failed", None)
+            .target("user", user.id)
+            .change(None, Some(json!({ "reason": "invalid_credentials" })));
+        record_audit_event(&state.database, &audit, event).await;
         return Err((
             StatusCode::UNAUTHORIZED,
-            Json(json!({ "error": "Incorrect credentials." }))
+            Json(json!({ "error": t(locale, "auth.incorrect_credentials") }))
         ));
     }
 
@@ -127,16 +162,20 @@ pub async fn login(
                     error!("Error creating TOTP instance for user: {}", user.id);
                     (
                         StatusCode::INTERNAL_SERVER_ERROR,
-                        Json(json!({ "error": "Internal server error." }))
+                        Json(json!({ "error": t(locale, "error.internal") }))
                     )
                 })?;
 
                 // Check if the provided TOTP code is valid.
                 if !totp.check_current(&totp_code).unwrap_or(false) {
                     error!("Invalid 2FA code for user: {}", user.id);
+                    let event = AuditEvent::new("user.login_failed", None)
+                        .target("user", user.id)
+                        .change(None, Some(json!({ "reason": "invalid_totp" })));
+                    record_audit_event(&state.database, &audit, event).await;
                     return Err((
                         StatusCode::UNAUTHORIZED,
-                        Json(json!({ "error": "Invalid 2FA code." }))
+                        Json(json!({ "error": t(locale, "auth.invalid_totp") }))
                     ));
                 }
             },
@@ -144,7 +183,7 @@ pub async fn login(
                 // If TOTP is set up but no code is provided, return a bad request.
                 return Err((
                     StatusCode::BAD_REQUEST,
-                    Json(json!({ "error": "2FA code required for this account." }))
+                    Json(json!({ "error": t(locale, "auth.totp_required") }))
                 ));
             }
         }
@@ -157,12 +196,17 @@ pub async fn login(
             error!("Error generating JWT for user: {}", user.id);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({ "error": "Internal server error." }))
+                Json(json!({ "error": t(locale, "error.internal") }))
             )
         })?;
 
     // Log the successful sign-in.
     debug!("User signed in: {}", email);
+    let method = if password_valid { "password" } else { "apikey" };
+    let event = AuditEvent::new("user.login", Some(user.id))
+        .target("user", user.id)
+        .change(None, Some(json!({ "method": method })));
+    record_audit_event(&state.database, &audit, event).await;
 
     // Prepare response headers
     let mut headers = HeaderMap::new();
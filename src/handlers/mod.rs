@@ -1,18 +1,46 @@
 // Module declarations
 pub mod delete_apikeys;
+pub mod delete_invitations;
+pub mod delete_tiers;
+pub mod delete_todo_lists;
+pub mod delete_todo_series;
 pub mod delete_todos;
 pub mod delete_users;
 pub mod get_apikeys;
+pub mod get_audit_log;
+pub mod get_billing;
+pub mod get_data_exports;
 pub mod get_health;
+pub mod get_invitations;
+pub mod get_preferences;
+pub mod get_tiers;
+pub mod get_todo_lists;
+pub mod get_todo_series;
 pub mod get_todos;
 pub mod get_usage;
 pub mod get_users;
 pub mod get_referencedata;
 pub mod homepage;
+pub mod import_users;
 pub mod post_apikeys;
+pub mod post_billing;
+pub mod post_data_exports;
+pub mod post_invitations;
+pub mod post_tiers;
+pub mod post_todo_lists;
 pub mod post_todos;
 pub mod post_users;
+pub mod patch_preferences;
+pub mod patch_todo_lists;
+pub mod patch_todo_series;
+pub mod patch_todos;
+pub mod patch_tiers;
 pub mod patch_users;
 pub mod protected;
 pub mod rotate_apikeys;
+pub mod share_todos;
+pub mod skip_todo_series;
+pub mod suspend_users;
+pub mod todo_attachments;
+pub mod move_todos;
 pub mod login;
\ No newline at end of file
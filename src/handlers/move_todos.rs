@@ -0,0 +1,93 @@
+use axum::{
+    extract::{State, Extension, Path},
+    Json,
+    http::StatusCode,
+};
+use uuid::Uuid;
+use serde_json::json;
+use tracing::{error, instrument};
+use std::sync::Arc;
+
+use crate::models::todo::{Todo, TodoMoveBody};
+use crate::models::user::User;
+use crate::database::todo_lists::fetch_todo_list_by_id_from_db;
+use crate::database::todos::{fetch_todo_by_id_from_db, move_todo_in_db};
+use crate::routes::AppState;
+
+// --- Route Handler ---
+
+// Move a todo to another list and/or position
+#[utoipa::path(
+    post,
+    path = "/todos/{id}/move",
+    tag = "todo",
+    security(
+        ("jwt_token" = [])
+    ),
+    params(
+        ("id" = String, Path, description = "Todo ID")
+    ),
+    request_body = TodoMoveBody,
+    responses(
+        (status = 200, description = "Todo moved successfully, its subtasks moved along", body = Todo),
+        (status = 400, description = "Invalid UUID format, negative position or moving a subtask to another list", body = serde_json::Value),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 403, description = "Only the owner can move the todo", body = serde_json::Value),
+        (status = 404, description = "Todo or list not found", body = serde_json::Value),
+        (status = 500, description = "Internal server error", body = serde_json::Value)
+    )
+)]
+#[instrument(skip(state, user, body))]
+pub async fn move_todo(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<User>,
+    Path(id): Path<String>,
+    Json(body): Json<TodoMoveBody>,
+) -> Result<Json<Todo>, (StatusCode, Json<serde_json::Value>)> {
+    let uuid = Uuid::parse_str(&id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(json!({ "error": "Invalid UUID format." }))))?;
+
+    if body.position.is_some_and(|position| position < 0) {
+        return Err((StatusCode::BAD_REQUEST, Json(json!({ "error": "Position cannot be negative." }))));
+    }
+
+    let not_found = || (
+        StatusCode::NOT_FOUND,
+        Json(json!({ "error": format!("Todo with ID '{}' not found.", id) })),
+    );
+    let db_error = |e: sqlx::Error| {
+        error!("Error moving todo {}: {}", uuid, e);
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "Could not move the todo." })))
+    };
+
+    let todo = fetch_todo_by_id_from_db(&state.database, uuid, user.id)
+        .await
+        .map_err(db_error)?
+        .ok_or_else(not_found)?;
+    if todo.user_id != user.id {
+        return Err((StatusCode::FORBIDDEN, Json(json!({ "error": "Only the owner can move this todo." }))));
+    }
+
+    let list_id = body.list_id.unwrap_or(todo.list_id);
+    if todo.parent_id.is_some() && list_id != todo.list_id {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": "Subtasks stay in the list of their parent, move the parent instead." })),
+        ));
+    }
+    if let Some(list_id) = list_id {
+        fetch_todo_list_by_id_from_db(&state.database, list_id, user.id)
+            .await
+            .map_err(db_error)?
+            .ok_or_else(|| (
+                StatusCode::NOT_FOUND,
+                Json(json!({ "error": format!("List with ID '{}' not found.", list_id) })),
+            ))?;
+    }
+
+    move_todo_in_db(&state.database, uuid, user.id, list_id, body.position)
+        .await
+        .map_err(db_error)?
+        .map(Json)
+        .ok_or_else(not_found)
+}
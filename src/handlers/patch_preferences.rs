@@ -0,0 +1,51 @@
+use axum::{extract::{Extension, State}, Json, http::StatusCode};
+use serde_json::{json, Value};
+use tracing::{error, instrument};
+use std::sync::Arc;
+
+use crate::database::preferences::update_user_preferences_in_db;
+use crate::models::preference::UserPreferences;
+use crate::models::user::User;
+use crate::utils::preferences::{parse_preferences_patch, resolve_preferences};
+use crate::routes::AppState;
+
+// --- Route Handlers ---
+
+// Change some of the preferences of the current user
+#[utoipa::path(
+    patch,
+    path = "/users/current/preferences",
+    tag = "user",
+    security(
+        ("jwt_token" = [])
+    ),
+    request_body(content = UserPreferences, description = "Any subset of the preferences, `null` resets a preference to its default"),
+    responses(
+        (status = 200, description = "The updated preferences, with defaults for everything that was not set", body = UserPreferences),
+        (status = 400, description = "Unknown preference or invalid value", body = serde_json::Value),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 500, description = "Internal server error", body = serde_json::Value)
+    )
+)]
+#[instrument(skip(state, patch))]
+pub async fn patch_current_user_preferences(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<User>,
+    Json(patch): Json<Value>,
+) -> Result<Json<UserPreferences>, (StatusCode, Json<serde_json::Value>)> {
+    let patch = parse_preferences_patch(&patch).map_err(|e| (
+        StatusCode::BAD_REQUEST,
+        Json(json!({ "error": e })),
+    ))?;
+
+    match update_user_preferences_in_db(&state.database, current_user.id, &Value::Object(patch.set), &patch.reset).await {
+        Ok(stored) => Ok(Json(resolve_preferences(&stored))),
+        Err(e) => {
+            error!("Error updating the preferences of user {}: {}", current_user.id, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Could not update the preferences." })),
+            ))
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use axum::{
+    extract::{Extension, State, Path},
+    Json,
+    http::StatusCode,
+};
+use uuid::Uuid;
+use serde_json::json;
+use tracing::{error, instrument};
+use validator::Validate;
+use std::sync::Arc;
+
+use crate::database::tiers::{fetch_tier_by_id_from_db, update_tier_in_db};
+use crate::middlewares::auth::invalidate_rate_limit_cache;
+use crate::models::tier::{Tier, TierUpdateBody};
+use crate::models::user::User;
+use crate::models::audit::AuditEvent;
+use crate::utils::audit::{json_diff, record_audit_event, AuditContext};
+use crate::routes::AppState;
+
+// --- Route Handler ---
+
+/// Updates a tier
+///
+/// Fields not included in the request body remain unchanged. When the level changes,
+/// users assigned to the old level are moved to the new one. Cached rate limits are
+/// dropped, so that the new limits apply to the next request.
+#[utoipa::path(
+    patch,
+    path = "/tiers/{id}",
+    tag = "tier",
+    security(
+        ("jwt_token" = [])
+    ),
+    request_body = TierUpdateBody,
+    params(
+        ("id" = String, Path, description = "Tier ID")
+    ),
+    responses(
+        (status = 200, description = "Tier updated successfully", body = Tier),
+        (status = 400, description = "Validation error", body = String),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 404, description = "Tier not found", body = String),
+        (status = 409, description = "A tier with this name or level already exists", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    )
+)]
+#[instrument(skip(state, current_user, audit, update))]
+pub async fn patch_tier(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<User>,
+    audit: AuditContext,
+    Path(id): Path<String>,
+    Json(update): Json<TierUpdateBody>
+) -> Result<Json<Tier>, (StatusCode, Json<serde_json::Value>)> {
+    let uuid = match Uuid::parse_str(&id) {
+        Ok(uuid) => uuid,
+        Err(_) => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({ "error": "Invalid UUID format." })),
+            ));
+        }
+    };
+
+    // Validate input
+    if let Err(errors) = update.validate() {
+        let error_messages: Vec<String> = errors
+            .field_errors()
+            .values()
+            .flat_map(|errors| errors.iter().map(|e| e.message.clone().unwrap_or_default().to_string()))
+            .collect();
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": error_messages.join(", ") }))
+        ));
+    }
+
+    // The tier before the update, to record what changed in the audit log
+    let before = fetch_tier_by_id_from_db(&state.database, uuid).await.ok().flatten();
+
+    match update_tier_in_db(&state.database, uuid, update).await {
+        Ok(Some(tier)) => {
+            invalidate_rate_limit_cache();
+            let (before, after) = json_diff(&json!(before), &json!(tier));
+            let event = AuditEvent::new("tier.update", Some(current_user.id))
+                .target("tier", tier.id)
+                .change(before, after);
+            record_audit_event(&state.database, &audit, event).await;
+            Ok(Json(tier))
+        }
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": format!("Tier with ID '{}' not found.", id) }))
+        )),
+        Err(sqlx::Error::Database(err)) if err.is_unique_violation() => Err((
+            StatusCode::CONFLICT,
+            Json(json!({ "error": "A tier with this name or level already exists." }))
+        )),
+        Err(err) => {
+            error!("Error updating tier {}: {}", id, err);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Could not update the tier." }))
+            ))
+        }
+    }
+}
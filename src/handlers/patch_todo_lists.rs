@@ -0,0 +1,98 @@
+use axum::{
+    extract::{State, Extension, Path},
+    Json,
+    http::StatusCode,
+};
+use uuid::Uuid;
+use serde_json::json;
+use tracing::{error, instrument};
+use validator::Validate;
+use std::sync::Arc;
+
+use crate::models::todo::{TodoList, TodoListBody, TodoListUpdateBody};
+use crate::models::user::User;
+use crate::database::todo_lists::{fetch_todo_list_by_id_from_db, update_todo_list_in_db};
+use crate::i18n::catalog::validation_message;
+use crate::i18n::locale::Locale;
+use crate::routes::AppState;
+
+// --- Route Handler ---
+
+// Rename, describe or reorder a list
+#[utoipa::path(
+    patch,
+    path = "/todos/lists/{id}",
+    tag = "todo",
+    security(
+        ("jwt_token" = [])
+    ),
+    params(
+        ("id" = String, Path, description = "List ID")
+    ),
+    request_body = TodoListUpdateBody,
+    responses(
+        (status = 200, description = "List updated successfully", body = TodoList),
+        (status = 400, description = "Invalid UUID format or validation error", body = serde_json::Value),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 404, description = "List not found", body = serde_json::Value),
+        (status = 409, description = "A list with this name already exists", body = serde_json::Value),
+        (status = 500, description = "Internal server error", body = serde_json::Value)
+    )
+)]
+#[instrument(skip(state, user, update))]
+pub async fn patch_todo_list_by_id(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<User>,
+    Path(id): Path<String>,
+    locale: Locale,
+    Json(update): Json<TodoListUpdateBody>,
+) -> Result<Json<TodoList>, (StatusCode, Json<serde_json::Value>)> {
+    let uuid = Uuid::parse_str(&id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(json!({ "error": "Invalid UUID format." }))))?;
+
+    if update.is_empty() {
+        return Err((StatusCode::BAD_REQUEST, Json(json!({ "error": "No changes provided." }))));
+    }
+    if update.position.is_some_and(|position| position < 0) {
+        return Err((StatusCode::BAD_REQUEST, Json(json!({ "error": "Position cannot be negative." }))));
+    }
+
+    let not_found = || (
+        StatusCode::NOT_FOUND,
+        Json(json!({ "error": format!("List with ID '{}' not found.", id) })),
+    );
+    let db_error = |e: sqlx::Error| {
+        error!("Error updating todo list {}: {}", uuid, e);
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "Could not update the list." })))
+    };
+
+    let list = fetch_todo_list_by_id_from_db(&state.database, uuid, user.id)
+        .await
+        .map_err(db_error)?
+        .ok_or_else(not_found)?;
+
+    // The updated list has to satisfy the same rules as a new one
+    let updated = TodoListBody {
+        name: update.name.unwrap_or(list.name),
+        description: update.description.unwrap_or(list.description),
+    };
+    if let Err(errors) = updated.validate() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": validation_message(locale, &errors) }))
+        ));
+    }
+
+    let name = updated.name.trim();
+    let description = updated.description.as_deref().map(str::trim).filter(|d| !d.is_empty());
+
+    match update_todo_list_in_db(&state.database, uuid, user.id, name, description, update.position).await {
+        Ok(Some(list)) => Ok(Json(list)),
+        Ok(None) => Err(not_found()),
+        Err(sqlx::Error::Database(err)) if err.is_unique_violation() => Err((
+            StatusCode::CONFLICT,
+            Json(json!({ "error": format!("A list named '{}' already exists.", name) })),
+        )),
+        Err(e) => Err(db_error(e)),
+    }
+}
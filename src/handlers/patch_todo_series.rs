@@ -0,0 +1,94 @@
+use axum::{
+    extract::{State, Extension, Path},
+    Json,
+    http::StatusCode,
+};
+use uuid::Uuid;
+use serde_json::json;
+use tracing::{error, instrument};
+use validator::Validate;
+use std::sync::Arc;
+
+use crate::models::todo::{TodoBody, TodoSeries, TodoSeriesUpdateBody};
+use crate::models::user::User;
+use crate::database::todo_series::{fetch_todo_series_by_id_from_db, update_todo_series_in_db};
+use crate::i18n::catalog::validation_message;
+use crate::i18n::locale::Locale;
+use crate::routes::AppState;
+
+// --- Route Handler ---
+
+// Change the rule or template of a recurring series
+#[utoipa::path(
+    patch,
+    path = "/todos/series/{id}",
+    tag = "todo",
+    security(
+        ("jwt_token" = [])
+    ),
+    params(
+        ("id" = String, Path, description = "Series ID")
+    ),
+    request_body = TodoSeriesUpdateBody,
+    responses(
+        (status = 200, description = "Series updated, the changes apply to the occurrences created from now on", body = TodoSeries),
+        (status = 400, description = "Invalid UUID format or validation error", body = serde_json::Value),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 404, description = "Series not found", body = serde_json::Value),
+        (status = 500, description = "Internal server error", body = serde_json::Value)
+    )
+)]
+#[instrument(skip(state, user, update))]
+pub async fn patch_todo_series_by_id(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<User>,
+    Path(id): Path<String>,
+    locale: Locale,
+    Json(update): Json<TodoSeriesUpdateBody>,
+) -> Result<Json<TodoSeries>, (StatusCode, Json<serde_json::Value>)> {
+    let uuid = Uuid::parse_str(&id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(json!({ "error": "Invalid UUID format." }))))?;
+
+    if update.is_empty() {
+        return Err((StatusCode::BAD_REQUEST, Json(json!({ "error": "No changes provided." }))));
+    }
+
+    let not_found = || (
+        StatusCode::NOT_FOUND,
+        Json(json!({ "error": format!("Series with ID '{}' not found.", id) })),
+    );
+    let db_error = |e: sqlx::Error| {
+        error!("Error updating todo series {}: {}", uuid, e);
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "Could not update the series." })))
+    };
+
+    let series = fetch_todo_series_by_id_from_db(&state.database, uuid, user.id)
+        .await
+        .map_err(db_error)?
+        .ok_or_else(not_found)?;
+
+    // The template has to satisfy the same rules as a new recurring todo
+    let template = TodoBody {
+        task: update.task.unwrap_or(series.task),
+        description: update.description.unwrap_or(series.description),
+        due_at: Some(series.dtstart),
+        due_timezone: Some(series.timezone),
+        priority: update.priority.unwrap_or(series.priority),
+        tags: update.tags.unwrap_or(series.tags),
+        list_id: series.list_id,
+        parent_id: None,
+        recurrence: Some(update.rrule.unwrap_or(series.rrule)),
+    };
+    if let Err(errors) = template.validate() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": validation_message(locale, &errors) }))
+        ));
+    }
+
+    update_todo_series_in_db(&state.database, uuid, user.id, &template)
+        .await
+        .map_err(db_error)?
+        .ok_or_else(not_found)
+        .map(Json)
+}
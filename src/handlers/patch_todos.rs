@@ -0,0 +1,132 @@
+use axum::{
+    extract::{State, Extension, Path},
+    Json,
+    http::StatusCode,
+};
+use chrono::Utc;
+use uuid::Uuid;
+use serde_json::json;
+use tracing::{debug, error, instrument};
+use validator::Validate;
+use std::sync::Arc;
+
+use crate::models::todo::{Todo, TodoPermission, TodoUpdateBody};
+use crate::models::user::User;
+use crate::database::todos::{fetch_todo_by_id_from_db, update_todo_in_db};
+use crate::database::todo_shares::fetch_todo_permission_from_db;
+use crate::utils::preferences::load_user_preferences;
+use crate::utils::recurrence::create_next_occurrence;
+use crate::i18n::catalog::{t, t_with, validation_message};
+use crate::i18n::locale::Locale;
+use crate::routes::AppState;
+
+/// Times an update is merged again when the todo changed while it was being updated.
+const MAX_UPDATE_ATTEMPTS: usize = 3;
+
+// --- Route Handler ---
+
+// Update the task, description, due date, priority, tags or completion of a todo
+#[utoipa::path(
+    patch,
+    path = "/todos/{id}",
+    tag = "todo",
+    security(
+        ("jwt_token" = [])
+    ),
+    params(
+        ("id" = String, Path, description = "Todo ID")
+    ),
+    request_body = TodoUpdateBody,
+    responses(
+        (status = 200, description = "Todo updated successfully", body = Todo),
+        (status = 400, description = "Invalid UUID format or validation error", body = serde_json::Value),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 403, description = "The todo is shared with you as viewer", body = serde_json::Value),
+        (status = 404, description = "Todo not found", body = serde_json::Value),
+        (status = 409, description = "The todo kept changing while it was being updated", body = serde_json::Value),
+        (status = 500, description = "Internal server error", body = serde_json::Value)
+    )
+)]
+#[instrument(skip(state, user, update))]
+pub async fn patch_todo_by_id(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<User>,
+    Path(id): Path<String>,
+    locale: Locale,
+    Json(update): Json<TodoUpdateBody>,
+) -> Result<Json<Todo>, (StatusCode, Json<serde_json::Value>)> {
+    let uuid = Uuid::parse_str(&id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(json!({ "error": t(locale, "error.invalid_uuid") }))))?;
+
+    if update.is_empty() {
+        return Err((StatusCode::BAD_REQUEST, Json(json!({ "error": t(locale, "todo.no_changes") }))));
+    }
+
+    let not_found = || (
+        StatusCode::NOT_FOUND,
+        Json(json!({ "error": t_with(locale, "todo.not_found", &[("id", &id)]) })),
+    );
+    let db_error = |e: sqlx::Error| {
+        error!("Error updating todo {}: {}", uuid, e);
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": t(locale, "todo.update_failed") })))
+    };
+
+    // Completion dates follow the calendar of the user, as do due dates without a time zone
+    let preferences = load_user_preferences(&state.database, user.id).await;
+    let today = Utc::now().with_timezone(&preferences.tz()).date_naive();
+
+    // The update is merged into the todo as read, and only stored when nobody changed the todo meanwhile
+    for _ in 0..MAX_UPDATE_ATTEMPTS {
+        let todo = fetch_todo_by_id_from_db(&state.database, uuid, user.id)
+            .await
+            .map_err(db_error)?
+            .ok_or_else(not_found)?;
+
+        // Besides the owner, only users the todo (or its list) is shared with as editor can change it
+        if todo.user_id != user.id {
+            let permission = fetch_todo_permission_from_db(&state.database, uuid, user.id)
+                .await
+                .map_err(db_error)?;
+            if permission.as_deref() != Some(TodoPermission::Editor.as_str()) {
+                return Err((
+                    StatusCode::FORBIDDEN,
+                    Json(json!({ "error": t(locale, "todo.viewer_only") })),
+                ));
+            }
+        }
+        let owner_id = todo.user_id;
+        let completes = update.completed == Some(true) && !todo.completed.unwrap_or(false);
+        let occurrence = todo.series_id.zip(todo.occurrence_at);
+
+        // The updated todo has to satisfy the same rules as a new one
+        let mut updated = update.merge_into(&todo);
+        if let Err(errors) = updated.validate() {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({ "error": validation_message(locale, &errors) }))
+            ));
+        }
+        updated.resolve_due_timezone(&preferences.timezone);
+
+        let Some(todo) = update_todo_in_db(&state.database, &todo, &updated, update.completed, today)
+            .await
+            .map_err(db_error)?
+        else {
+            debug!("Todo {} changed while it was being updated, merging again.", uuid);
+            continue;
+        };
+
+        // Completing an occurrence of a recurring todo creates the next one, the completion itself stands regardless
+        if let (true, Some((series_id, occurrence_at))) = (completes, occurrence) {
+            match create_next_occurrence(&state.database, series_id, owner_id, occurrence_at, Some(uuid)).await {
+                Ok(Some(next)) => debug!("Created occurrence {} of series {}.", next.id, series_id),
+                Ok(None) => debug!("Series {} has no next occurrence to create.", series_id),
+                Err(e) => error!("Error creating the next occurrence of series {}: {}", series_id, e),
+            }
+        }
+
+        return Ok(Json(todo));
+    }
+
+    Err((StatusCode::CONFLICT, Json(json!({ "error": t(locale, "todo.changed_concurrently") }))))
+}
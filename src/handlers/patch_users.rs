@@ -3,13 +3,21 @@ use axum::{
     Json,
     http::StatusCode,
 };
+use chrono::{Duration, Utc};
 use serde_json::json;
-use tracing::instrument;
+use tracing::{error, instrument};
 use std::sync::Arc;
 
-use crate::database::users::update_user_in_db;
-use crate::models::user::{User, UserUpdateBody, UserUpdateResponse};
+use crate::core::config::get_env_u64;
+use crate::database::users::{
+    change_username_in_db, fetch_active_user_by_field_from_db, fetch_last_username_change_from_db,
+    fetch_user_by_field_from_db, is_username_blocked_in_db, update_user_in_db,
+};
+use crate::database::tiers::fetch_tier_levels_from_db;
+use crate::models::user::{User, UserUpdateBody, UserUpdateResponse, UsernameChangeBody, UsernameChangeResponse};
 use crate::models::error::ErrorResponse;
+use crate::models::audit::AuditEvent;
+use crate::utils::audit::{json_diff, record_audit_event, AuditContext};
 use crate::routes::AppState;
 
 use validator::Validate;
@@ -24,7 +32,7 @@ use validator::Validate;
 ///
 /// # Validation Layers
 /// 1. **Structural Validation**: Handled by `UserUpdateBody`'s `deny_unknown_fields` attribute
-/// 2. **Business Logic Validation**: Manual checks for role_level, tier_level, and birthday
+/// 2. **Business Logic Validation**: Manual checks for role_level, tier_level, tenant and birthday
 ///
 /// # Request Flow
 /// 1. Permission check (self or admin)
@@ -52,11 +60,12 @@ use validator::Validate;
         (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
 )]
-#[instrument(skip(state, current_user, update))]
+#[instrument(skip(state, current_user, audit, update))]
 pub async fn patch_user_profile(
     Path(id): Path<String>,
     State(state): State<Arc<AppState>>,
     Extension(current_user): Extension<User>,
+    audit: AuditContext,
     Json(update): Json<UserUpdateBody>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
     // --- Permission Validation ---
@@ -85,7 +94,16 @@ pub async fn patch_user_profile(
 
     // Tier Level Validation
     if let Some(tier_level) = update.tier_level {
-        validate_tier_level(tier_level, is_admin, current_user.tier_level, &mut validation_errors);
+        let tier_levels = fetch_tier_levels_from_db(&state.database).await.map_err(|e| (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "error": format!("Database error: {}", e) }))
+        ))?;
+        validate_tier_level(tier_level, is_admin, current_user.tier_level, &tier_levels, &mut validation_errors);
+    }
+
+    // Tenant Validation, users only share todos within their tenant so they cannot pick one themselves
+    if update.tenant.is_some() && !is_admin {
+        validation_errors.push("Cannot modify your own tenant".to_string());
     }
 
     // Birthday Validation
@@ -94,6 +112,13 @@ pub async fn patch_user_profile(
     }
 
     // --- Error Handling ---
+    if !validation_errors.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": validation_errors.join(", ") }))
+        ));
+    }
+
     if let Err(validation_errors) = update.validate() {
         return Err((
             StatusCode::BAD_REQUEST,
@@ -105,8 +130,25 @@ pub async fn patch_user_profile(
     }
 
     // --- Database Operation ---
+    // The profile before the update, to record what changed in the audit log
+    let before = fetch_user_by_field_from_db(&state.database, "id", &target_user_id.to_string())
+        .await
+        .ok()
+        .flatten();
+
     match update_user_in_db(&state.database, target_user_id, update).await {
-        Ok(_) => Ok(Json(json!({ "success": true }))),
+        Ok(_) => {
+            let after = fetch_user_by_field_from_db(&state.database, "id", &target_user_id.to_string())
+                .await
+                .ok()
+                .flatten();
+            let (before, after) = json_diff(&json!(before), &json!(after));
+            let event = AuditEvent::new("user.update", Some(current_user.id))
+                .target("user", target_user_id)
+                .change(before, after);
+            record_audit_event(&state.database, &audit, event).await;
+            Ok(Json(json!({ "success": true })))
+        }
         Err(e) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({ "error": format!("Database error: {}", e) }))
@@ -114,6 +156,139 @@ pub async fn patch_user_profile(
     }
 }
 
+/// Changes the username of a user
+///
+/// Users can change their own username once per `USERNAME_CHANGE_COOLDOWN_DAYS`, admins can change
+/// any username at any time. The previous username is kept in the history and cannot be taken by
+/// other users for `USERNAME_REUSE_BLOCK_DAYS`.
+///
+/// # Error Responses
+/// - **400 Bad Request**: Invalid or reserved username, or the username did not change
+/// - **403 Forbidden**: Changing the username of someone else without being an admin
+/// - **404 Not Found**: No active user with this ID
+/// - **409 Conflict**: The username is taken or still blocked after a recent change
+/// - **429 Too Many Requests**: The cooldown has not passed yet
+#[utoipa::path(
+    patch,
+    path = "/users/{id}/username",
+    tag = "user",
+    security(("jwt_token" = [])),
+    request_body = UsernameChangeBody,
+    params(("id" = String, Path, description = "User UUID or 'current'")),
+    responses(
+        (status = 200, description = "Username changed successfully", body = UsernameChangeResponse),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 403, description = "Not allowed", body = ErrorResponse),
+        (status = 404, description = "User not found", body = ErrorResponse),
+        (status = 409, description = "Username already taken", body = ErrorResponse),
+        (status = 429, description = "The username was changed too recently", body = serde_json::Value),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+)]
+#[instrument(skip(state, current_user, audit, body))]
+pub async fn patch_username(
+    Path(id): Path<String>,
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<User>,
+    audit: AuditContext,
+    Json(body): Json<UsernameChangeBody>,
+) -> Result<Json<UsernameChangeResponse>, (StatusCode, Json<serde_json::Value>)> {
+    // --- Permission Validation ---
+    let is_admin = current_user.role_level == 2;
+    let target_user_id = if id == "current" {
+        current_user.id
+    } else {
+        match uuid::Uuid::parse_str(&id) {
+            Ok(uuid) => {
+                if uuid != current_user.id && !is_admin {
+                    return Err((StatusCode::FORBIDDEN, Json(json!({ "error": "Not allowed" }))));
+                }
+                uuid
+            }
+            Err(_) => return Err((StatusCode::BAD_REQUEST, Json(json!({ "error": "Invalid UUID" })))),
+        }
+    };
+
+    if let Err(errors) = body.validate() {
+        let error_messages: Vec<String> = errors
+            .field_errors()
+            .values()
+            .flat_map(|errors| errors.iter().map(|e| e.message.clone().unwrap_or_default().to_string()))
+            .collect();
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": error_messages.join(", ") }))
+        ));
+    }
+
+    let db_error = |e: sqlx::Error| {
+        error!("Error changing the username of user {}: {}", target_user_id, e);
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "Could not change the username." })))
+    };
+
+    let user = fetch_active_user_by_field_from_db(&state.database, "id", &target_user_id.to_string())
+        .await
+        .map_err(db_error)?
+        .ok_or_else(|| (
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": format!("User with ID '{}' not found", target_user_id) }))
+        ))?;
+
+    if user.username == body.username {
+        return Err((StatusCode::BAD_REQUEST, Json(json!({ "error": "This already is the username." }))));
+    }
+
+    // --- Cooldown ---
+    let cooldown = Duration::days(get_env_u64("USERNAME_CHANGE_COOLDOWN_DAYS", 30) as i64);
+    if !is_admin {
+        if let Some(last_change) = fetch_last_username_change_from_db(&state.database, target_user_id).await.map_err(db_error)? {
+            let next_change_at = last_change + cooldown;
+            if next_change_at > Utc::now() {
+                return Err((
+                    StatusCode::TOO_MANY_REQUESTS,
+                    Json(json!({
+                        "error": format!("The username can be changed again after {}.", next_change_at.format("%Y-%m-%d %H:%M UTC")),
+                        "next_change_at": next_change_at
+                    }))
+                ));
+            }
+        }
+    }
+
+    // --- Availability ---
+    if is_username_blocked_in_db(&state.database, &body.username, Some(target_user_id)).await.map_err(db_error)? {
+        return Err((StatusCode::CONFLICT, Json(json!({ "error": "Username already exists." }))));
+    }
+
+    // --- Database Operation ---
+    let reserved_until = Utc::now() + Duration::days(get_env_u64("USERNAME_REUSE_BLOCK_DAYS", 180) as i64);
+    match change_username_in_db(&state.database, target_user_id, &user.username, &body.username, reserved_until).await {
+        Ok(Some(changed_at)) => {
+            let event = AuditEvent::new("user.username_change", Some(current_user.id))
+                .target("user", target_user_id)
+                .change(Some(json!({ "username": user.username })), Some(json!({ "username": body.username })));
+            record_audit_event(&state.database, &audit, event).await;
+
+            Ok(Json(UsernameChangeResponse {
+                id: target_user_id,
+                username: body.username,
+                previous_username: user.username,
+                changed_at,
+                next_change_at: changed_at + cooldown,
+            }))
+        }
+        Ok(None) => Err((
+            StatusCode::CONFLICT,
+            Json(json!({ "error": "The username was changed in the meantime." }))
+        )),
+        Err(sqlx::Error::Database(err)) if err.is_unique_violation() => Err((
+            StatusCode::CONFLICT,
+            Json(json!({ "error": "Username already exists." }))
+        )),
+        Err(e) => Err(db_error(e)),
+    }
+}
+
 // --- Validation Helpers ---
 
 /// Validates role level changes
@@ -135,17 +310,19 @@ fn validate_role_level(
 }
 
 /// Validates tier level changes
-/// - Admins can set 1-4
+/// - Admins can set any level of an existing tier
 /// - Regular users can't change their tier
 fn validate_tier_level(
     new_level: i32,
     is_admin: bool,
     current_level: i32,
+    tier_levels: &[i32],
     errors: &mut Vec<String>
 ) {
     if is_admin {
-        if !(1..=4).contains(&new_level) {
-            errors.push("Tier level must be between 1-4".into());
+        if !tier_levels.contains(&new_level) {
+            let levels: Vec<String> = tier_levels.iter().map(|level| level.to_string()).collect();
+            errors.push(format!("Tier level must be one of: {}", levels.join(", ")));
         }
     } else if new_level != current_level {
         errors.push("Cannot modify your own tier level".into());
@@ -10,6 +10,8 @@ use crate::utils::auth::{generate_api_key, hash_password};
 use crate::models::user::User;
 use crate::database::apikeys::{check_existing_api_key_count, insert_api_key_into_db};
 use crate::models::apikey::{ApiKeyInsertBody, ApiKeyInsertResponse};
+use crate::models::audit::AuditEvent;
+use crate::utils::audit::{record_audit_event, AuditContext};
 use crate::routes::AppState;
 
 // --- Route Handler ---
@@ -33,6 +35,7 @@ use crate::routes::AppState;
 pub async fn post_apikey(
     State(state): State<Arc<AppState>>,
     Extension(user): Extension<User>,
+    audit: AuditContext,
     Json(api_key_request): Json<ApiKeyInsertBody>
 ) -> Result<Json<ApiKeyInsertResponse>, (StatusCode, Json<serde_json::Value>)> {
     // Validate input
@@ -86,6 +89,13 @@ pub async fn post_apikey(
             debug!("Successfully created API key for user: {}", user.id);
             // Restore generated api_key to response. It is not stored in database for security reasons.
             api_key_response.api_key = api_key;
+            let event = AuditEvent::new("apikey.create", Some(user.id))
+                .target("apikey", api_key_response.id)
+                .change(None, Some(json!({
+                    "description": api_key_response.description,
+                    "expiration_date": api_key_response.expiration_date,
+                })));
+            record_audit_event(&state.database, &audit, event).await;
             Ok(Json(api_key_response))
         }
         Err(err) => {
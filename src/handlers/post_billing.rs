@@ -0,0 +1,150 @@
+use axum::{extract::State, Json, http::StatusCode};
+use chrono::{Datelike, Utc};
+use serde_json::json;
+use tracing::{error, instrument};
+use validator::Validate;
+use std::sync::Arc;
+
+use crate::database::billing::{insert_billing_price_into_db, generate_billing_statements_in_db, fetch_last_billed_period_from_db};
+use crate::database::usage::fetch_usage_rolled_up_until_from_db;
+use crate::models::billing::{BillingPrice, BillingPriceInsertBody, BillingStatementGenerateBody, BillingStatementGenerateResponse};
+use crate::utils::billing::parse_billing_period;
+use crate::routes::AppState;
+
+// --- Route Handlers ---
+
+// Add a price to a tier, replacing the previous price from `valid_from` onwards
+#[utoipa::path(
+    post,
+    path = "/billing/prices/new",
+    tag = "billing",
+    security(
+        ("jwt_token" = [])
+    ),
+    request_body = BillingPriceInsertBody,
+    responses(
+        (status = 200, description = "Price created successfully", body = BillingPrice),
+        (status = 400, description = "Validation error, or a start in a period that has been billed already", body = String),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 409, description = "The tier already has a price starting on this date", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    )
+)]
+#[instrument(skip(state, price))]
+pub async fn post_billing_price(
+    State(state): State<Arc<AppState>>,
+    Json(price): Json<BillingPriceInsertBody>
+) -> Result<Json<BillingPrice>, (StatusCode, Json<serde_json::Value>)> {
+    // Validate input
+    if let Err(errors) = price.validate() {
+        let error_messages: Vec<String> = errors
+            .field_errors()
+            .values()
+            .flat_map(|errors| errors.iter().map(|e| e.message.clone().unwrap_or_default().to_string()))
+            .collect();
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": error_messages.join(", ") }))
+        ));
+    }
+
+    // Statements cover whole months, so a price has to start at the beginning of one
+    if price.valid_from.day() != 1 {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": "Valid from must be the first day of a month." }))
+        ));
+    }
+
+    // Billed periods are final, a price can only change what has not been billed yet
+    let last_billed = fetch_last_billed_period_from_db(&state.database).await.map_err(|err| {
+        error!("Error fetching the last billed period: {}", err);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "error": "Could not create the price." }))
+        )
+    })?;
+    if let Some(last_billed) = last_billed.filter(|last_billed| price.valid_from <= *last_billed) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": format!("Valid from must be after {}, the last billed period.", last_billed.format("%Y-%m")) }))
+        ));
+    }
+
+    match insert_billing_price_into_db(&state.database, price).await {
+        Ok(price) => Ok(Json(price)),
+        Err(sqlx::Error::Database(err)) if err.is_unique_violation() => Err((
+            StatusCode::CONFLICT,
+            Json(json!({ "error": "The tier already has a price starting on this date." }))
+        )),
+        Err(sqlx::Error::Database(err)) if err.is_foreign_key_violation() => Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": "Tier not found." }))
+        )),
+        Err(err) => {
+            error!("Error creating price: {}", err);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Could not create the price." }))
+            ))
+        }
+    }
+}
+
+// Generate the statements of a completed month. Existing statements are left untouched.
+#[utoipa::path(
+    post,
+    path = "/billing/statements/generate",
+    tag = "billing",
+    security(
+        ("jwt_token" = [])
+    ),
+    request_body = BillingStatementGenerateBody,
+    responses(
+        (status = 200, description = "Statements generated successfully", body = BillingStatementGenerateResponse),
+        (status = 400, description = "Invalid period", body = String),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 409, description = "The usage of the period has not been rolled up completely yet", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    )
+)]
+#[instrument(skip(state))]
+pub async fn post_billing_statements_generate(
+    State(state): State<Arc<AppState>>,
+    Json(body): Json<BillingStatementGenerateBody>
+) -> Result<Json<BillingStatementGenerateResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let (period_start, period_end) = parse_billing_period(&body.period).ok_or_else(|| (
+        StatusCode::BAD_REQUEST,
+        Json(json!({ "error": "Period must be formatted as YYYY-MM." }))
+    ))?;
+
+    if period_end > Utc::now().date_naive() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": "Only completed months can be billed." }))
+        ));
+    }
+
+    let rolled_up_until = fetch_usage_rolled_up_until_from_db(&state.database).await.map_err(|_| (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(json!({ "error": "Could not fetch the usage data." }))
+    ))?;
+
+    if rolled_up_until.date_naive() < period_end {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(json!({ "error": "The usage of this period has not been rolled up completely yet." }))
+        ));
+    }
+
+    match generate_billing_statements_in_db(&state.database, period_start, period_end, body.user_id).await {
+        Ok(generated) => Ok(Json(BillingStatementGenerateResponse { period_start, generated })),
+        Err(err) => {
+            error!("Error generating statements for {}: {}", period_start, err);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Could not generate the statements." }))
+            ))
+        }
+    }
+}
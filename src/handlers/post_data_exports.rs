@@ -0,0 +1,71 @@
+use axum::{extract::{Extension, State}, Json, http::StatusCode};
+use chrono::{Duration, Utc};
+use serde_json::json;
+use tracing::{error, instrument};
+use std::sync::Arc;
+
+use crate::core::config::get_env_u64;
+use crate::database::data_exports::{insert_data_export_into_db, fetch_latest_data_export_from_db};
+use crate::models::data_export::DataExport;
+use crate::models::user::User;
+use crate::routes::AppState;
+
+// --- Route Handlers ---
+
+// Request an export of all personal data of the current user
+#[utoipa::path(
+    post,
+    path = "/users/current/export",
+    tag = "user",
+    security(
+        ("jwt_token" = [])
+    ),
+    responses(
+        (status = 202, description = "Export requested, it will be emailed once ready", body = DataExport),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 429, description = "An export has been requested recently", body = serde_json::Value),
+        (status = 500, description = "Internal server error", body = serde_json::Value)
+    )
+)]
+#[instrument(skip(state, user))]
+pub async fn post_data_export(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<User>,
+) -> Result<(StatusCode, Json<DataExport>), (StatusCode, Json<serde_json::Value>)> {
+    let latest = fetch_latest_data_export_from_db(&state.database, user.id).await.map_err(|e| {
+        error!("Error fetching the latest data export: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "error": "Could not request the export." }))
+        )
+    })?;
+
+    if let Some(latest) = latest {
+        // An export that is still being processed is simply returned again
+        if latest.status == "pending" || latest.status == "processing" {
+            return Ok((StatusCode::ACCEPTED, Json(latest)));
+        }
+
+        let cooldown = Duration::hours(get_env_u64("DATA_EXPORT_COOLDOWN_HOURS", 24) as i64);
+        if latest.requested_at + cooldown > Utc::now() {
+            return Err((
+                StatusCode::TOO_MANY_REQUESTS,
+                Json(json!({
+                    "error": "An export has been requested recently.",
+                    "retry_after": (latest.requested_at + cooldown).to_rfc3339()
+                }))
+            ));
+        }
+    }
+
+    match insert_data_export_into_db(&state.database, user.id).await {
+        Ok(export) => Ok((StatusCode::ACCEPTED, Json(export))),
+        Err(e) => {
+            error!("Error requesting a data export: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Could not request the export." }))
+            ))
+        }
+    }
+}
@@ -0,0 +1,293 @@
+use axum::{extract::{Extension, Path, State}, Json, http::StatusCode};
+use chrono::{DateTime, Duration, Utc};
+use rand::Rng;
+use rand::distributions::Alphanumeric;
+use serde_json::json;
+use tracing::{error, instrument};
+use uuid::Uuid;
+use validator::Validate;
+use std::sync::Arc;
+
+use crate::core::config::{get_env_u64, get_env_with_default};
+use crate::database::invitations::{insert_invitation_into_db, renew_invitation_in_db, fetch_invitation_by_id_from_db, accept_invitation_in_db};
+use crate::database::tiers::fetch_tier_levels_from_db;
+use crate::database::users::{fetch_user_by_email_from_db, is_username_blocked_in_db};
+use crate::mail::MailerState;
+use crate::i18n::catalog::{t, t_with};
+use crate::i18n::locale::Locale;
+use crate::mail::send::send_mail;
+use crate::models::invitation::{Invitation, InvitationInsertBody, InvitationAcceptBody};
+use crate::models::user::{User, UserInsertResponse};
+use crate::utils::auth::{encode_invitation_token, decode_invitation_token, generate_totp_secret, hash_password};
+use crate::models::audit::AuditEvent;
+use crate::utils::audit::{record_audit_event, AuditContext};
+use crate::routes::AppState;
+
+// --- Route Handlers ---
+
+// Invite a new user by email
+#[utoipa::path(
+    post,
+    path = "/invitations/new",
+    tag = "invitation",
+    security(
+        ("jwt_token" = [])
+    ),
+    request_body = InvitationInsertBody,
+    responses(
+        (status = 200, description = "Invitation created and sent", body = Invitation),
+        (status = 400, description = "Validation error", body = serde_json::Value),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 409, description = "The email address is already registered or invited", body = serde_json::Value),
+        (status = 500, description = "Internal server error", body = serde_json::Value)
+    )
+)]
+#[instrument(skip(state, current_user, audit, body))]
+pub async fn post_invitation(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<User>,
+    audit: AuditContext,
+    Json(body): Json<InvitationInsertBody>,
+) -> Result<Json<Invitation>, (StatusCode, Json<serde_json::Value>)> {
+    // Validate input
+    if let Err(errors) = body.validate() {
+        let error_messages: Vec<String> = errors
+            .field_errors()
+            .values()
+            .flat_map(|errors| errors.iter().map(|e| e.message.clone().unwrap_or_default().to_string()))
+            .collect();
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": error_messages.join(", ") }))
+        ));
+    }
+
+    let tier_level = body.tier_level.unwrap_or(1);
+    let tier_levels = fetch_tier_levels_from_db(&state.database).await.map_err(|e| {
+        error!("Error fetching tier levels: {}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "Could not create the invitation." })))
+    })?;
+    if !tier_levels.contains(&tier_level) {
+        let levels: Vec<String> = tier_levels.iter().map(|level| level.to_string()).collect();
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": format!("Tier level must be one of: {}", levels.join(", ")) }))
+        ));
+    }
+
+    let email = body.email.trim().to_lowercase();
+    match fetch_user_by_email_from_db(&state.database, &email).await {
+        Ok(None) => {}
+        Ok(Some(_)) => return Err((
+            StatusCode::CONFLICT,
+            Json(json!({ "error": "A user with this email address already exists." }))
+        )),
+        Err(e) => {
+            error!("Error checking the invited email address: {}", e);
+            return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "Could not create the invitation." }))));
+        }
+    }
+
+    let invitation = match insert_invitation_into_db(
+        &state.database,
+        &email,
+        body.role_level.unwrap_or(1),
+        tier_level,
+        &generate_nonce(),
+        current_user.id,
+        invitation_expiry(),
+    ).await {
+        Ok(invitation) => invitation,
+        Err(sqlx::Error::Database(err)) if err.is_unique_violation() => return Err((
+            StatusCode::CONFLICT,
+            Json(json!({ "error": "This email address has already been invited, resend the pending invitation instead." }))
+        )),
+        Err(e) => {
+            error!("Error creating invitation: {}", e);
+            return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "Could not create the invitation." }))));
+        }
+    };
+
+    let event = AuditEvent::new("invitation.create", Some(current_user.id))
+        .target("invitation", invitation.id)
+        .change(None, Some(json!({
+            "email": invitation.email,
+            "role_level": invitation.role_level,
+            "tier_level": invitation.tier_level,
+        })));
+    record_audit_event(&state.database, &audit, event).await;
+
+    send_invitation(&state.mail, &invitation).await?;
+
+    Ok(Json(invitation))
+}
+
+// Send a pending invitation again, with a new link
+#[utoipa::path(
+    post,
+    path = "/invitations/{id}/resend",
+    tag = "invitation",
+    security(
+        ("jwt_token" = [])
+    ),
+    params(
+        ("id" = String, Path, description = "Invitation ID")
+    ),
+    responses(
+        (status = 200, description = "Invitation sent again, earlier links no longer work", body = Invitation),
+        (status = 400, description = "Invalid UUID format", body = serde_json::Value),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 404, description = "Invitation not found", body = serde_json::Value),
+        (status = 409, description = "The invitation has already been accepted or revoked", body = serde_json::Value),
+        (status = 500, description = "Internal server error", body = serde_json::Value)
+    )
+)]
+#[instrument(skip(state, current_user, audit))]
+pub async fn post_invitation_resend(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<User>,
+    audit: AuditContext,
+    Path(id): Path<String>,
+) -> Result<Json<Invitation>, (StatusCode, Json<serde_json::Value>)> {
+    let uuid = Uuid::parse_str(&id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(json!({ "error": "Invalid UUID format." }))))?;
+
+    let invitation = match renew_invitation_in_db(&state.database, uuid, &generate_nonce(), invitation_expiry()).await {
+        Ok(Some(invitation)) => invitation,
+        Ok(None) => {
+            // Tell apart a missing invitation from one that can no longer be resent
+            return match fetch_invitation_by_id_from_db(&state.database, uuid).await {
+                Ok(Some(invitation)) => Err((
+                    StatusCode::CONFLICT,
+                    Json(json!({ "error": format!("The invitation has already been {}.", invitation.status) }))
+                )),
+                Ok(None) => Err((
+                    StatusCode::NOT_FOUND,
+                    Json(json!({ "error": format!("Invitation with ID '{}' not found.", id) }))
+                )),
+                Err(e) => {
+                    error!("Error fetching invitation: {}", e);
+                    Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "Could not resend the invitation." }))))
+                }
+            };
+        }
+        Err(e) => {
+            error!("Error renewing invitation: {}", e);
+            return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "Could not resend the invitation." }))));
+        }
+    };
+
+    let event = AuditEvent::new("invitation.resend", Some(current_user.id)).target("invitation", invitation.id);
+    record_audit_event(&state.database, &audit, event).await;
+
+    send_invitation(&state.mail, &invitation).await?;
+
+    Ok(Json(invitation))
+}
+
+// Accept an invitation, creating the account with the chosen password and profile
+#[utoipa::path(
+    post,
+    path = "/invitations/accept",
+    tag = "invitation",
+    request_body = InvitationAcceptBody,
+    responses(
+        (status = 200, description = "Account created", body = UserInsertResponse),
+        (status = 400, description = "Validation error or invalid link", body = serde_json::Value),
+        (status = 409, description = "The username is already taken", body = serde_json::Value),
+        (status = 410, description = "The invitation has expired, been revoked or already been accepted", body = serde_json::Value),
+        (status = 429, description = "Too many requests", body = serde_json::Value),
+        (status = 500, description = "Internal server error", body = serde_json::Value)
+    )
+)]
+#[instrument(skip(state, audit, body))]
+pub async fn post_invitation_accept(
+    State(state): State<Arc<AppState>>,
+    audit: AuditContext,
+    Json(body): Json<InvitationAcceptBody>,
+) -> Result<Json<UserInsertResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let claims = decode_invitation_token(&body.token)
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(json!({ "error": e }))))?;
+    let id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(json!({ "error": "Invalid invitation link." }))))?;
+
+    // Validate input
+    if let Err(errors) = body.validate() {
+        let error_messages: Vec<String> = errors
+            .field_errors()
+            .values()
+            .flat_map(|errors| errors.iter().map(|e| e.message.clone().unwrap_or_default().to_string()))
+            .collect();
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": error_messages.join(", ") }))
+        ));
+    }
+
+    // Usernames given up recently stay blocked for a while
+    match is_username_blocked_in_db(&state.database, body.username.trim(), None).await {
+        Ok(false) => {}
+        Ok(true) => return Err((StatusCode::CONFLICT, Json(json!({ "error": "Username already exists." })))),
+        Err(_) => return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "Could not accept the invitation." })))),
+    }
+
+    let password_hash = hash_password(&body.password)
+        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "Failed to hash password." }))))?;
+    let totp_secret = if body.totp.unwrap_or(false) { generate_totp_secret() } else { String::new() };
+
+    match accept_invitation_in_db(&state.database, id, &claims.nonce, &body, &password_hash, &totp_secret).await {
+        Ok(Some(user)) => {
+            let event = AuditEvent::new("invitation.accept", Some(user.id))
+                .target("invitation", id)
+                .change(None, Some(json!({ "user_id": user.id, "username": user.username })));
+            record_audit_event(&state.database, &audit, event).await;
+            Ok(Json(user))
+        }
+        Ok(None) => Err((
+            StatusCode::GONE,
+            Json(json!({ "error": "This invitation is no longer valid." }))
+        )),
+        Err(sqlx::Error::Database(err)) if err.is_unique_violation() => Err((
+            StatusCode::CONFLICT,
+            Json(json!({ "error": "Username or email already exists." }))
+        )),
+        Err(e) => {
+            error!("Error accepting invitation: {}", e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "Could not accept the invitation." }))))
+        }
+    }
+}
+
+// --- Helper Functions ---
+
+fn generate_nonce() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(32)
+        .map(char::from)
+        .collect()
+}
+
+fn invitation_expiry() -> DateTime<Utc> {
+    Utc::now() + Duration::hours(get_env_u64("INVITATION_EXPIRY_HOURS", 168) as i64)
+}
+
+// Emails the signed link of an invitation
+async fn send_invitation(mail: &MailerState, invitation: &Invitation) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
+    let token = encode_invitation_token(invitation.id, &invitation.token_nonce, invitation.expires_at)
+        .map_err(|status| (status, Json(json!({ "error": "Could not sign the invitation." }))))?;
+    let base_url = get_env_with_default("INVITATION_ACCEPT_URL", "http://127.0.0.1:3000/invitations/accept");
+    let separator = if base_url.contains('?') { '&' } else { '?' };
+
+    // The invitee has no account and therefore no language yet
+    let locale = Locale::fallback();
+    let link = format!("{}{}token={}", base_url, separator, token);
+    let expires_at = invitation.expires_at.format("%Y-%m-%d %H:%M UTC").to_string();
+    let subject = t(locale, "mail.invitation.subject");
+    let body = t_with(locale, "mail.invitation.body", &[("link", &link), ("expires_at", &expires_at)]);
+
+    send_mail(mail, &invitation.email, &subject, &body).await.map_err(|e| {
+        error!("Failed to send invitation {}: {}", invitation.id, e);
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "The invitation has been saved, but could not be sent. Try resending it." })))
+    })
+}
@@ -0,0 +1,76 @@
+use axum::{extract::{Extension, State}, Json, http::StatusCode};
+use serde_json::json;
+use tracing::{error, instrument};
+use validator::Validate;
+use std::sync::Arc;
+
+use crate::database::tiers::insert_tier_into_db;
+use crate::middlewares::auth::invalidate_rate_limit_cache;
+use crate::models::tier::{Tier, TierInsertBody};
+use crate::models::user::User;
+use crate::models::audit::AuditEvent;
+use crate::utils::audit::{record_audit_event, AuditContext};
+use crate::routes::AppState;
+
+// --- Route Handler ---
+
+// Create a new tier
+#[utoipa::path(
+    post,
+    path = "/tiers/new",
+    tag = "tier",
+    security(
+        ("jwt_token" = [])
+    ),
+    request_body = TierInsertBody,
+    responses(
+        (status = 200, description = "Tier created successfully", body = Tier),
+        (status = 400, description = "Validation error", body = String),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 409, description = "A tier with this name or level already exists", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    )
+)]
+#[instrument(skip(state, current_user, audit, tier))]
+pub async fn post_tier(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<User>,
+    audit: AuditContext,
+    Json(tier): Json<TierInsertBody>
+) -> Result<Json<Tier>, (StatusCode, Json<serde_json::Value>)> {
+    // Validate input
+    if let Err(errors) = tier.validate() {
+        let error_messages: Vec<String> = errors
+            .field_errors()
+            .values()
+            .flat_map(|errors| errors.iter().map(|e| e.message.clone().unwrap_or_default().to_string()))
+            .collect();
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": error_messages.join(", ") }))
+        ));
+    }
+
+    match insert_tier_into_db(&state.database, tier).await {
+        Ok(new_tier) => {
+            // Users may already reference the new level, without a tier to back it
+            invalidate_rate_limit_cache();
+            let event = AuditEvent::new("tier.create", Some(current_user.id))
+                .target("tier", new_tier.id)
+                .change(None, Some(json!(new_tier)));
+            record_audit_event(&state.database, &audit, event).await;
+            Ok(Json(new_tier))
+        }
+        Err(sqlx::Error::Database(err)) if err.is_unique_violation() => Err((
+            StatusCode::CONFLICT,
+            Json(json!({ "error": "A tier with this name or level already exists." }))
+        )),
+        Err(err) => {
+            error!("Error creating tier: {}", err);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Could not create the tier." }))
+            ))
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use axum::{extract::{Extension, State}, Json};
+use axum::http::StatusCode;
+use serde_json::json;
+use tracing::{error, instrument};
+use validator::Validate;
+use std::sync::Arc;
+
+use crate::models::todo::{TodoList, TodoListBody};
+use crate::models::user::User;
+use crate::database::todo_lists::insert_todo_list_into_db;
+use crate::i18n::catalog::validation_message;
+use crate::i18n::locale::Locale;
+use crate::routes::AppState;
+
+// --- Route Handler ---
+
+// Create a list at the end of the lists of the current user
+#[utoipa::path(
+    post,
+    path = "/todos/lists/new",
+    tag = "todo",
+    security(
+        ("jwt_token" = [])
+    ),
+    request_body = TodoListBody,
+    responses(
+        (status = 200, description = "List created successfully", body = TodoList),
+        (status = 400, description = "Validation error", body = serde_json::Value),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 409, description = "A list with this name already exists", body = serde_json::Value),
+        (status = 500, description = "Internal server error", body = serde_json::Value)
+    )
+)]
+#[instrument(skip(state, user, body))]
+pub async fn post_todo_list(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<User>,
+    locale: Locale,
+    Json(body): Json<TodoListBody>,
+) -> Result<Json<TodoList>, (StatusCode, Json<serde_json::Value>)> {
+    if let Err(errors) = body.validate() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": validation_message(locale, &errors) }))
+        ));
+    }
+
+    let name = body.name.trim();
+    let description = body.description.as_deref().map(str::trim).filter(|d| !d.is_empty());
+
+    match insert_todo_list_into_db(&state.database, user.id, name, description).await {
+        Ok(list) => Ok(Json(list)),
+        Err(sqlx::Error::Database(err)) if err.is_unique_violation() => Err((
+            StatusCode::CONFLICT,
+            Json(json!({ "error": format!("A list named '{}' already exists.", name) })),
+        )),
+        Err(e) => {
+            error!("Error creating a todo list for user {}: {}", user.id, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Could not create the list." })),
+            ))
+        }
+    }
+}
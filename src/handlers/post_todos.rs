@@ -1,26 +1,18 @@
 use axum::{extract::{Extension, State}, Json};
 use axum::http::StatusCode;
-use serde::Deserialize;
 use serde_json::json;
 use tracing::instrument;
-use utoipa::ToSchema;
 use validator::Validate;
 use std::sync::Arc;
 
-use crate::models::todo::Todo;
+use crate::models::todo::{Todo, TodoBody};
 use crate::models::user::User;
-use crate::database::todos::insert_todo_into_db;
+use crate::database::todos::{fetch_todo_by_id_from_db, insert_todo_into_db};
+use crate::database::todo_lists::fetch_todo_list_by_id_from_db;
+use crate::utils::preferences::load_user_preferences;
+use crate::utils::recurrence::{within_recurrence_window, RECURRENCE_WINDOW_YEARS};
 use crate::routes::AppState;
 
-// Define the request body structure
-#[derive(Deserialize, Validate, ToSchema)]
-pub struct TodoBody {
-    #[validate(length(min = 3, max = 50))]
-    pub task: String,
-    #[validate(length(min = 3, max = 100))]
-    pub description: Option<String>,
-}
-
 // --- Route Handler ---
 
 // Define the API endpoint
@@ -36,6 +28,7 @@ pub struct TodoBody {
         (status = 200, description = "Todo created successfully", body = Todo),
         (status = 400, description = "Validation error", body = String),
         (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 404, description = "List or parent todo not found", body = serde_json::Value),
         (status = 500, description = "Internal server error", body = String)
     )
 )]
@@ -43,7 +36,7 @@ pub struct TodoBody {
 pub async fn post_todo(
     State(state): State<Arc<AppState>>,
     Extension(user): Extension<User>,
-    Json(todo): Json<TodoBody>
+    Json(mut todo): Json<TodoBody>
 ) -> Result<Json<Todo>, (StatusCode, Json<serde_json::Value>)> {
     // Validate input
     if let Err(errors) = todo.validate() {
@@ -58,11 +51,70 @@ pub async fn post_todo(
         ));
     }
 
-    match insert_todo_into_db(&state.database, todo.task, todo.description, user.id).await {
-        Ok(new_todo) => Ok(Json(new_todo)),
-        Err(_err) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({ "error": "Could not create a new todo." }))
-        )),
+    let db_error = |_err: sqlx::Error| (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(json!({ "error": "Could not create a new todo." }))
+    );
+
+    // Recurring todos repeat from their due date, and subtasks follow their parent instead
+    if todo.recurrence.is_some() && todo.due_at.is_none() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": "Recurring todos need a due date to repeat from." }))
+        ));
+    }
+    if todo.recurrence.is_some() && !todo.due_at.is_some_and(within_recurrence_window) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": format!("Recurring todos need a due date within {} years from now.", RECURRENCE_WINDOW_YEARS) }))
+        ));
+    }
+    if todo.recurrence.is_some() && todo.parent_id.is_some() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": "Subtasks cannot recur." }))
+        ));
+    }
+
+    // Subtasks belong to a top-level todo of the user, and always to the list of that todo
+    if let Some(parent_id) = todo.parent_id {
+        let parent = fetch_todo_by_id_from_db(&state.database, parent_id, user.id)
+            .await
+            .map_err(db_error)?
+            .filter(|parent| parent.user_id == user.id)
+            .ok_or_else(|| (
+                StatusCode::NOT_FOUND,
+                Json(json!({ "error": format!("Todo with ID '{}' not found.", parent_id) }))
+            ))?;
+        if parent.parent_id.is_some() {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({ "error": "Subtasks cannot have subtasks of their own." }))
+            ));
+        }
+        if todo.list_id.is_some_and(|list_id| Some(list_id) != parent.list_id) {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({ "error": "Subtasks belong to the list of their parent." }))
+            ));
+        }
+        todo.list_id = parent.list_id;
+    } else if let Some(list_id) = todo.list_id {
+        fetch_todo_list_by_id_from_db(&state.database, list_id, user.id)
+            .await
+            .map_err(db_error)?
+            .ok_or_else(|| (
+                StatusCode::NOT_FOUND,
+                Json(json!({ "error": format!("List with ID '{}' not found.", list_id) }))
+            ))?;
     }
+
+    // Due dates without a time zone belong to the zone the user prefers
+    let preferences = load_user_preferences(&state.database, user.id).await;
+    todo.resolve_due_timezone(&preferences.timezone);
+
+    insert_todo_into_db(&state.database, &todo, user.id)
+        .await
+        .map(Json)
+        .map_err(db_error)
 }
@@ -14,15 +14,20 @@ use chrono::Utc;
 use chrono::Duration;
 use tracing::error;
 
-use crate::{core::config::{get_env_bool, get_env_with_default}, utils::auth::{generate_totp_secret, hash_password}};
+use crate::{core::config::{get_env_bool, get_env_u64, get_env_with_default}, utils::auth::{generate_totp_secret, hash_password}};
 use crate::utils::process_image::process_image;
-use crate::database::users::{insert_user_into_db, update_user_profile_picture_in_db, fetch_profile_picture_url_from_db, fetch_user_by_email_from_db, insert_user_password_reset_code_into_db, update_user_password_in_db, fetch_current_password_reset_code_from_db, delete_all_password_reset_codes_for_user, check_user_exists_in_db, fetch_pending_user_by_email_from_db, activate_user_in_db, insert_pending_user_into_db};
+use crate::utils::avatar::{all_avatar_keys, avatar_variant_key, AVATAR_SIZES, DEFAULT_AVATAR_SIZE};
+use crate::database::users::{insert_user_into_db, update_user_profile_picture_in_db, fetch_profile_picture_url_from_db, fetch_user_by_email_from_db, insert_user_password_reset_code_into_db, update_user_password_in_db, fetch_current_password_reset_code_from_db, delete_all_password_reset_codes_for_user, check_user_exists_in_db, fetch_pending_user_by_email_from_db, activate_user_in_db, insert_pending_user_into_db, restore_user_in_db, renew_verification_code_in_db, record_verification_attempt_in_db, is_username_blocked_in_db};
 use crate::storage::upload::upload_to_storage;
 use crate::storage::delete::delete_from_storage;
 use crate::storage::presign_url::generate_presigned_url;
-use crate::models::user::{UserInsertResponse, UserInsertBody, UserProfilePictureUploadBody, UserProfilePictureUploadResponse, UserPasswordResetRequestBody, UserPasswordResetConfirmBody, UserRegisterBody, UserRegisterEmailVerifyBody, User};
+use crate::models::user::{UserInsertResponse, UserInsertBody, UserProfilePictureUploadBody, UserProfilePictureUploadResponse, UserPasswordResetRequestBody, UserPasswordResetConfirmBody, UserRegisterBody, UserRegisterEmailVerifyBody, UserRegisterResendBody, User};
+use crate::models::audit::AuditEvent;
+use crate::utils::audit::{record_audit_event, AuditContext};
 use crate::routes::AppState;
 use crate::mail::send::send_mail;
+use crate::i18n::catalog::{t, t_with, validation_message};
+use crate::i18n::locale::Locale;
 
 // --- Route Handler ---
 
@@ -42,27 +47,32 @@ use crate::mail::send::send_mail;
         (status = 500, description = "Internal server error", body = String)
     )
 )]
-#[instrument(skip(state, user))]
+#[instrument(skip(state, admin, audit, user))]
 pub async fn post_user(
     State(state): State<Arc<AppState>>,
+    Extension(admin): Extension<User>,
+    audit: AuditContext,
+    locale: Locale,
     Json(user): Json<UserInsertBody>,
 ) -> Result<Json<UserInsertResponse>, (StatusCode, Json<serde_json::Value>)> {
     // Validate input
     if let Err(errors) = user.validate() {
-        let error_messages: Vec<String> = errors
-            .field_errors()
-            .iter()
-            .flat_map(|(_, errors)| errors.iter().map(|e| e.message.clone().unwrap_or_default().to_string()))
-            .collect();
         return Err((
             StatusCode::BAD_REQUEST,
-            Json(json!({ "error": error_messages.join(", ") }))
+            Json(json!({ "error": validation_message(locale, &errors) }))
         ));
     }
 
+    // Usernames given up recently stay blocked for a while
+    match is_username_blocked_in_db(&state.database, &user.username, None).await {
+        Ok(false) => {}
+        Ok(true) => return Err((StatusCode::CONFLICT, Json(json!({ "error": t(locale, "user.username_taken") })))),
+        Err(_) => return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": t(locale, "user.create_failed") })))),
+    }
+
     // Hash the password before saving it
     let hashed_password = hash_password(&user.password)
-        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "Failed to hash password." }))))?;
+        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": t(locale, "error.hash_password") }))))?;
 
     // Generate TOTP secret if totp is Some("true")
     let totp_secret = if user.totp.unwrap_or(false) {
@@ -72,10 +82,21 @@ pub async fn post_user(
     };
 
     match insert_user_into_db(&state.database, &user.username, &user.email, &hashed_password, &totp_secret, 1, 1).await {
-        Ok(new_user) => Ok(Json(new_user)),
+        Ok(new_user) => {
+            let event = AuditEvent::new("user.create", Some(admin.id))
+                .target("user", new_user.id)
+                .change(None, Some(json!({
+                    "username": new_user.username,
+                    "email": new_user.email,
+                    "role_level": new_user.role_level,
+                    "tier_level": new_user.tier_level,
+                })));
+            record_audit_event(&state.database, &audit, event).await;
+            Ok(Json(new_user))
+        }
         Err(_err) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({ "error": "Could not create the user." }))
+            Json(json!({ "error": t(locale, "user.create_failed") }))
         )),
     }
 }
@@ -97,6 +118,7 @@ pub async fn post_user_profilepicture(
     Path(id): Path<String>,
     State(state): State<Arc<AppState>>,
     Extension(current_user): Extension<User>,
+    locale: Locale,
     mut multipart: Multipart,
 ) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
     // Config
@@ -110,7 +132,7 @@ pub async fn post_user_profilepicture(
         if !allowed_role_levels.contains(&current_user.role_level) && id != current_user.id.to_string() {
             return Err((
                 StatusCode::FORBIDDEN,
-                Json(json!({ "error": "You do not have permission to upload for this user." })),
+                Json(json!({ "error": t(locale, "picture.forbidden") })),
             ));
         }
         match Uuid::parse_str(&id) {
@@ -118,7 +140,7 @@ pub async fn post_user_profilepicture(
             Err(_) => {
                 return Err((
                     StatusCode::BAD_REQUEST,
-                    Json(json!({ "error": "Invalid UUID format." })),
+                    Json(json!({ "error": t(locale, "error.invalid_uuid") })),
                 ));
             }
         }
@@ -135,7 +157,7 @@ pub async fn post_user_profilepicture(
             error!("DB fetch error: {e}");
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({ "error": "Failed to check existing profile picture" })),
+                Json(json!({ "error": t(locale, "picture.check_failed") })),
             )
         })?
         {
@@ -147,10 +169,12 @@ pub async fn post_user_profilepicture(
         // Now, remove the bucket prefix
         let object_key = path.strip_prefix(&format!("{}/", bucket)).unwrap_or(path);
         
-        // Now use object_key
-        if let Err(e) = delete_from_storage(&state.storage, &bucket, object_key).await {
-            error!("Old image deletion failed: {e}");
-            // Continue with upload despite deletion failure
+        // Now delete every size of the old picture
+        for object_key in all_avatar_keys(object_key) {
+            if let Err(e) = delete_from_storage(&state.storage, &bucket, &object_key).await {
+                error!("Old image deletion failed: {e}");
+                // Continue with upload despite deletion failure
+            }
         }
     }
 
@@ -158,7 +182,7 @@ pub async fn post_user_profilepicture(
         error!("Multipart error: {e}");
         (
             StatusCode::BAD_REQUEST,
-            Json(json!({ "error": "Invalid file data" })),
+            Json(json!({ "error": t(locale, "picture.invalid_data") })),
         )
     })? {
         if field.name() == Some("profile_picture") {
@@ -167,7 +191,7 @@ pub async fn post_user_profilepicture(
             if !["image/webp", "image/jpeg", "image/png"].contains(&content_type.as_str()) {
                 return Err((
                     StatusCode::UNSUPPORTED_MEDIA_TYPE,
-                    Json(json!({ "error": "Only WebP, JPEG, and PNG formats allowed" })),
+                    Json(json!({ "error": t(locale, "picture.invalid_format") })),
                 ));
             }
 
@@ -176,52 +200,59 @@ pub async fn post_user_profilepicture(
                 error!("File read error: {e}");
                 (
                     StatusCode::BAD_REQUEST,
-                    Json(json!({ "error": "Failed to read file" })),
+                    Json(json!({ "error": t(locale, "picture.read_failed") })),
                 )
             })?;
 
             if data.len() > MAX_FILE_SIZE {
                 return Err((
                     StatusCode::PAYLOAD_TOO_LARGE,
-                    Json(json!({ "error": format!("File too large (max {}MB)", MAX_FILE_SIZE / 1024 / 1024) })),
+                    Json(json!({ "error": t_with(locale, "picture.too_large", &[("max", &(MAX_FILE_SIZE / 1024 / 1024).to_string())]) })),
                 ));
             }
 
-            // Process image
-            let processed_data = process_image(data, 300, 300, debug).await.map_err(|e| {
+            // Process image into every size
+            let variants = process_image(data, &AVATAR_SIZES, debug).await.map_err(|e| {
                 error!("Image processing failed: {e}");
                 (
                     StatusCode::UNPROCESSABLE_ENTITY,
-                    Json(json!({ "error": format!("Image processing failed: {e}") })),
+                    Json(json!({ "error": t_with(locale, "picture.processing_failed", &[("error", &e)]) })),
                 )
             })?;
 
-            // Generate secure filename
+            // Generate secure filename, the size is appended per variant
             let timestamp = chrono::Utc::now().timestamp();
-            let object_key = format!("profile_pictures/{}_{}.webp", user_id, timestamp);
-
-            // Upload processed image
-            let file_url = upload_to_storage(
-                &state.storage,
-                &bucket,
-                &object_key,
-                &processed_data,
-            )
-            .await
-            .map_err(|e| {
-                error!("Upload error: {e}");
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(json!({ "error": "Upload failed" })),
+            let base_key = format!("profile_pictures/{}_{}", user_id, timestamp);
+            let object_key = avatar_variant_key(&base_key, DEFAULT_AVATAR_SIZE);
+
+            // Upload processed images, the URL of the default size is stored
+            let mut file_url = String::new();
+            for (size, processed_data) in variants {
+                let url = upload_to_storage(
+                    &state.storage,
+                    &bucket,
+                    &avatar_variant_key(&base_key, size),
+                    &processed_data,
                 )
-            })?;
+                .await
+                .map_err(|e| {
+                    error!("Upload error: {e}");
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        Json(json!({ "error": t(locale, "picture.upload_failed") })),
+                    )
+                })?;
+                if size == DEFAULT_AVATAR_SIZE {
+                    file_url = url;
+                }
+            }
 
             // Update database
             if let Err(e) = update_user_profile_picture_in_db(&state.database, user_id, &file_url).await {
                 error!("DB update error: {e}");
                 return Err((
                     StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(json!({ "error": "Failed to update profile URL" })),
+                    Json(json!({ "error": t(locale, "picture.update_failed") })),
                 ));
             }
 
@@ -237,21 +268,22 @@ pub async fn post_user_profilepicture(
                 error!("Presign error: {e}");
                 (
                     StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(json!({ "error": "Failed to generate presigned URL" })),
+                    Json(json!({ "error": t(locale, "picture.presign_failed") })),
                 )
             })?;
 
 
             return Ok(Json(json!({
                 "profile_picture_url": file_url,
-                "profile_picture_presigned_url": presigned_url
+                "profile_picture_presigned_url": presigned_url,
+                "avatar_url": format!("/users/{}/avatar", user_id)
             })));
         }
     }
 
     Err((
         StatusCode::BAD_REQUEST,
-        Json(json!({ "error": "No file uploaded" })),
+        Json(json!({ "error": t(locale, "picture.missing") })),
     ))
 }
 
@@ -270,13 +302,15 @@ pub async fn post_user_profilepicture(
 )]
 pub async fn post_user_password_reset(
     State(state): State<Arc<AppState>>,
+    audit: AuditContext,
+    locale: Locale,
     Json(body): Json<UserPasswordResetRequestBody>,
 ) -> Result<StatusCode, (StatusCode, Json<serde_json::Value>)> {
     // 1. Find user by email
     let user = match fetch_user_by_email_from_db(&state.database, &body.email).await {
         Ok(Some(user)) => user,
         Ok(None) => return Ok(StatusCode::OK), // Don't reveal if email exists
-        Err(_) => return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Database error"})))),
+        Err(_) => return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": t(locale, "error.database")})))),
     };
 
     // 2. Generate code and expiry
@@ -290,17 +324,18 @@ pub async fn post_user_password_reset(
     // 3. Store code in DB
     insert_user_password_reset_code_into_db(&state.database, user.id, &code, expires_at)
         .await
-        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to store reset code"}))))?;
-
-    // 4. Send email
-    let subject = "Password reset request";
-    let body = format!(
-        "Use this code to reset your password: {}\n\nThis code will expire in 24 hours.",
-        code
-    );
-    send_mail(&state.mail, &user.email, subject, &body)
+        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": t(locale, "reset.store_failed")}))))?;
+
+    let event = AuditEvent::new("user.password_reset_request", None).target("user", user.id);
+    record_audit_event(&state.database, &audit, event).await;
+
+    // 4. Send email, in the language of the user
+    let mail_locale = Locale::for_user(user.language_code.as_deref(), locale);
+    let subject = t(mail_locale, "mail.password_reset.subject");
+    let body = t_with(mail_locale, "mail.password_reset.body", &[("code", &code)]);
+    send_mail(&state.mail, &user.email, &subject, &body)
         .await
-        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to send email"}))))?;
+        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": t(locale, "error.send_mail")}))))?;
 
     Ok(StatusCode::OK)
 }
@@ -319,16 +354,18 @@ pub async fn post_user_password_reset(
         (status = 500, description = "Internal server error, database issue", body = String)
     )
 )]
-#[instrument(skip(state, body))]
+#[instrument(skip(state, audit, body))]
 pub async fn post_user_password_reset_verify(
     State(state): State<Arc<AppState>>,
+    audit: AuditContext,
+    locale: Locale,
     Json(body): Json<UserPasswordResetConfirmBody>,
 ) -> Result<StatusCode, (StatusCode, Json<serde_json::Value>)> {
     // 1. Validate new password (example: at least 8 chars)
     if body.new_password.len() < 8 {
         return Err((
             StatusCode::BAD_REQUEST,
-            Json(json!({ "error": "Password must be at least 8 characters long." }))
+            Json(json!({ "error": t(locale, "reset.password_too_short") }))
         ));
     }
 
@@ -339,13 +376,13 @@ pub async fn post_user_password_reset_verify(
             // Don't reveal if email exists or not
             return Err((
                 StatusCode::BAD_REQUEST,
-                Json(json!({ "error": "Invalid code or email." }))
+                Json(json!({ "error": t(locale, "reset.invalid_code_or_email") }))
             ));
         }
         Err(_) => {
             return Err((
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({ "error": "Database error." }))
+                Json(json!({ "error": t(locale, "error.database") }))
             ));
         }
     };
@@ -359,9 +396,11 @@ pub async fn post_user_password_reset_verify(
                 // The reset code is valid
                 // Proceed with the next steps
             } else {
+                let event = AuditEvent::new("user.password_reset_failed", None).target("user", user.id);
+                record_audit_event(&state.database, &audit, event).await;
                 return Err((
                     StatusCode::BAD_REQUEST,
-                    Json(json!({ "error": "Invalid or expired code." }))
+                    Json(json!({ "error": t(locale, "reset.invalid_code") }))
                 ));
             }
         },
@@ -369,7 +408,7 @@ pub async fn post_user_password_reset_verify(
             // If no code was found or there's an error, return an invalid code response
             return Err((
                 StatusCode::BAD_REQUEST,
-                Json(json!({ "error": "Invalid or expired code." }))
+                Json(json!({ "error": t(locale, "reset.invalid_code") }))
             ));
         }
     };
@@ -377,15 +416,18 @@ pub async fn post_user_password_reset_verify(
 
     // 4. Hash new password
     let new_password_hash = hash_password(&body.new_password)
-        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "Failed to hash password." }))))?;
+        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": t(locale, "error.hash_password") }))))?;
 
     // 5. Update user's password
     update_user_password_in_db(&state.database, user.id, &new_password_hash).await
-        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "Failed to update password." }))))?;
+        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": t(locale, "reset.update_failed") }))))?;
 
     // 6. Invalidate the reset code
     delete_all_password_reset_codes_for_user(&state.database, user.id).await
-        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "Failed to invalidate reset code." }))))?;
+        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": t(locale, "reset.invalidate_failed") }))))?;
+
+    let event = AuditEvent::new("user.password_reset", Some(user.id)).target("user", user.id);
+    record_audit_event(&state.database, &audit, event).await;
 
     Ok(StatusCode::OK)
 }
@@ -404,40 +446,52 @@ pub async fn post_user_password_reset_verify(
 )]
 pub async fn post_user_register(
     State(state): State<Arc<AppState>>,
+    locale: Locale,
     Json(user): Json<UserRegisterBody>,
 ) -> Result<StatusCode, (StatusCode, Json<serde_json::Value>)> {
     // Validate input
     if let Err(errors) = user.validate() {
-        let error_messages: Vec<String> = errors
-            .field_errors()
-            .iter()
-            .flat_map(|(_, errors)| errors.iter().map(|e| e.message.clone().unwrap_or_default().to_string()))
-            .collect();
         return Err((
             StatusCode::BAD_REQUEST,
-            Json(json!({ "error": error_messages.join(", ") }))
+            Json(json!({ "error": validation_message(locale, &errors) }))
         ));
     }
 
     // Check if user/email exists
     if check_user_exists_in_db(&state.database, &user.email, &user.username)
     .await
-    .map_err(|e| {
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "error": t(locale, "error.database") }))
+        )
+    })?
+    {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(json!({ "error": t(locale, "user.exists") }))
+        ));
+    }
+
+    // Usernames given up recently stay blocked for a while
+    if is_username_blocked_in_db(&state.database, &user.username, None)
+    .await
+    .map_err(|_| {
         (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({ "error": format!("Database error: {e}") }))
+            Json(json!({ "error": t(locale, "error.database") }))
         )
     })?
     {
         return Err((
             StatusCode::CONFLICT,
-            Json(json!({ "error": "User or email already exists." }))
+            Json(json!({ "error": t(locale, "user.exists") }))
         ));
     }
 
     // Hash password
     let hashed_password = hash_password(&user.password)
-        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "Failed to hash password." }))))?;
+        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": t(locale, "error.hash_password") }))))?;
 
     // Generate TOTP secret if requested
     let totp_secret = if user.totp.unwrap_or(false) {
@@ -447,11 +501,7 @@ pub async fn post_user_register(
     };
 
     // Generate verification code
-    let code: String = rand::thread_rng()
-        .sample_iter(&Alphanumeric)
-        .take(32)
-        .map(char::from)
-        .collect();
+    let code = generate_verification_code();
     let expires_at = Utc::now() + Duration::hours(24);
 
     // Insert user in "pending" state
@@ -470,21 +520,77 @@ pub async fn post_user_register(
         user.description.as_deref(),
         totp_secret.as_deref()
     ).await
-        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "Failed to create user." }))))?;
+        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": t(locale, "user.create_failed") }))))?;
 
     // Send verification email
-    let subject = "Verify your email";
-    let body = format!(
-        "Welcome! Please verify your email by using this code: {}\n\nThis code will expire in 24 hours.",
-        code
-    );
-    send_mail(&state.mail, &user.email, subject, &body)
-        .await
-        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "Failed to send verification email." }))))?;
+    let mail_locale = Locale::for_user(user.language_code.as_deref(), locale);
+    send_verification_code(&state, mail_locale, &user.email, &code).await?;
 
     Ok(StatusCode::OK)
 }
 
+#[utoipa::path(
+    post,
+    path = "/register/resend",
+    tag = "user",
+    request_body = UserRegisterResendBody,
+    responses(
+        (status = 200, description = "A new verification code has been sent, if a registration for this email is pending", body = String),
+        (status = 400, description = "Invalid email", body = String),
+        (status = 429, description = "Too many requests", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    )
+)]
+#[instrument(skip(state, body))]
+pub async fn post_user_register_resend(
+    State(state): State<Arc<AppState>>,
+    locale: Locale,
+    Json(body): Json<UserRegisterResendBody>,
+) -> Result<StatusCode, (StatusCode, Json<serde_json::Value>)> {
+    if body.validate().is_err() {
+        return Err((StatusCode::BAD_REQUEST, Json(json!({ "error": t(locale, "register.invalid_email") }))));
+    }
+
+    // Replaces the previous code, which stops working
+    let code = generate_verification_code();
+    let expires_at = Utc::now() + Duration::hours(24);
+    let cooldown = get_env_u64("REGISTRATION_RESEND_COOLDOWN_SECONDS", 60) as i64;
+
+    match renew_verification_code_in_db(&state.database, &body.email, &code, expires_at, cooldown).await {
+        Ok(Some(language_code)) => {
+            // In the language the user registered with, like the first code
+            let mail_locale = Locale::for_user(language_code.as_deref(), locale);
+            send_verification_code(&state, mail_locale, &body.email, &code).await?
+        }
+        // Don't reveal whether a registration is pending, or when the next code can be requested
+        Ok(None) => {}
+        Err(e) => {
+            error!("Error renewing verification code: {}", e);
+            return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": t(locale, "error.database") }))));
+        }
+    }
+
+    Ok(StatusCode::OK)
+}
+
+// Generates a random code to verify an email address with
+fn generate_verification_code() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(32)
+        .map(char::from)
+        .collect()
+}
+
+// Emails a verification code to a pending user
+async fn send_verification_code(state: &AppState, locale: Locale, email: &str, code: &str) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
+    let subject = t(locale, "mail.verification.subject");
+    let body = t_with(locale, "mail.verification.body", &[("code", code)]);
+    send_mail(&state.mail, email, &subject, &body)
+        .await
+        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": t(locale, "register.send_failed") }))))
+}
+
 #[utoipa::path(
     post,
     path = "/register/verify",
@@ -495,32 +601,98 @@ pub async fn post_user_register(
         (status = 400, description = "Invalid code or email", body = String),
         (status = 404, description = "User not found", body = String),
         (status = 410, description = "Verification code expired", body = String),
+        (status = 429, description = "Too many attempts, a new code has to be requested", body = String),
         (status = 500, description = "Internal server error", body = String)
     )
 )]
 pub async fn post_user_register_verify(
     State(state): State<Arc<AppState>>,
+    audit: AuditContext,
+    locale: Locale,
     Json(body): Json<UserRegisterEmailVerifyBody>,
 ) -> Result<StatusCode, (StatusCode, Json<serde_json::Value>)> {
     // 1. Find user by email
     let user = match fetch_pending_user_by_email_from_db(&state.database, &body.email).await {
         Ok(Some(user)) => user,
-        Ok(None) => return Err((StatusCode::NOT_FOUND, Json(json!({ "error": "User not found." })))),
-        Err(_) => return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "Database error." })))),
+        Ok(None) => return Err((StatusCode::NOT_FOUND, Json(json!({ "error": t(locale, "user.not_found") })))),
+        Err(_) => return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": t(locale, "error.database") })))),
     };
 
-    // 2. Check code and expiry
-    if user.verification_code.as_deref() != Some(body.code.as_str()) {
-        return Err((StatusCode::BAD_REQUEST, Json(json!({ "error": "Invalid code." }))));
+    // 2. Check expiry and the amount of attempts, then the code
+    if user.verification_expires_at.is_none() || Utc::now() > user.verification_expires_at.unwrap() {
+        return Err((StatusCode::GONE, Json(json!({ "error": t(locale, "register.code_expired") }))));
     }
 
-    if user.verification_expires_at.is_none() || Utc::now() > user.verification_expires_at.unwrap() {
-        return Err((StatusCode::GONE, Json(json!({ "error": "Verification code expired." }))));
+    // Every attempt is counted before comparing, so parallel guesses can't slip past the limit
+    let max_attempts = get_env_u64("REGISTRATION_MAX_VERIFICATION_ATTEMPTS", 5).max(1) as i32;
+    let attempts = record_verification_attempt_in_db(&state.database, user.id).await
+        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": t(locale, "error.database") }))))?;
+    if attempts > max_attempts {
+        return Err((
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(json!({ "error": t(locale, "register.too_many_attempts") }))
+        ));
+    }
+
+    if user.verification_code.as_deref() != Some(body.code.as_str()) {
+        let event = AuditEvent::new("user.verification_failed", None).target("user", user.id);
+        record_audit_event(&state.database, &audit, event).await;
+        return Err((StatusCode::BAD_REQUEST, Json(json!({ "error": t(locale, "register.invalid_code") }))));
     }
 
     // 3. Activate user
     activate_user_in_db(&state.database, user.id).await
-        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "Failed to activate user." }))))?;
+        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": t(locale, "register.activate_failed") }))))?;
+
+    let event = AuditEvent::new("user.activate", Some(user.id)).target("user", user.id);
+    record_audit_event(&state.database, &audit, event).await;
 
     Ok(StatusCode::OK)
 }
+
+// Restore a deleted user whose grace period has not passed yet
+#[utoipa::path(
+    post,
+    path = "/users/{id}/restore",
+    tag = "user",
+    security(
+        ("jwt_token" = [])
+    ),
+    params(
+        ("id" = String, Path, description = "User ID")
+    ),
+    responses(
+        (status = 200, description = "User restored successfully", body = serde_json::Value),
+        (status = 400, description = "Invalid UUID format", body = serde_json::Value),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 404, description = "No deleted user with this ID, or its grace period has passed", body = serde_json::Value),
+        (status = 500, description = "Internal server error", body = serde_json::Value)
+    )
+)]
+#[instrument(skip(state, admin, audit))]
+pub async fn post_user_restore(
+    State(state): State<Arc<AppState>>,
+    Extension(admin): Extension<User>,
+    audit: AuditContext,
+    locale: Locale,
+    Path(id): Path<String>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    let uuid = Uuid::parse_str(&id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(json!({ "error": t(locale, "error.invalid_uuid") }))))?;
+
+    match restore_user_in_db(&state.database, uuid).await {
+        Ok(0) => Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": t_with(locale, "user.not_restorable", &[("id", &id)]) })),
+        )),
+        Ok(_) => {
+            let event = AuditEvent::new("user.restore", Some(admin.id)).target("user", uuid);
+            record_audit_event(&state.database, &audit, event).await;
+            Ok(Json(json!({ "success": t_with(locale, "user.restored", &[("id", &id)]) })))
+        }
+        Err(e) => {
+            error!("Error restoring user: {}", e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": t(locale, "user.restore_failed") }))))
+        }
+    }
+}
@@ -16,5 +16,5 @@ use tracing::instrument;
 )]
 #[instrument(skip(user))]
 pub async fn protected(Extension(user): Extension<User>) -> impl IntoResponse {
-    Json(UserGetResponse {id:user.id,username:user.username,email:user.email,role_level:user.role_level,tier_level:user.tier_level,creation_date:user.creation_date, profile_picture_url: user.profile_picture_url, first_name: user.first_name, last_name: user.last_name, country_code: user.country_code, language_code: user.language_code, birthday: user.birthday, description: user.description })
+    Json(UserGetResponse {id:user.id,username:user.username,email:user.email,role_level:user.role_level,tier_level:user.tier_level,creation_date:user.creation_date, profile_picture_url: user.profile_picture_url, first_name: user.first_name, last_name: user.last_name, country_code: user.country_code, language_code: user.language_code, birthday: user.birthday, description: user.description, tenant: user.tenant })
 }
\ No newline at end of file
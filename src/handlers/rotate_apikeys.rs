@@ -11,6 +11,8 @@ use crate::utils::auth::{generate_api_key, hash_password};
 use crate::models::user::User;
 use crate::database::apikeys::{fetch_existing_apikey, insert_api_key_into_db, disable_apikey_in_db};
 use crate::models::apikey::{ApiKeyRotateBody, ApiKeyRotateResponse, ApiKeyRotateResponseInfo};
+use crate::models::audit::AuditEvent;
+use crate::utils::audit::{record_audit_event, AuditContext};
 use crate::routes::AppState;
 
 #[utoipa::path(
@@ -31,10 +33,11 @@ use crate::routes::AppState;
         ("id" = String, Path, description = "API key identifier")
     )
 )]
-#[instrument(skip(state, user, apikeyrotatebody))]
+#[instrument(skip(state, user, audit, apikeyrotatebody))]
 pub async fn rotate_apikey(
     State(state): State<Arc<AppState>>,
     Extension(user): Extension<User>,
+    audit: AuditContext,
     Path(id): Path<String>,
     Json(apikeyrotatebody): Json<ApiKeyRotateBody>
 ) -> Result<Json<ApiKeyRotateResponse>, (StatusCode, Json<serde_json::Value>)> {
@@ -113,6 +116,11 @@ pub async fn rotate_apikey(
         ));
     }
 
+    let event = AuditEvent::new("apikey.rotate", Some(user.id))
+        .target("apikey", existing_key.id)
+        .change(None, Some(json!({ "replaced_by": new_key.id })));
+    record_audit_event(&state.database, &audit, event).await;
+
     // Create the ApiKeyRotateResponse
     let rotate_response = ApiKeyRotateResponse {
         id: new_key.id,
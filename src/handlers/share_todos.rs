@@ -0,0 +1,395 @@
+use axum::{
+    extract::{State, Extension, Path},
+    Json,
+    http::StatusCode,
+};
+use uuid::Uuid;
+use serde_json::json;
+use tracing::{error, instrument};
+use std::sync::Arc;
+
+use crate::models::todo::{Todo, TodoList, TodoListShare, TodoShare, TodoShareBody};
+use crate::models::user::User;
+use crate::models::audit::AuditEvent;
+use crate::database::todos::fetch_todo_by_id_from_db;
+use crate::database::todo_lists::fetch_accessible_todo_list_by_id_from_db;
+use crate::database::todo_shares::{
+    delete_todo_list_share_from_db, delete_todo_share_from_db, fetch_share_recipient_from_db,
+    fetch_todo_list_share_from_db, fetch_todo_list_shares_from_db, fetch_todo_share_from_db,
+    fetch_todo_shares_from_db, upsert_todo_list_share_in_db, upsert_todo_share_in_db,
+};
+use crate::utils::audit::{record_audit_event, AuditContext};
+use crate::routes::AppState;
+
+type HandlerError = (StatusCode, Json<serde_json::Value>);
+
+// --- Route Handlers ---
+
+// Share a todo with another user, or change the permission of an existing share
+#[utoipa::path(
+    post,
+    path = "/todos/{id}/shares",
+    tag = "todo",
+    security(
+        ("jwt_token" = [])
+    ),
+    params(
+        ("id" = String, Path, description = "Todo ID")
+    ),
+    request_body = TodoShareBody,
+    responses(
+        (status = 200, description = "Todo shared successfully", body = TodoShare),
+        (status = 400, description = "Invalid UUID format, or sharing with yourself", body = serde_json::Value),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 403, description = "Only the owner can share the todo", body = serde_json::Value),
+        (status = 404, description = "Todo not found, or no active user with this username in the tenant", body = serde_json::Value),
+        (status = 500, description = "Internal server error", body = serde_json::Value)
+    )
+)]
+#[instrument(skip(state, user, audit, body))]
+pub async fn post_todo_share(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<User>,
+    audit: AuditContext,
+    Path(id): Path<String>,
+    Json(body): Json<TodoShareBody>,
+) -> Result<Json<TodoShare>, HandlerError> {
+    let todo = fetch_accessible_todo(&state, &id, user.id).await?;
+    if todo.user_id != user.id {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({ "error": "Only the owner can share this todo." })),
+        ));
+    }
+
+    let recipient_id = fetch_recipient(&state, &body, user.id).await?;
+    if recipient_id == user.id {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": "You cannot share a todo with yourself." })),
+        ));
+    }
+
+    let previous = fetch_todo_share_from_db(&state.database, todo.id, recipient_id)
+        .await
+        .map_err(|e| db_error(todo.id, e))?;
+
+    let share = upsert_todo_share_in_db(&state.database, todo.id, recipient_id, body.permission.as_str(), user.id)
+        .await
+        .map_err(|e| db_error(todo.id, e))?;
+
+    let event = AuditEvent::new("todo.share", Some(user.id))
+        .target("todo", todo.id)
+        .change(
+            previous.map(|previous| json!({ "user_id": previous.user_id, "permission": previous.permission })),
+            Some(json!({ "user_id": share.user_id, "permission": share.permission })),
+        );
+    record_audit_event(&state.database, &audit, event).await;
+
+    Ok(Json(share))
+}
+
+// List the users a todo is shared with
+#[utoipa::path(
+    get,
+    path = "/todos/{id}/shares",
+    tag = "todo",
+    security(
+        ("jwt_token" = [])
+    ),
+    params(
+        ("id" = String, Path, description = "Todo ID")
+    ),
+    responses(
+        (status = 200, description = "All shares for the owner, only their own share for other users", body = [TodoShare]),
+        (status = 400, description = "Invalid UUID format", body = serde_json::Value),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 404, description = "Todo not found", body = serde_json::Value),
+        (status = 500, description = "Internal server error", body = serde_json::Value)
+    )
+)]
+#[instrument(skip(state, user))]
+pub async fn get_todo_shares(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<User>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<TodoShare>>, HandlerError> {
+    let todo = fetch_accessible_todo(&state, &id, user.id).await?;
+
+    // Users a todo is shared with only learn their own permission, not who else can see it
+    let shares = if todo.user_id == user.id {
+        fetch_todo_shares_from_db(&state.database, todo.id).await
+    } else {
+        fetch_todo_share_from_db(&state.database, todo.id, user.id)
+            .await
+            .map(|share| share.into_iter().collect())
+    };
+
+    shares.map(Json).map_err(|e| db_error(todo.id, e))
+}
+
+// Revoke the access of a user to a todo, users can also remove todos shared with them
+#[utoipa::path(
+    delete,
+    path = "/todos/{id}/shares/{user_id}",
+    tag = "todo",
+    security(
+        ("jwt_token" = [])
+    ),
+    params(
+        ("id" = String, Path, description = "Todo ID"),
+        ("user_id" = String, Path, description = "ID of the user to revoke the access of")
+    ),
+    responses(
+        (status = 200, description = "Access revoked", body = serde_json::Value),
+        (status = 400, description = "Invalid UUID format", body = serde_json::Value),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 403, description = "Only the owner can revoke the access of other users", body = serde_json::Value),
+        (status = 404, description = "Todo or share not found", body = serde_json::Value),
+        (status = 500, description = "Internal server error", body = serde_json::Value)
+    )
+)]
+#[instrument(skip(state, user, audit))]
+pub async fn delete_todo_share(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<User>,
+    audit: AuditContext,
+    Path((id, user_id)): Path<(String, String)>,
+) -> Result<Json<serde_json::Value>, HandlerError> {
+    let recipient_id = Uuid::parse_str(&user_id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(json!({ "error": "Invalid UUID format." }))))?;
+
+    let todo = fetch_accessible_todo(&state, &id, user.id).await?;
+    if todo.user_id != user.id && recipient_id != user.id {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({ "error": "Only the owner can revoke the access of other users." })),
+        ));
+    }
+
+    let removed = delete_todo_share_from_db(&state.database, todo.id, recipient_id)
+        .await
+        .map_err(|e| db_error(todo.id, e))?;
+    if removed == 0 {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": format!("Todo '{}' is not shared with user '{}'.", todo.id, recipient_id) })),
+        ));
+    }
+
+    let event = AuditEvent::new("todo.unshare", Some(user.id))
+        .target("todo", todo.id)
+        .change(Some(json!({ "user_id": recipient_id })), None);
+    record_audit_event(&state.database, &audit, event).await;
+
+    Ok(Json(json!({ "success": format!("Access of user '{}' to todo '{}' revoked.", recipient_id, todo.id) })))
+}
+
+// Share a list with another user, which gives access to all todos in the list
+#[utoipa::path(
+    post,
+    path = "/todos/lists/{id}/shares",
+    tag = "todo",
+    security(
+        ("jwt_token" = [])
+    ),
+    params(
+        ("id" = String, Path, description = "List ID")
+    ),
+    request_body = TodoShareBody,
+    responses(
+        (status = 200, description = "List shared successfully", body = TodoListShare),
+        (status = 400, description = "Invalid UUID format, or sharing with yourself", body = serde_json::Value),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 403, description = "Only the owner can share the list", body = serde_json::Value),
+        (status = 404, description = "List not found, or no active user with this username in the tenant", body = serde_json::Value),
+        (status = 500, description = "Internal server error", body = serde_json::Value)
+    )
+)]
+#[instrument(skip(state, user, audit, body))]
+pub async fn post_todo_list_share(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<User>,
+    audit: AuditContext,
+    Path(id): Path<String>,
+    Json(body): Json<TodoShareBody>,
+) -> Result<Json<TodoListShare>, HandlerError> {
+    let list = fetch_accessible_list(&state, &id, user.id).await?;
+    if list.user_id != user.id {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({ "error": "Only the owner can share this list." })),
+        ));
+    }
+
+    let recipient_id = fetch_recipient(&state, &body, user.id).await?;
+    if recipient_id == user.id {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": "You cannot share a list with yourself." })),
+        ));
+    }
+
+    let previous = fetch_todo_list_share_from_db(&state.database, list.id, recipient_id)
+        .await
+        .map_err(|e| db_error(list.id, e))?;
+
+    let share = upsert_todo_list_share_in_db(&state.database, list.id, recipient_id, body.permission.as_str(), user.id)
+        .await
+        .map_err(|e| db_error(list.id, e))?;
+
+    let event = AuditEvent::new("todo_list.share", Some(user.id))
+        .target("todo_list", list.id)
+        .change(
+            previous.map(|previous| json!({ "user_id": previous.user_id, "permission": previous.permission })),
+            Some(json!({ "user_id": share.user_id, "permission": share.permission })),
+        );
+    record_audit_event(&state.database, &audit, event).await;
+
+    Ok(Json(share))
+}
+
+// List the users a list is shared with
+#[utoipa::path(
+    get,
+    path = "/todos/lists/{id}/shares",
+    tag = "todo",
+    security(
+        ("jwt_token" = [])
+    ),
+    params(
+        ("id" = String, Path, description = "List ID")
+    ),
+    responses(
+        (status = 200, description = "All shares for the owner, only their own share for other users", body = [TodoListShare]),
+        (status = 400, description = "Invalid UUID format", body = serde_json::Value),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 404, description = "List not found", body = serde_json::Value),
+        (status = 500, description = "Internal server error", body = serde_json::Value)
+    )
+)]
+#[instrument(skip(state, user))]
+pub async fn get_todo_list_shares(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<User>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<TodoListShare>>, HandlerError> {
+    let list = fetch_accessible_list(&state, &id, user.id).await?;
+
+    let shares = if list.user_id == user.id {
+        fetch_todo_list_shares_from_db(&state.database, list.id).await
+    } else {
+        fetch_todo_list_share_from_db(&state.database, list.id, user.id)
+            .await
+            .map(|share| share.into_iter().collect())
+    };
+
+    shares.map(Json).map_err(|e| db_error(list.id, e))
+}
+
+// Revoke the access of a user to a list, users can also remove lists shared with them
+#[utoipa::path(
+    delete,
+    path = "/todos/lists/{id}/shares/{user_id}",
+    tag = "todo",
+    security(
+        ("jwt_token" = [])
+    ),
+    params(
+        ("id" = String, Path, description = "List ID"),
+        ("user_id" = String, Path, description = "ID of the user to revoke the access of")
+    ),
+    responses(
+        (status = 200, description = "Access revoked", body = serde_json::Value),
+        (status = 400, description = "Invalid UUID format", body = serde_json::Value),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 403, description = "Only the owner can revoke the access of other users", body = serde_json::Value),
+        (status = 404, description = "List or share not found", body = serde_json::Value),
+        (status = 500, description = "Internal server error", body = serde_json::Value)
+    )
+)]
+#[instrument(skip(state, user, audit))]
+pub async fn delete_todo_list_share(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<User>,
+    audit: AuditContext,
+    Path((id, user_id)): Path<(String, String)>,
+) -> Result<Json<serde_json::Value>, HandlerError> {
+    let recipient_id = Uuid::parse_str(&user_id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(json!({ "error": "Invalid UUID format." }))))?;
+
+    let list = fetch_accessible_list(&state, &id, user.id).await?;
+    if list.user_id != user.id && recipient_id != user.id {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({ "error": "Only the owner can revoke the access of other users." })),
+        ));
+    }
+
+    let removed = delete_todo_list_share_from_db(&state.database, list.id, recipient_id)
+        .await
+        .map_err(|e| db_error(list.id, e))?;
+    if removed == 0 {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": format!("List '{}' is not shared with user '{}'.", list.id, recipient_id) })),
+        ));
+    }
+
+    let event = AuditEvent::new("todo_list.unshare", Some(user.id))
+        .target("todo_list", list.id)
+        .change(Some(json!({ "user_id": recipient_id })), None);
+    record_audit_event(&state.database, &audit, event).await;
+
+    Ok(Json(json!({ "success": format!("Access of user '{}' to list '{}' revoked.", recipient_id, list.id) })))
+}
+
+// --- Helper Functions ---
+
+// Fetches the user to share with: only active users in the tenant of the owner, as a 404 otherwise
+async fn fetch_recipient(state: &AppState, body: &TodoShareBody, owner_id: Uuid) -> Result<Uuid, HandlerError> {
+    let username = body.username.trim();
+    fetch_share_recipient_from_db(&state.database, username, owner_id)
+        .await
+        .map_err(|e| {
+            error!("Error looking up user '{}' to share with: {}", username, e);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "Could not update the sharing of the todo." })))
+        })?
+        .ok_or_else(|| (
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": format!("User '{}' not found.", username) })),
+        ))
+}
+
+// Fetches a list the user owns or that is shared with the user, as a 404 otherwise
+async fn fetch_accessible_list(state: &AppState, id: &str, user_id: Uuid) -> Result<TodoList, HandlerError> {
+    let uuid = Uuid::parse_str(id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(json!({ "error": "Invalid UUID format." }))))?;
+
+    fetch_accessible_todo_list_by_id_from_db(&state.database, uuid, user_id)
+        .await
+        .map_err(|e| db_error(uuid, e))?
+        .ok_or_else(|| (
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": format!("List with ID '{}' not found.", id) })),
+        ))
+}
+
+// Fetches a todo the user owns or that is shared with the user, as a 404 otherwise
+async fn fetch_accessible_todo(state: &AppState, id: &str, user_id: Uuid) -> Result<Todo, HandlerError> {
+    let uuid = Uuid::parse_str(id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(json!({ "error": "Invalid UUID format." }))))?;
+
+    fetch_todo_by_id_from_db(&state.database, uuid, user_id)
+        .await
+        .map_err(|e| db_error(uuid, e))?
+        .ok_or_else(|| (
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": format!("Todo with ID '{}' not found.", id) })),
+        ))
+}
+
+fn db_error(id: Uuid, e: sqlx::Error) -> HandlerError {
+    error!("Error managing the shares of todo (list) {}: {}", id, e);
+    (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "Could not update the sharing of the todo." })))
+}
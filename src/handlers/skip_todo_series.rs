@@ -0,0 +1,123 @@
+use axum::{
+    extract::{State, Extension, Path},
+    Json,
+    http::StatusCode,
+};
+use tokio::task;
+use uuid::Uuid;
+use serde_json::json;
+use tracing::{debug, error, instrument};
+use std::sync::Arc;
+
+use crate::models::todo::{TodoSeries, TodoSeriesSkipBody};
+use crate::models::user::User;
+use crate::database::todos::delete_todo_from_db;
+use crate::database::todo_series::{
+    fetch_todo_occurrence_from_db, fetch_todo_series_by_id_from_db, insert_todo_series_skip_into_db,
+};
+use crate::utils::attachments::delete_attachment_objects;
+use crate::utils::recurrence::{create_next_occurrence, within_recurrence_window, RECURRENCE_WINDOW_YEARS};
+use crate::routes::AppState;
+
+// --- Route Handler ---
+
+// Skip a single occurrence of a recurring series
+#[utoipa::path(
+    post,
+    path = "/todos/series/{id}/skip",
+    tag = "todo",
+    security(
+        ("jwt_token" = [])
+    ),
+    params(
+        ("id" = String, Path, description = "Series ID")
+    ),
+    request_body = TodoSeriesSkipBody,
+    responses(
+        (status = 200, description = "Occurrence skipped, an open todo for it is replaced by the next occurrence", body = TodoSeries),
+        (status = 400, description = "Invalid UUID format, or not an occurrence of the series within the supported years", body = serde_json::Value),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 404, description = "Series not found", body = serde_json::Value),
+        (status = 409, description = "The occurrence is already completed", body = serde_json::Value),
+        (status = 500, description = "Internal server error", body = serde_json::Value)
+    )
+)]
+#[instrument(skip(state, user, body))]
+pub async fn post_todo_series_skip(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<User>,
+    Path(id): Path<String>,
+    Json(body): Json<TodoSeriesSkipBody>,
+) -> Result<Json<TodoSeries>, (StatusCode, Json<serde_json::Value>)> {
+    let uuid = Uuid::parse_str(&id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(json!({ "error": "Invalid UUID format." }))))?;
+
+    let not_found = || (
+        StatusCode::NOT_FOUND,
+        Json(json!({ "error": format!("Series with ID '{}' not found.", id) })),
+    );
+    let db_error = |e: sqlx::Error| {
+        error!("Error skipping an occurrence of todo series {}: {}", uuid, e);
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "Could not skip the occurrence." })))
+    };
+
+    let occurrence_at = body.occurrence_at;
+    if !within_recurrence_window(occurrence_at) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": format!("Only occurrences within {} years from now can be skipped.", RECURRENCE_WINDOW_YEARS) })),
+        ));
+    }
+
+    let series = fetch_todo_series_by_id_from_db(&state.database, uuid, user.id)
+        .await
+        .map_err(db_error)?
+        .ok_or_else(not_found)?;
+
+    // Expanding the rule up to the occurrence can take a while, so it stays off the async runtime
+    let is_occurrence = task::spawn_blocking(move || series.has_occurrence(occurrence_at))
+        .await
+        .map_err(|e| {
+            error!("Error expanding todo series {}: {}", uuid, e);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "Could not skip the occurrence." })))
+        })?;
+    if !is_occurrence {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": format!("'{}' is not an occurrence of this series.", occurrence_at.to_rfc3339()) })),
+        ));
+    }
+
+    let todo = fetch_todo_occurrence_from_db(&state.database, uuid, occurrence_at)
+        .await
+        .map_err(db_error)?;
+    if let Some((_, true)) = todo {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(json!({ "error": "This occurrence is already completed." })),
+        ));
+    }
+
+    insert_todo_series_skip_into_db(&state.database, uuid, occurrence_at)
+        .await
+        .map_err(db_error)?;
+
+    // An open todo for the skipped occurrence makes way for the next one, which inherits its shares
+    if let Some((todo_id, false)) = todo {
+        match create_next_occurrence(&state.database, uuid, user.id, occurrence_at, Some(todo_id)).await {
+            Ok(Some(next)) => debug!("Created occurrence {} of series {}.", next.id, uuid),
+            Ok(None) => debug!("Series {} has no next occurrence to create.", uuid),
+            Err(e) => error!("Error creating the next occurrence of series {}: {}", uuid, e),
+        }
+        let object_keys = delete_todo_from_db(&state.database, todo_id, user.id)
+            .await
+            .map_err(db_error)?;
+        delete_attachment_objects(&state.storage, &object_keys.unwrap_or_default()).await;
+    }
+
+    fetch_todo_series_by_id_from_db(&state.database, uuid, user.id)
+        .await
+        .map_err(db_error)?
+        .ok_or_else(not_found)
+        .map(Json)
+}
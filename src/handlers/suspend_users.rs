@@ -0,0 +1,156 @@
+use axum::{extract::{Extension, Path, State}, Json, http::StatusCode};
+use chrono::Utc;
+use serde_json::json;
+use tracing::{error, instrument};
+use uuid::Uuid;
+use validator::Validate;
+use std::sync::Arc;
+
+use crate::database::users::{suspend_user_in_db, reinstate_user_in_db};
+use crate::i18n::catalog::{t, t_with};
+use crate::i18n::locale::Locale;
+use crate::mail::send::send_mail;
+use crate::models::user::{User, UserSuspendBody, UserSuspension};
+use crate::models::audit::AuditEvent;
+use crate::utils::audit::{record_audit_event, AuditContext};
+use crate::utils::preferences::load_user_preferences;
+use crate::routes::AppState;
+
+// --- Route Handlers ---
+
+// Suspend a user, with a reason and an optional end date
+#[utoipa::path(
+    post,
+    path = "/users/{id}/suspend",
+    tag = "user",
+    security(
+        ("jwt_token" = [])
+    ),
+    params(
+        ("id" = String, Path, description = "User ID")
+    ),
+    request_body = UserSuspendBody,
+    responses(
+        (status = 200, description = "User suspended, its sessions and API keys have been invalidated", body = UserSuspension),
+        (status = 400, description = "Validation error or invalid UUID format", body = serde_json::Value),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 404, description = "No active or suspended user with this ID", body = serde_json::Value),
+        (status = 500, description = "Internal server error", body = serde_json::Value)
+    )
+)]
+#[instrument(skip(state, current_user, audit, body))]
+pub async fn suspend_user(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<User>,
+    audit: AuditContext,
+    Path(id): Path<String>,
+    Json(body): Json<UserSuspendBody>,
+) -> Result<Json<UserSuspension>, (StatusCode, Json<serde_json::Value>)> {
+    let uuid = Uuid::parse_str(&id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(json!({ "error": "Invalid UUID format." }))))?;
+
+    // Validate input
+    if let Err(errors) = body.validate() {
+        let error_messages: Vec<String> = errors
+            .field_errors()
+            .values()
+            .flat_map(|errors| errors.iter().map(|e| e.message.clone().unwrap_or_default().to_string()))
+            .collect();
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": error_messages.join(", ") }))
+        ));
+    }
+
+    if uuid == current_user.id {
+        return Err((StatusCode::BAD_REQUEST, Json(json!({ "error": "You can not suspend yourself." }))));
+    }
+    if body.until.is_some_and(|until| until <= Utc::now()) {
+        return Err((StatusCode::BAD_REQUEST, Json(json!({ "error": "The end of the suspension must be in the future." }))));
+    }
+
+    let suspension = match suspend_user_in_db(&state.database, uuid, body.reason.trim(), body.until, current_user.id).await {
+        Ok(Some(suspension)) => suspension,
+        Ok(None) => return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": format!("No active or suspended user with ID '{}' found.", id) }))
+        )),
+        Err(e) => {
+            error!("Error suspending user: {}", e);
+            return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "Could not suspend the user." }))));
+        }
+    };
+
+    let event = AuditEvent::new("user.suspend", Some(current_user.id))
+        .target("user", suspension.id)
+        .change(None, Some(json!({
+            "reason": suspension.suspension_reason,
+            "suspended_until": suspension.suspended_until,
+        })));
+    record_audit_event(&state.database, &audit, event).await;
+
+    let preferences = load_user_preferences(&state.database, suspension.id).await;
+    let mail_locale = suspension.locale(Locale::fallback());
+    let message = suspension.message_in_timezone(mail_locale, preferences.tz());
+    let subject = t(mail_locale, "mail.suspended.subject");
+    let body = t_with(mail_locale, "mail.suspended.body", &[("message", &message)]);
+    if let Err(e) = send_mail(&state.mail, &suspension.email, &subject, &body).await {
+        error!("Failed to send the suspension notice to user {}: {}", suspension.id, e);
+    }
+
+    Ok(Json(suspension))
+}
+
+// Reinstate a suspended user
+#[utoipa::path(
+    post,
+    path = "/users/{id}/reinstate",
+    tag = "user",
+    security(
+        ("jwt_token" = [])
+    ),
+    params(
+        ("id" = String, Path, description = "User ID")
+    ),
+    responses(
+        (status = 200, description = "User reinstated", body = serde_json::Value),
+        (status = 400, description = "Invalid UUID format", body = serde_json::Value),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 404, description = "No suspended user with this ID", body = serde_json::Value),
+        (status = 500, description = "Internal server error", body = serde_json::Value)
+    )
+)]
+#[instrument(skip(state, current_user, audit))]
+pub async fn reinstate_user(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<User>,
+    audit: AuditContext,
+    Path(id): Path<String>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    let uuid = Uuid::parse_str(&id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(json!({ "error": "Invalid UUID format." }))))?;
+
+    let user = match reinstate_user_in_db(&state.database, uuid).await {
+        Ok(Some(user)) => user,
+        Ok(None) => return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": format!("No suspended user with ID '{}' found.", id) }))
+        )),
+        Err(e) => {
+            error!("Error reinstating user: {}", e);
+            return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "Could not reinstate the user." }))));
+        }
+    };
+
+    let event = AuditEvent::new("user.reinstate", Some(current_user.id)).target("user", user.id);
+    record_audit_event(&state.database, &audit, event).await;
+
+    let mail_locale = Locale::for_user(user.language_code.as_deref(), Locale::fallback());
+    let subject = t(mail_locale, "mail.reinstated.subject");
+    let body = t(mail_locale, "mail.reinstated.body");
+    if let Err(e) = send_mail(&state.mail, &user.email, &subject, &body).await {
+        error!("Failed to send the reinstatement notice to user {}: {}", user.id, e);
+    }
+
+    Ok(Json(json!({ "success": format!("User with ID '{}' reinstated.", id) })))
+}
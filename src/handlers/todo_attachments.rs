@@ -0,0 +1,339 @@
+use axum::{
+    extract::{Multipart, State, Extension, Path},
+    Json,
+    http::StatusCode,
+};
+use chrono::Utc;
+use uuid::Uuid;
+use serde_json::json;
+use tracing::{error, instrument};
+use std::sync::Arc;
+
+use crate::core::config::get_env_u64;
+use crate::models::todo::{Todo, TodoAttachment, TodoAttachmentResponse, TodoAttachmentUploadBody, TodoPermission};
+use crate::models::user::User;
+use crate::database::todos::fetch_todo_by_id_from_db;
+use crate::database::todo_shares::fetch_todo_permission_from_db;
+use crate::database::todo_attachments::{
+    delete_todo_attachment_from_db, fetch_attachment_quota_mb_from_db, fetch_attachment_storage_used_from_db,
+    fetch_todo_attachment_from_db, fetch_todo_attachments_from_db, insert_todo_attachment_into_db,
+};
+use crate::storage::upload::upload_to_storage;
+use crate::storage::delete::delete_from_storage;
+use crate::storage::presign_url::generate_presigned_url;
+use crate::utils::attachments::{
+    attachment_bucket, attachment_quota, delete_attachment_objects, detect_attachment_type, is_allowed_content_type,
+    max_attachment_size, normalize_content_type, sanitize_file_name,
+};
+use crate::routes::AppState;
+
+type HandlerError = (StatusCode, Json<serde_json::Value>);
+
+// --- Route Handlers ---
+
+// Attach a file to a todo
+#[utoipa::path(
+    post,
+    path = "/todos/{id}/attachments",
+    tag = "todo",
+    security(
+        ("jwt_token" = [])
+    ),
+    params(
+        ("id" = String, Path, description = "Todo ID")
+    ),
+    request_body(content = TodoAttachmentUploadBody, content_type = "multipart/form-data"),
+    responses(
+        (status = 200, description = "File attached successfully", body = TodoAttachment),
+        (status = 400, description = "Invalid UUID format, or no (empty) file uploaded", body = serde_json::Value),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 403, description = "The todo is shared with you as viewer", body = serde_json::Value),
+        (status = 404, description = "Todo not found", body = serde_json::Value),
+        (status = 413, description = "The file is too large, or does not fit in the storage quota of the owner", body = serde_json::Value),
+        (status = 415, description = "This type of file cannot be attached", body = serde_json::Value),
+        (status = 500, description = "Internal server error", body = serde_json::Value)
+    )
+)]
+#[instrument(skip(state, user, multipart))]
+pub async fn post_todo_attachment(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<User>,
+    Path(id): Path<String>,
+    mut multipart: Multipart,
+) -> Result<Json<TodoAttachment>, HandlerError> {
+    let todo = fetch_editable_todo(&state, &id, user.id).await?;
+    let max_size = max_attachment_size();
+
+    let mut upload = None;
+    while let Some(field) = multipart.next_field().await.map_err(|e| {
+        (e.status(), Json(json!({ "error": format!("Invalid upload: {}", e.body_text()) })))
+    })? {
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        let content_type = normalize_content_type(field.content_type().unwrap_or(""));
+        if !is_allowed_content_type(&content_type) {
+            return Err((
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                Json(json!({ "error": format!("Files of type '{}' cannot be attached.", content_type) })),
+            ));
+        }
+        let file_name = sanitize_file_name(field.file_name().unwrap_or(""));
+
+        let data = field.bytes().await.map_err(|e| {
+            (e.status(), Json(json!({ "error": format!("Could not read the file: {}", e.body_text()) })))
+        })?;
+        upload = Some((file_name, content_type, data));
+        break;
+    }
+
+    let Some((file_name, content_type, data)) = upload.filter(|(_, _, data)| !data.is_empty()) else {
+        return Err((StatusCode::BAD_REQUEST, Json(json!({ "error": "No file uploaded." }))));
+    };
+    if data.len() > max_size {
+        return Err((
+            StatusCode::PAYLOAD_TOO_LARGE,
+            Json(json!({ "error": format!("Files can be at most {} MB.", max_size / 1024 / 1024) })),
+        ));
+    }
+
+    // The declared type is only a claim of the client, the contents decide
+    let Some(content_type) = detect_attachment_type(&content_type, &data) else {
+        return Err((
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            Json(json!({ "error": format!("The contents of the file do not match a type that can be attached ('{}').", content_type) })),
+        ));
+    };
+
+    // Files count towards the quota of the owner, also when an editor uploads them
+    let tier_quota_mb = fetch_attachment_quota_mb_from_db(&state.database, todo.user_id)
+        .await
+        .map_err(|e| db_error(todo.id, e))?;
+    let quota = attachment_quota(tier_quota_mb);
+    let quota_exceeded = |used: i64| (
+        StatusCode::PAYLOAD_TOO_LARGE,
+        Json(json!({ "error": format!(
+            "This file does not fit in the storage quota of the owner ({} of {} MB used).",
+            used / 1024 / 1024,
+            quota / 1024 / 1024
+        ) })),
+    );
+    let used = fetch_attachment_storage_used_from_db(&state.database, todo.user_id)
+        .await
+        .map_err(|e| db_error(todo.id, e))?;
+    if used + data.len() as i64 > quota {
+        return Err(quota_exceeded(used));
+    }
+
+    let attachment_id = Uuid::new_v4();
+    let attachment = TodoAttachment {
+        id: attachment_id,
+        todo_id: todo.id,
+        user_id: todo.user_id,
+        uploaded_by: Some(user.id),
+        file_name,
+        content_type,
+        size: data.len() as i64,
+        object_key: format!("todo_attachments/{}/{}", todo.id, attachment_id),
+        created_at: Utc::now(),
+    };
+
+    let bucket = attachment_bucket();
+    if let Err(e) = upload_to_storage(&state.storage, &bucket, &attachment.object_key, &data).await {
+        error!("Error uploading an attachment of todo {}: {}", todo.id, e);
+        return Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "error": "Could not upload the file." })),
+        ));
+    }
+
+    // The quota is checked again while storing, parallel uploads may have taken the space meanwhile
+    let stored = insert_todo_attachment_into_db(&state.database, &attachment, quota).await;
+    if !matches!(stored, Ok(true)) {
+        if let Err(e) = delete_from_storage(&state.storage, &bucket, &attachment.object_key).await {
+            error!("Error removing the rejected attachment {}: {}", attachment.object_key, e);
+        }
+    }
+
+    match stored {
+        Ok(true) => Ok(Json(attachment)),
+        Ok(false) => {
+            let used = fetch_attachment_storage_used_from_db(&state.database, todo.user_id)
+                .await
+                .map_err(|e| db_error(todo.id, e))?;
+            Err(quota_exceeded(used))
+        }
+        Err(e) => Err(db_error(todo.id, e)),
+    }
+}
+
+// List the files attached to a todo
+#[utoipa::path(
+    get,
+    path = "/todos/{id}/attachments",
+    tag = "todo",
+    security(
+        ("jwt_token" = [])
+    ),
+    params(
+        ("id" = String, Path, description = "Todo ID")
+    ),
+    responses(
+        (status = 200, description = "The attachments of the todo, oldest first", body = [TodoAttachment]),
+        (status = 400, description = "Invalid UUID format", body = serde_json::Value),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 404, description = "Todo not found", body = serde_json::Value),
+        (status = 500, description = "Internal server error", body = serde_json::Value)
+    )
+)]
+#[instrument(skip(state, user))]
+pub async fn get_todo_attachments(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<User>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<TodoAttachment>>, HandlerError> {
+    let todo = fetch_accessible_todo(&state, &id, user.id).await?;
+
+    fetch_todo_attachments_from_db(&state.database, todo.id)
+        .await
+        .map(Json)
+        .map_err(|e| db_error(todo.id, e))
+}
+
+// Get a file attached to a todo, with a temporary download link
+#[utoipa::path(
+    get,
+    path = "/todos/{id}/attachments/{attachment_id}",
+    tag = "todo",
+    security(
+        ("jwt_token" = [])
+    ),
+    params(
+        ("id" = String, Path, description = "Todo ID"),
+        ("attachment_id" = String, Path, description = "Attachment ID")
+    ),
+    responses(
+        (status = 200, description = "The attachment with a temporary download link", body = TodoAttachmentResponse),
+        (status = 400, description = "Invalid UUID format", body = serde_json::Value),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 404, description = "Todo or attachment not found", body = serde_json::Value),
+        (status = 500, description = "Internal server error", body = serde_json::Value)
+    )
+)]
+#[instrument(skip(state, user))]
+pub async fn get_todo_attachment(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<User>,
+    Path((id, attachment_id)): Path<(String, String)>,
+) -> Result<Json<TodoAttachmentResponse>, HandlerError> {
+    let attachment_uuid = Uuid::parse_str(&attachment_id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(json!({ "error": "Invalid UUID format." }))))?;
+
+    let todo = fetch_accessible_todo(&state, &id, user.id).await?;
+    let attachment = fetch_todo_attachment_from_db(&state.database, attachment_uuid, todo.id)
+        .await
+        .map_err(|e| db_error(todo.id, e))?
+        .ok_or_else(|| attachment_not_found(&attachment_id))?;
+
+    // Every request gets a fresh link, as the previous one may have expired
+    let expiry = get_env_u64("TODO_ATTACHMENT_URL_EXPIRY_SECONDS", 900).min(604800);
+    let download_url = generate_presigned_url(&state.storage, &attachment_bucket(), &attachment.object_key, expiry)
+        .await
+        .map_err(|e| {
+            error!("Error generating the download link of attachment {}: {}", attachment.id, e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Could not generate the download link." })),
+            )
+        })?;
+
+    Ok(Json(TodoAttachmentResponse { attachment, download_url }))
+}
+
+// Remove a file from a todo
+#[utoipa::path(
+    delete,
+    path = "/todos/{id}/attachments/{attachment_id}",
+    tag = "todo",
+    security(
+        ("jwt_token" = [])
+    ),
+    params(
+        ("id" = String, Path, description = "Todo ID"),
+        ("attachment_id" = String, Path, description = "Attachment ID")
+    ),
+    responses(
+        (status = 200, description = "Attachment removed", body = serde_json::Value),
+        (status = 400, description = "Invalid UUID format", body = serde_json::Value),
+        (status = 401, description = "Unauthorized", body = serde_json::Value),
+        (status = 403, description = "The todo is shared with you as viewer", body = serde_json::Value),
+        (status = 404, description = "Todo or attachment not found", body = serde_json::Value),
+        (status = 500, description = "Internal server error", body = serde_json::Value)
+    )
+)]
+#[instrument(skip(state, user))]
+pub async fn delete_todo_attachment(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<User>,
+    Path((id, attachment_id)): Path<(String, String)>,
+) -> Result<Json<serde_json::Value>, HandlerError> {
+    let attachment_uuid = Uuid::parse_str(&attachment_id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(json!({ "error": "Invalid UUID format." }))))?;
+
+    let todo = fetch_editable_todo(&state, &id, user.id).await?;
+    let object_key = delete_todo_attachment_from_db(&state.database, attachment_uuid, todo.id)
+        .await
+        .map_err(|e| db_error(todo.id, e))?
+        .ok_or_else(|| attachment_not_found(&attachment_id))?;
+
+    delete_attachment_objects(&state.storage, &[object_key]).await;
+
+    Ok(Json(json!({ "success": format!("Attachment with ID '{}' removed.", attachment_id) })))
+}
+
+// Fetches a todo the user owns or that is shared with the user, as a 404 otherwise
+async fn fetch_accessible_todo(state: &AppState, id: &str, user_id: Uuid) -> Result<Todo, HandlerError> {
+    let uuid = Uuid::parse_str(id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, Json(json!({ "error": "Invalid UUID format." }))))?;
+
+    fetch_todo_by_id_from_db(&state.database, uuid, user_id)
+        .await
+        .map_err(|e| db_error(uuid, e))?
+        .ok_or_else(|| (
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": format!("Todo with ID '{}' not found.", id) })),
+        ))
+}
+
+// Fetches a todo the user owns or that is shared with the user (or whose list is) as editor
+async fn fetch_editable_todo(state: &AppState, id: &str, user_id: Uuid) -> Result<Todo, HandlerError> {
+    let todo = fetch_accessible_todo(state, id, user_id).await?;
+    if todo.user_id == user_id {
+        return Ok(todo);
+    }
+
+    let permission = fetch_todo_permission_from_db(&state.database, todo.id, user_id)
+        .await
+        .map_err(|e| db_error(todo.id, e))?;
+    if permission.as_deref() != Some(TodoPermission::Editor.as_str()) {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({ "error": "This todo is shared with you as viewer." })),
+        ));
+    }
+
+    Ok(todo)
+}
+
+fn attachment_not_found(attachment_id: &str) -> HandlerError {
+    (
+        StatusCode::NOT_FOUND,
+        Json(json!({ "error": format!("Attachment with ID '{}' not found.", attachment_id) })),
+    )
+}
+
+fn db_error(todo_id: Uuid, e: sqlx::Error) -> HandlerError {
+    error!("Error managing the attachments of todo {}: {}", todo_id, e);
+    (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "Could not update the attachments of the todo." })))
+}
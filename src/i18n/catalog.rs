@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use validator::ValidationErrors;
+
+use crate::i18n::locale::Locale;
+
+static CATALOGS: OnceLock<HashMap<Locale, HashMap<String, String>>> = OnceLock::new();
+
+// The message catalogs, embedded in the binary and parsed once
+fn catalogs() -> &'static HashMap<Locale, HashMap<String, String>> {
+    CATALOGS.get_or_init(|| {
+        Locale::ALL
+            .into_iter()
+            .map(|locale| {
+                let source = match locale {
+                    Locale::En => include_str!("locales/en.json"),
+                    Locale::Nl => include_str!("locales/nl.json"),
+                };
+                let messages = serde_json::from_str(source)
+                    .unwrap_or_else(|e| panic!("Invalid message catalog '{}': {}", locale.code(), e));
+                (locale, messages)
+            })
+            .collect()
+    })
+}
+
+// Looks up a message, falling back to English
+fn lookup(locale: Locale, key: &str) -> Option<&'static str> {
+    let catalogs = catalogs();
+    catalogs
+        .get(&locale)
+        .and_then(|messages| messages.get(key))
+        .or_else(|| catalogs.get(&Locale::En).and_then(|messages| messages.get(key)))
+        .map(String::as_str)
+}
+
+/// Translates a message, returning the key itself when no catalog has it.
+pub fn t(locale: Locale, key: &str) -> String {
+    lookup(locale, key).unwrap_or(key).to_string()
+}
+
+/// Translates a message and fills in its `{name}` placeholders.
+pub fn t_with(locale: Locale, key: &str, args: &[(&str, &str)]) -> String {
+    args.iter().fold(t(locale, key), |message, (name, value)| {
+        message.replace(&format!("{{{}}}", name), value)
+    })
+}
+
+/// Translates validation errors into a single message, one sentence per failed rule.
+///
+/// Rules without a `validation.{code}` message use their own message, or their code.
+pub fn validation_message(locale: Locale, errors: &ValidationErrors) -> String {
+    let mut fields: Vec<_> = errors.field_errors().into_iter().collect();
+    fields.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    fields
+        .into_iter()
+        .flat_map(|(field, errors)| {
+            errors.iter().map(move |error| {
+                if lookup(locale, &format!("validation.{}", error.code)).is_some() {
+                    t_with(locale, &format!("validation.{}", error.code), &[("field", &field)])
+                } else {
+                    let message = error.message.as_deref().unwrap_or(&error.code);
+                    format!("{}: {}", field, message)
+                }
+            })
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+    use validator::ValidationError;
+
+    #[test]
+    fn catalogs_have_the_same_messages() {
+        let keys = |locale: Locale| catalogs()[&locale].keys().cloned().collect::<BTreeSet<String>>();
+        for locale in Locale::ALL {
+            assert_eq!(keys(locale), keys(Locale::En), "catalog '{}' differs from 'en'", locale.code());
+        }
+    }
+
+    #[test]
+    fn translates_with_placeholders() {
+        assert_eq!(t(Locale::Nl, "register.invalid_code"), "Ongeldige code.");
+        assert_eq!(t_with(Locale::En, "picture.too_large", &[("max", "10")]), "File too large (max 10 MB).");
+        assert_eq!(t(Locale::Nl, "unknown.key"), "unknown.key");
+    }
+
+    #[test]
+    fn translates_validation_errors() {
+        let mut errors = ValidationErrors::new();
+        errors.add("username", ValidationError::new("length"));
+        errors.add("email", ValidationError::new("email"));
+        errors.add("birthday", ValidationError::new("Birthday cannot be in the future."));
+
+        assert_eq!(
+            validation_message(Locale::Nl, &errors),
+            "birthday: Birthday cannot be in the future. email: ongeldig e-mailadres. username: ongeldige lengte."
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use axum::{
+    extract::FromRequestParts,
+    http::{header, request::Parts, HeaderMap},
+};
+use std::convert::Infallible;
+
+use crate::core::config::get_env_with_default;
+use crate::models::user::User;
+
+/// A language the API messages and emails are available in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Locale {
+    #[default]
+    En,
+    Nl,
+}
+
+impl Locale {
+    /// All supported locales.
+    pub const ALL: [Locale; 2] = [Locale::En, Locale::Nl];
+
+    /// The ISO 639-1 code of the locale.
+    pub fn code(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Nl => "nl",
+        }
+    }
+
+    /// Matches a language tag such as `nl`, `nl-NL` or `nl_BE` on its primary language.
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let primary = tag.trim().split(['-', '_']).next()?.to_lowercase();
+        Locale::ALL.into_iter().find(|locale| locale.code() == primary)
+    }
+
+    /// Picks the supported locale the client prefers most from an `Accept-Language` header.
+    pub fn negotiate(accept_language: &str) -> Option<Self> {
+        let mut best: Option<(Locale, f32)> = None;
+
+        for entry in accept_language.split(',') {
+            let mut parts = entry.split(';');
+            let tag = parts.next().unwrap_or("");
+            let weight = parts
+                .find_map(|param| param.trim().strip_prefix("q="))
+                .map(|q| q.trim().parse::<f32>().unwrap_or(0.0))
+                .unwrap_or(1.0);
+
+            // Earlier entries win on equal weights
+            if let Some(locale) = Locale::from_tag(tag) {
+                if weight > 0.0 && best.is_none_or(|(_, best_weight)| weight > best_weight) {
+                    best = Some((locale, weight));
+                }
+            }
+        }
+
+        best.map(|(locale, _)| locale)
+    }
+
+    /// The locale used when neither the user nor the client has a supported preference,
+    /// from `DEFAULT_LOCALE` (default: "en").
+    pub fn fallback() -> Self {
+        Locale::from_tag(&get_env_with_default("DEFAULT_LOCALE", "en")).unwrap_or_default()
+    }
+
+    /// The locale of a user: its language code when supported, otherwise the given locale.
+    pub fn for_user(language_code: Option<&str>, otherwise: Locale) -> Self {
+        language_code.and_then(Locale::from_tag).unwrap_or(otherwise)
+    }
+
+    /// The locale the client prefers in its `Accept-Language` header, if supported.
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        headers
+            .get(header::ACCEPT_LANGUAGE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(Locale::negotiate)
+    }
+}
+
+/// Resolves the locale of a request from the language code of the signed in user,
+/// then the `Accept-Language` header, then `DEFAULT_LOCALE`.
+impl<S: Send + Sync> FromRequestParts<S> for Locale {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let from_user = parts
+            .extensions
+            .get::<User>()
+            .and_then(|user| user.language_code.as_deref())
+            .and_then(Locale::from_tag);
+
+        Ok(from_user.or_else(|| Locale::from_headers(&parts.headers)).unwrap_or_else(Locale::fallback))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_language_tags() {
+        assert_eq!(Locale::from_tag("nl"), Some(Locale::Nl));
+        assert_eq!(Locale::from_tag("nl-BE"), Some(Locale::Nl));
+        assert_eq!(Locale::from_tag("EN_gb"), Some(Locale::En));
+        assert_eq!(Locale::from_tag("de-DE"), None);
+        assert_eq!(Locale::for_user(Some("fr-FR"), Locale::Nl), Locale::Nl);
+    }
+
+    #[test]
+    fn negotiates_accept_language() {
+        assert_eq!(Locale::negotiate("nl-NL,nl;q=0.9,en;q=0.8"), Some(Locale::Nl));
+        assert_eq!(Locale::negotiate("de-DE, en;q=0.5, nl;q=0.7"), Some(Locale::Nl));
+        assert_eq!(Locale::negotiate("en, nl"), Some(Locale::En));
+        assert_eq!(Locale::negotiate("nl;q=0, fr"), None);
+        assert_eq!(Locale::negotiate("*"), None);
+    }
+}
@@ -0,0 +1,3 @@
+// Module declarations
+pub mod catalog;
+pub mod locale;
@@ -0,0 +1,60 @@
+use sqlx::PgPool;
+use std::time::Duration;
+use chrono::Utc;
+use tokio::time::interval;
+use tracing::{debug, error, info, instrument};
+
+use crate::core::config::{get_env_bool, get_env_u64};
+use crate::database::billing::generate_billing_statements_in_db;
+use crate::database::usage::fetch_usage_rolled_up_until_from_db;
+use crate::utils::quota::quota_period;
+
+/// Starts the background task that generates the statements of the previous month.
+///
+/// Statements are generated as soon as the usage of the whole month has been rolled up.
+/// Generating is idempotent, so every run (on every instance) only fills in missing statements.
+///
+/// # Configuration
+/// - `BILLING_ENABLED`: Generate statements automatically (default: true)
+/// - `BILLING_INTERVAL_SECONDS`: Seconds between two runs (default: 3600)
+pub fn start_billing_statements(pool: PgPool) {
+    if !get_env_bool("BILLING_ENABLED", true) {
+        return;
+    }
+
+    let interval_secs = get_env_u64("BILLING_INTERVAL_SECONDS", 3600).max(1);
+
+    tokio::spawn(async move {
+        let mut interval = interval(Duration::from_secs(interval_secs));
+        loop {
+            interval.tick().await;
+            generate_previous_month(&pool).await;
+        }
+    });
+}
+
+// Generates the missing statements of the previous month
+#[instrument(skip(pool))]
+async fn generate_previous_month(pool: &PgPool) {
+    // The current period starts where the previous one ends
+    let (period_end, _) = quota_period(Utc::now().date_naive());
+    let (period_start, _) = quota_period(period_end.pred_opt().expect("The day before the first of a month is always valid."));
+
+    match fetch_usage_rolled_up_until_from_db(pool).await {
+        Ok(until) if until.date_naive() >= period_end => {}
+        Ok(_) => {
+            debug!("Usage of {} has not been rolled up completely yet.", period_start);
+            return;
+        }
+        Err(e) => {
+            error!("Error fetching the usage rollup state: {}", e);
+            return;
+        }
+    }
+
+    match generate_billing_statements_in_db(pool, period_start, period_end, None).await {
+        Ok(0) => {}
+        Ok(count) => info!("Generated {} statements for {}.", count, period_start.format("%Y-%m")),
+        Err(e) => error!("Error generating statements for {}: {}", period_start, e),
+    }
+}
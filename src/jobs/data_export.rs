@@ -0,0 +1,185 @@
+use sqlx::PgPool;
+use std::time::Duration;
+use base64::Engine;
+use chrono::Utc;
+use tokio::time::interval;
+use tracing::{debug, error, info, instrument};
+
+use crate::core::config::{get_env_u64, get_env_with_default};
+use crate::database::apikeys::fetch_all_apikeys_from_db;
+use crate::database::billing::fetch_billing_statements_by_user_from_db;
+use crate::database::data_exports::{
+    claim_pending_data_export_in_db, complete_data_export_in_db, expire_data_export_in_db,
+    fail_data_export_in_db, fetch_expired_data_exports_from_db,
+};
+use crate::database::preferences::fetch_user_preferences_from_db;
+use crate::database::todo_lists::fetch_todo_lists_from_db;
+use crate::database::todo_series::fetch_todo_series_from_db;
+use crate::database::todo_attachments::fetch_todo_attachments_by_user_from_db;
+use crate::database::todos::fetch_all_todos_from_db;
+use crate::database::usage::fetch_usage_export_from_db;
+use crate::database::users::fetch_user_by_field_from_db;
+use crate::mail::MailerState;
+use crate::i18n::catalog::{t, t_with};
+use crate::i18n::locale::Locale;
+use crate::mail::send::send_mail;
+use crate::models::data_export::{DataExport, DataExportBundle, DataExportFile};
+use crate::storage::StorageState;
+use crate::storage::delete::delete_from_storage;
+use crate::storage::download::{download_from_storage, split_storage_url};
+use crate::storage::presign_url::generate_presigned_url;
+use crate::utils::preferences::resolve_preferences;
+use crate::storage::upload::upload_to_storage;
+
+/// Starts the background task that builds the requested personal data exports.
+///
+/// Every export is bundled into a single JSON file, uploaded to the data exports bucket,
+/// and the user receives a temporary download link by email. Bundles are deleted again
+/// once their retention period has passed.
+///
+/// # Configuration
+/// - `STORAGE_BUCKET_DATA_EXPORTS`: Bucket the bundles are stored in (default: data-exports)
+/// - `DATA_EXPORT_INTERVAL_SECONDS`: Seconds between two runs (default: 30)
+/// - `DATA_EXPORT_URL_EXPIRY_SECONDS`: Lifetime of a download link (default: 86400, at most 604800)
+/// - `DATA_EXPORT_RETENTION_DAYS`: Days a bundle is kept (default: 7)
+pub fn start_data_exports(pool: PgPool, storage: StorageState, mail: MailerState) {
+    let interval_secs = get_env_u64("DATA_EXPORT_INTERVAL_SECONDS", 30).max(1);
+
+    tokio::spawn(async move {
+        let mut interval = interval(Duration::from_secs(interval_secs));
+        loop {
+            interval.tick().await;
+
+            // Exports are claimed one by one, so multiple instances can share the work
+            loop {
+                match claim_pending_data_export_in_db(&pool).await {
+                    Ok(Some(export)) => process_data_export(&pool, &storage, &mail, export).await,
+                    Ok(None) => break,
+                    Err(e) => {
+                        error!("Error claiming a data export: {}", e);
+                        break;
+                    }
+                }
+            }
+
+            remove_expired_data_exports(&pool, &storage).await;
+        }
+    });
+}
+
+// Builds, uploads and mails a single export
+#[instrument(skip(pool, storage, mail, export), fields(export_id = %export.id))]
+async fn process_data_export(pool: &PgPool, storage: &StorageState, mail: &MailerState, export: DataExport) {
+    let bucket = get_env_with_default("STORAGE_BUCKET_DATA_EXPORTS", "data-exports");
+    let object_key = format!("{}/{}.json", export.user_id, export.id);
+
+    let result = async {
+        let (bundle, email, locale) = build_bundle(pool, storage, &export).await?;
+        let json = serde_json::to_vec_pretty(&bundle).map_err(|e| format!("Failed to serialize the bundle: {}", e))?;
+        upload_to_storage(storage, &bucket, &object_key, &json).await?;
+
+        let expiry = get_env_u64("DATA_EXPORT_URL_EXPIRY_SECONDS", 86400).min(604800);
+        let url = generate_presigned_url(storage, &bucket, &object_key, expiry).await?;
+        Ok::<_, String>((email, locale, url, expiry))
+    }.await;
+
+    let (email, locale, url, expiry) = match result {
+        Ok(result) => result,
+        Err(e) => {
+            error!("Failed to build data export {}: {}", export.id, e);
+            if let Err(e) = fail_data_export_in_db(pool, export.id, &e).await {
+                error!("Failed to mark data export {} as failed: {}", export.id, e);
+            }
+            return;
+        }
+    };
+
+    let retention = chrono::Duration::days(get_env_u64("DATA_EXPORT_RETENTION_DAYS", 7) as i64);
+    if let Err(e) = complete_data_export_in_db(pool, export.id, &object_key, Utc::now() + retention).await {
+        error!("Failed to mark data export {} as completed: {}", export.id, e);
+        return;
+    }
+
+    let subject = t(locale, "mail.data_export.subject");
+    let body = t_with(locale, "mail.data_export.body", &[("url", &url), ("hours", &(expiry / 3600).to_string())]);
+
+    // The export can always be fetched through the API, so a failed mail is not retried
+    if let Err(e) = send_mail(mail, &email, &subject, &body).await {
+        error!("Failed to send the data export mail to user {}: {}", export.user_id, e);
+    }
+
+    info!("Completed data export {} of user {}.", export.id, export.user_id);
+}
+
+// Gathers all personal data of the user, returning the bundle and the address and language to mail it in
+async fn build_bundle(pool: &PgPool, storage: &StorageState, export: &DataExport) -> Result<(DataExportBundle, String, Locale), String> {
+    let db_error = |e: sqlx::Error| format!("Database error: {}", e);
+
+    let profile = fetch_user_by_field_from_db(pool, "id", &export.user_id.to_string())
+        .await
+        .map_err(db_error)?
+        .ok_or_else(|| "The user no longer exists.".to_string())?;
+
+    let profile_picture = match profile.profile_picture_url.as_deref() {
+        Some(stored_url) => match split_storage_url(storage, stored_url) {
+            Some((bucket, object_key)) => {
+                let data = download_from_storage(storage, bucket, object_key).await?;
+                Some(DataExportFile {
+                    url: stored_url.to_string(),
+                    base64: base64::engine::general_purpose::STANDARD.encode(data),
+                })
+            }
+            None => None,
+        },
+        None => None,
+    };
+
+    let email = profile.email.clone();
+    let locale = Locale::for_user(profile.language_code.as_deref(), Locale::fallback());
+    let bundle = DataExportBundle {
+        generated_at: Utc::now(),
+        preferences: fetch_user_preferences_from_db(pool, export.user_id)
+            .await
+            .map_err(db_error)?
+            .map(|stored| resolve_preferences(&stored))
+            .unwrap_or_default(),
+        todos: fetch_all_todos_from_db(pool, export.user_id).await.map_err(db_error)?,
+        todo_lists: fetch_todo_lists_from_db(pool, export.user_id).await.map_err(db_error)?,
+        todo_series: fetch_todo_series_from_db(pool, export.user_id).await.map_err(db_error)?,
+        todo_attachments: fetch_todo_attachments_by_user_from_db(pool, export.user_id).await.map_err(db_error)?,
+        api_keys: fetch_all_apikeys_from_db(pool, export.user_id).await.map_err(db_error)?,
+        usage: fetch_usage_export_from_db(pool, export.user_id).await.map_err(db_error)?,
+        billing_statements: fetch_billing_statements_by_user_from_db(pool, export.user_id).await.map_err(db_error)?,
+        profile,
+        profile_picture,
+    };
+
+    Ok((bundle, email, locale))
+}
+
+// Deletes the bundles that passed their retention period
+#[instrument(skip(pool, storage))]
+async fn remove_expired_data_exports(pool: &PgPool, storage: &StorageState) {
+    let exports = match fetch_expired_data_exports_from_db(pool).await {
+        Ok(exports) => exports,
+        Err(e) => {
+            error!("Error fetching expired data exports: {}", e);
+            return;
+        }
+    };
+
+    let bucket = get_env_with_default("STORAGE_BUCKET_DATA_EXPORTS", "data-exports");
+    for export in exports {
+        if let Some(object_key) = export.object_key.as_deref() {
+            if let Err(e) = delete_from_storage(storage, &bucket, object_key).await {
+                error!("Failed to delete the bundle of data export {}: {}", export.id, e);
+                continue;
+            }
+        }
+
+        match expire_data_export_in_db(pool, export.id).await {
+            Ok(()) => debug!("Expired data export {}.", export.id),
+            Err(e) => error!("Failed to mark data export {} as expired: {}", export.id, e),
+        }
+    }
+}
@@ -0,0 +1,9 @@
+// Module declarations
+pub mod usage_rollup;
+pub mod usage_quota;
+pub mod billing;
+pub mod data_export;
+pub mod user_purge;
+pub mod suspension_expiry;
+pub mod pending_user_cleanup;
+pub mod todo_reminders;
@@ -0,0 +1,30 @@
+use sqlx::PgPool;
+use std::time::Duration;
+use tokio::time::interval;
+use tracing::{error, info};
+
+use crate::core::config::get_env_u64;
+use crate::database::users::delete_expired_pending_users_from_db;
+
+/// Starts the background task that deletes registrations that were never verified.
+///
+/// Once the verification code of a pending user has expired, the user is deleted, so that
+/// its username and email address can be registered again.
+///
+/// # Configuration
+/// - `PENDING_USER_CLEANUP_INTERVAL_SECONDS`: Seconds between two runs (default: 3600)
+pub fn start_pending_user_cleanup(pool: PgPool) {
+    let interval_secs = get_env_u64("PENDING_USER_CLEANUP_INTERVAL_SECONDS", 3600).max(1);
+
+    tokio::spawn(async move {
+        let mut interval = interval(Duration::from_secs(interval_secs));
+        loop {
+            interval.tick().await;
+            match delete_expired_pending_users_from_db(&pool).await {
+                Ok(0) => {}
+                Ok(count) => info!("Deleted {} unverified registration(s).", count),
+                Err(e) => error!("Error deleting unverified registrations: {}", e),
+            }
+        }
+    });
+}
@@ -0,0 +1,53 @@
+use sqlx::PgPool;
+use std::time::Duration;
+use tokio::time::interval;
+use tracing::{error, info, instrument};
+
+use crate::core::config::get_env_u64;
+use crate::database::users::reinstate_expired_suspensions_in_db;
+use crate::i18n::catalog::t;
+use crate::i18n::locale::Locale;
+use crate::mail::MailerState;
+use crate::mail::send::send_mail;
+use crate::models::audit::AuditEvent;
+use crate::utils::audit::{record_audit_event, AuditContext};
+
+/// Starts the background task that reinstates users whose suspension has ended.
+///
+/// # Configuration
+/// - `USER_SUSPENSION_CHECK_INTERVAL_SECONDS`: Seconds between two runs (default: 60)
+pub fn start_suspension_expiry(pool: PgPool, mail: MailerState) {
+    let interval_secs = get_env_u64("USER_SUSPENSION_CHECK_INTERVAL_SECONDS", 60).max(1);
+
+    tokio::spawn(async move {
+        let mut interval = interval(Duration::from_secs(interval_secs));
+        loop {
+            interval.tick().await;
+            reinstate_expired_suspensions(&pool, &mail).await;
+        }
+    });
+}
+
+// Reinstates the users and lets them know
+#[instrument(skip(pool, mail))]
+async fn reinstate_expired_suspensions(pool: &PgPool, mail: &MailerState) {
+    let users = match reinstate_expired_suspensions_in_db(pool).await {
+        Ok(users) => users,
+        Err(e) => {
+            error!("Error reinstating users with an ended suspension: {}", e);
+            return;
+        }
+    };
+
+    for user in users {
+        info!("The suspension of user {} has ended.", user.id);
+        let event = AuditEvent::new("user.reinstate", None).target("user", user.id);
+        record_audit_event(pool, &AuditContext::default(), event).await;
+        let locale = Locale::for_user(user.language_code.as_deref(), Locale::fallback());
+        let subject = t(locale, "mail.reinstated.subject");
+        let body = t(locale, "mail.reinstated.body");
+        if let Err(e) = send_mail(mail, &user.email, &subject, &body).await {
+            error!("Failed to send the reinstatement notice to user {}: {}", user.id, e);
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use sqlx::PgPool;
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use tokio::time::interval;
+use tracing::{debug, error, instrument};
+
+use crate::core::config::get_env_u64;
+use crate::database::todo_reminders::{
+    claim_todo_reminders_in_db, complete_todo_reminders_in_db, fetch_upcoming_todo_reminders_from_db,
+    release_todo_reminders_in_db,
+};
+use crate::i18n::catalog::t_with;
+use crate::i18n::locale::Locale;
+use crate::mail::MailerState;
+use crate::mail::send::send_mail;
+use crate::models::preference::UserPreferences;
+use crate::models::todo::TodoReminderRow;
+use crate::utils::preferences::resolve_preferences;
+use crate::utils::reminders::{due_lead_minutes, MAX_REMINDER_LEAD_MINUTES};
+
+/// Starts the background task that emails users about their todos before the due date.
+///
+/// Every reminder is claimed in the database before it is sent, so multiple instances can run
+/// this task and a restart does not send a reminder again. A claim that was not sent within
+/// `CLAIM_TIMEOUT_MINUTES` is taken over, so a crash between claiming and sending does not lose it.
+/// Users choose their lead times with the `todo_reminder_lead_minutes` preference, and opt out
+/// with `email_todo_reminders`. Only todos with a reminder to send are loaded, in batches.
+///
+/// # Configuration
+/// - `TODO_REMINDER_INTERVAL_SECONDS`: Seconds between two scans (default: 60)
+pub fn start_todo_reminders(pool: PgPool, mail: MailerState) {
+    let interval_secs = get_env_u64("TODO_REMINDER_INTERVAL_SECONDS", 60).max(1);
+
+    tokio::spawn(async move {
+        let mut interval = interval(Duration::from_secs(interval_secs));
+        loop {
+            interval.tick().await;
+            send_todo_reminders(&pool, &mail).await;
+        }
+    });
+}
+
+// Minutes after which the claim of a reminder that was not sent is taken over by another scan
+const CLAIM_TIMEOUT_MINUTES: i64 = 10;
+
+// Amount of todos loaded at once
+const BATCH_SIZE: i64 = 500;
+
+// Sends the reminders that are due, one email per todo
+#[instrument(skip(pool, mail))]
+async fn send_todo_reminders(pool: &PgPool, mail: &MailerState) {
+    let now = Utc::now();
+    let until = now + chrono::Duration::minutes(MAX_REMINDER_LEAD_MINUTES as i64);
+    let stale_before = now - chrono::Duration::minutes(CLAIM_TIMEOUT_MINUTES);
+
+    let defaults = UserPreferences::default();
+    let default_lead_minutes: Vec<i32> = defaults.todo_reminder_lead_minutes.iter().map(|minutes| *minutes as i32).collect();
+
+    let mut after = (DateTime::<Utc>::MIN_UTC, uuid::Uuid::nil());
+    loop {
+        let todos = match fetch_upcoming_todo_reminders_from_db(
+            pool,
+            until,
+            (&default_lead_minutes, defaults.email_todo_reminders),
+            stale_before,
+            after,
+            BATCH_SIZE,
+        )
+        .await
+        {
+            Ok(todos) => todos,
+            Err(e) => {
+                error!("Error fetching the upcoming todos for reminders: {}", e);
+                return;
+            }
+        };
+
+        let Some(last) = todos.last() else { return };
+        after = (last.due_at, last.todo_id);
+        let complete = (todos.len() as i64) < BATCH_SIZE;
+
+        for todo in todos {
+            send_todo_reminder(pool, mail, todo, now, stale_before).await;
+        }
+        if complete {
+            return;
+        }
+    }
+}
+
+// Claims and sends the reminders of a single todo that are due
+async fn send_todo_reminder(pool: &PgPool, mail: &MailerState, todo: TodoReminderRow, now: DateTime<Utc>, stale_before: DateTime<Utc>) {
+    let preferences = resolve_preferences(&todo.preferences);
+    if !preferences.email_todo_reminders {
+        return;
+    }
+
+    let due = due_lead_minutes(&preferences.todo_reminder_lead_minutes, todo.due_at, now, &todo.claimed);
+    if due.is_empty() {
+        return;
+    }
+
+    // Lead times that passed together are claimed together and sent as a single reminder
+    let claimed = match claim_todo_reminders_in_db(pool, todo.todo_id, todo.due_at, &due, stale_before).await {
+        Ok(claimed) if claimed.is_empty() => return,
+        Ok(claimed) => claimed,
+        Err(e) => {
+            error!("Failed to claim the reminders of todo {}: {}", todo.todo_id, e);
+            return;
+        }
+    };
+
+    let timezone = todo
+        .due_timezone
+        .as_deref()
+        .and_then(|timezone| timezone.parse::<Tz>().ok())
+        .unwrap_or_else(|| preferences.tz());
+    let (subject, body) = reminder_mail(&todo, timezone);
+
+    if let Err(e) = send_mail(mail, &todo.email, &subject, &body).await {
+        error!("Failed to send the reminder of todo {} to user {}: {}", todo.todo_id, todo.user_id, e);
+        // Release the claim, so that the reminder is retried during the next scan
+        if let Err(e) = release_todo_reminders_in_db(pool, todo.todo_id, todo.due_at, &claimed).await {
+            error!("Failed to release the reminders of todo {}: {}", todo.todo_id, e);
+        }
+    } else {
+        debug!("Sent a reminder of todo {} to user {}.", todo.todo_id, todo.user_id);
+        if let Err(e) = complete_todo_reminders_in_db(pool, todo.todo_id, todo.due_at, &claimed).await {
+            error!("Failed to record the reminders of todo {} as sent: {}", todo.todo_id, e);
+        }
+    }
+}
+
+// The subject and body of a reminder, in the language of the user and with the due date in its time zone
+fn reminder_mail(todo: &TodoReminderRow, timezone: Tz) -> (String, String) {
+    let locale = Locale::for_user(todo.language_code.as_deref().map(str::trim), Locale::default());
+    let due = format!("{} ({})", todo.due_at.with_timezone(&timezone).format("%Y-%m-%d %H:%M"), timezone.name());
+
+    let args = [("task", todo.task.as_str()), ("due", due.as_str())];
+    (
+        t_with(locale, "mail.todo_reminder.subject", &args),
+        t_with(locale, "mail.todo_reminder.body", &args),
+    )
+}
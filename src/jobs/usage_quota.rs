@@ -0,0 +1,91 @@
+use sqlx::PgPool;
+use std::time::Duration;
+use chrono::Utc;
+use tokio::time::interval;
+use tracing::{debug, error, instrument};
+
+use crate::core::config::get_env_u64;
+use crate::database::usage::{fetch_monthly_quota_usage_from_db, insert_quota_notification_into_db, delete_quota_notification_from_db};
+use crate::mail::MailerState;
+use crate::i18n::catalog::t_with;
+use crate::i18n::locale::Locale;
+use crate::mail::send::send_mail;
+use crate::utils::preferences::load_user_preferences;
+use crate::utils::quota::{quota_period, quota_thresholds, reached_thresholds};
+
+/// Starts the background task that emails users when they cross a monthly quota threshold.
+///
+/// # Configuration
+/// - `USAGE_QUOTA_CHECK_INTERVAL_SECONDS`: Seconds between two checks (default: 300)
+/// - `USAGE_QUOTA_THRESHOLDS`: Comma separated percentages of the quota (default: "80,100")
+pub fn start_quota_notifications(pool: PgPool, mail: MailerState) {
+    let interval_secs = get_env_u64("USAGE_QUOTA_CHECK_INTERVAL_SECONDS", 300).max(1);
+    let thresholds = quota_thresholds();
+    if thresholds.is_empty() {
+        return;
+    }
+
+    tokio::spawn(async move {
+        let mut interval = interval(Duration::from_secs(interval_secs));
+        loop {
+            interval.tick().await;
+            notify_quota_thresholds(&pool, &mail, &thresholds).await;
+        }
+    });
+}
+
+// Sends a notification for the highest newly reached threshold of every user
+#[instrument(skip(pool, mail))]
+async fn notify_quota_thresholds(pool: &PgPool, mail: &MailerState, thresholds: &[i32]) {
+    let (period_start, reset_date) = quota_period(Utc::now().date_naive());
+
+    let rows = match fetch_monthly_quota_usage_from_db(pool, period_start).await {
+        Ok(rows) => rows,
+        Err(e) => {
+            error!("Error fetching the monthly quota usage: {}", e);
+            return;
+        }
+    };
+
+    for row in rows {
+        let quota = row.requests_per_month as i64;
+        let mut notified = false;
+
+        // Claim every reached threshold, but only mail about the highest one
+        for threshold in reached_thresholds(thresholds, row.count, quota) {
+            match insert_quota_notification_into_db(pool, row.user_id, period_start, threshold).await {
+                Ok(true) if !notified => {
+                    notified = true;
+
+                    // The claim is kept, so users who opted out are not notified once they opt in again
+                    if !load_user_preferences(pool, row.user_id).await.email_usage_alerts {
+                        debug!("User {} opted out of quota notifications.", row.user_id);
+                        continue;
+                    }
+
+                    let locale = Locale::for_user(row.language_code.as_deref(), Locale::fallback());
+                    let threshold_text = threshold.to_string();
+                    let subject = t_with(locale, "mail.quota.subject", &[("threshold", &threshold_text)]);
+                    let body = t_with(locale, "mail.quota.body", &[
+                        ("count", &row.count.to_string()),
+                        ("quota", &quota.to_string()),
+                        ("threshold", &threshold_text),
+                        ("reset_date", &reset_date.to_string()),
+                    ]);
+
+                    if let Err(e) = send_mail(mail, &row.email, &subject, &body).await {
+                        error!("Failed to send the quota notification to user {}: {}", row.user_id, e);
+                        // Release the claim, so that the notification is retried during the next check
+                        if let Err(e) = delete_quota_notification_from_db(pool, row.user_id, period_start, threshold).await {
+                            error!("Failed to release the quota notification of user {}: {}", row.user_id, e);
+                        }
+                    } else {
+                        debug!("Sent the {}% quota notification to user {}.", threshold, row.user_id);
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => error!("Failed to claim the quota notification of user {}: {}", row.user_id, e),
+            }
+        }
+    }
+}
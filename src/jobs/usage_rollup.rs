@@ -0,0 +1,64 @@
+use sqlx::PgPool;
+use std::time::Duration;
+use chrono::Utc;
+use tokio::time::interval;
+use tracing::{debug, error, instrument};
+
+use crate::core::config::{get_env_u64, get_env_with_default};
+use crate::database::usage::{rollup_usage_in_db, purge_usage_from_db};
+
+/// Starts the background task that rolls the raw usage up into the hourly and daily aggregates.
+///
+/// After every rollup the retention policy is applied to the raw usage rows.
+///
+/// # Configuration
+/// - `USAGE_ROLLUP_INTERVAL_SECONDS`: Seconds between two runs (default: 300)
+/// - `USAGE_RETENTION_DAYS`: Days raw usage rows are kept (default: 30)
+/// - `USAGE_HOURLY_RETENTION_DAYS`: Days hourly aggregates are kept (default: 90)
+/// - `USAGE_RETENTION_MODE`: "delete" or "archive" the expired raw rows (default: "delete")
+pub fn start_usage_rollup(pool: PgPool) {
+    let interval_secs = get_env_u64("USAGE_ROLLUP_INTERVAL_SECONDS", 300).max(1);
+    let retention_days = get_env_u64("USAGE_RETENTION_DAYS", 30).max(1) as i64;
+    // The daily aggregates are recomputed from the hourly ones, so keep at least two days of those.
+    let hourly_retention_days = get_env_u64("USAGE_HOURLY_RETENTION_DAYS", 90).max(2) as i64;
+    let archive = get_env_with_default("USAGE_RETENTION_MODE", "delete").to_lowercase() == "archive";
+
+    tokio::spawn(async move {
+        let mut interval = interval(Duration::from_secs(interval_secs));
+        loop {
+            interval.tick().await;
+            run_usage_rollup(&pool, retention_days, hourly_retention_days, archive).await;
+        }
+    });
+}
+
+// Performs a single rollup followed by the retention policy
+#[instrument(skip(pool))]
+async fn run_usage_rollup(pool: &PgPool, retention_days: i64, hourly_retention_days: i64, archive: bool) {
+    match rollup_usage_in_db(pool).await {
+        Ok(Some(until)) => debug!("Usage has been rolled up until {}.", until),
+        Ok(None) => {
+            debug!("Usage rollup is already running on another instance.");
+            return;
+        }
+        Err(e) => {
+            error!("Error rolling up usage: {}", e);
+            return;
+        }
+    }
+
+    let now = Utc::now();
+    let raw_cutoff = now - chrono::Duration::days(retention_days);
+    let hourly_cutoff = now - chrono::Duration::days(hourly_retention_days);
+
+    match purge_usage_from_db(pool, raw_cutoff, hourly_cutoff, archive).await {
+        Ok(0) => {}
+        Ok(count) => debug!(
+            "{} {} raw usage records older than {} days.",
+            if archive { "Archived" } else { "Deleted" },
+            count,
+            retention_days
+        ),
+        Err(e) => error!("Error applying the usage retention policy: {}", e),
+    }
+}
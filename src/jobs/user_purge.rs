@@ -0,0 +1,110 @@
+use sqlx::PgPool;
+use std::time::Duration;
+use tokio::time::interval;
+use tracing::{error, info, instrument};
+
+use crate::core::config::{get_env_u64, get_env_with_default};
+use crate::database::data_exports::fetch_data_export_keys_by_user_from_db;
+use crate::database::todo_attachments::fetch_todo_attachments_by_user_from_db;
+use crate::database::users::{claim_user_for_purge_in_db, purge_user_from_db};
+use crate::mail::MailerState;
+use crate::i18n::catalog::t;
+use crate::i18n::locale::Locale;
+use crate::mail::send::send_mail;
+use crate::models::audit::AuditEvent;
+use crate::models::user::UserPurge;
+use crate::storage::StorageState;
+use crate::storage::delete::delete_from_storage;
+use crate::storage::download::split_storage_url;
+use crate::utils::audit::{record_audit_event, AuditContext};
+use crate::utils::attachments::attachment_bucket;
+use crate::utils::avatar::all_avatar_keys;
+
+/// Starts the background task that permanently erases deleted users once their grace period has passed.
+///
+/// The stored files of the user are removed first, then the user is deleted together with all
+/// its data. A purge that fails halfway is retried during a later run.
+///
+/// # Configuration
+/// - `USER_PURGE_INTERVAL_SECONDS`: Seconds between two runs (default: 3600)
+pub fn start_user_purge(pool: PgPool, storage: StorageState, mail: MailerState) {
+    let interval_secs = get_env_u64("USER_PURGE_INTERVAL_SECONDS", 3600).max(1);
+
+    tokio::spawn(async move {
+        let mut interval = interval(Duration::from_secs(interval_secs));
+        loop {
+            interval.tick().await;
+
+            // Users are claimed one by one, so multiple instances can share the work
+            loop {
+                match claim_user_for_purge_in_db(&pool).await {
+                    Ok(Some(user)) => purge_user(&pool, &storage, &mail, user).await,
+                    Ok(None) => break,
+                    Err(e) => {
+                        error!("Error claiming a user for purging: {}", e);
+                        break;
+                    }
+                }
+            }
+        }
+    });
+}
+
+// Erases the files and data of a single user
+#[instrument(skip(pool, storage, mail, user), fields(user_id = %user.id))]
+async fn purge_user(pool: &PgPool, storage: &StorageState, mail: &MailerState, user: UserPurge) {
+    if let Err(e) = delete_stored_files(pool, storage, &user).await {
+        error!("Failed to delete the files of user {}: {}", user.id, e);
+        return;
+    }
+
+    match purge_user_from_db(pool, user.id).await {
+        Ok(0) => return,
+        Ok(_) => {
+            info!("Purged user {}.", user.id);
+            let event = AuditEvent::new("user.purge", None).target("user", user.id);
+            record_audit_event(pool, &AuditContext::default(), event).await;
+        }
+        Err(e) => {
+            error!("Failed to purge user {}: {}", user.id, e);
+            return;
+        }
+    }
+
+    let locale = Locale::for_user(user.language_code.as_deref(), Locale::fallback());
+    let subject = t(locale, "mail.purged.subject");
+    let body = t(locale, "mail.purged.body");
+
+    if let Err(e) = send_mail(mail, &user.email, &subject, &body).await {
+        error!("Failed to send the erasure confirmation to user {}: {}", user.id, e);
+    }
+}
+
+// Deletes the profile picture, the data export bundles and the todo attachments of a user
+async fn delete_stored_files(pool: &PgPool, storage: &StorageState, user: &UserPurge) -> Result<(), String> {
+    if let Some((bucket, object_key)) = user.profile_picture_url.as_deref().and_then(|url| split_storage_url(storage, url)) {
+        for object_key in all_avatar_keys(object_key) {
+            delete_from_storage(storage, bucket, &object_key).await?;
+        }
+    }
+
+    let bucket = get_env_with_default("STORAGE_BUCKET_DATA_EXPORTS", "data-exports");
+    let keys = fetch_data_export_keys_by_user_from_db(pool, user.id)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?;
+
+    for object_key in keys {
+        delete_from_storage(storage, &bucket, &object_key).await?;
+    }
+
+    let bucket = attachment_bucket();
+    let attachments = fetch_todo_attachments_by_user_from_db(pool, user.id)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?;
+
+    for attachment in attachments {
+        delete_from_storage(storage, &bucket, &attachment.object_key).await?;
+    }
+
+    Ok(())
+}
@@ -28,7 +28,7 @@ async fn get_footer_html() -> Result<Arc<String>, SmtpError> {
         let footer = fs::read_to_string("src/mail/footer.html")
             .await
             .map_err(|e| SmtpError::OperationError(format!("Failed to read footer.html: {}", e)))?;
-        Ok(FOOTER_HTML.get().unwrap().clone()) 
+        Ok(FOOTER_HTML.get_or_init(|| Arc::new(footer)).clone())
     }
 }
 
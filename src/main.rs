@@ -16,6 +16,8 @@ mod handlers;
 mod utils;
 mod wrappers;
 mod referencedata;
+mod jobs;
+mod i18n;
 
 use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
@@ -169,7 +171,7 @@ async fn main() {
 
         // Create the server future but don't await it yet
         let server = axum_server::bind_rustls(addr, rustls_config)
-            .serve(app.into_make_service());
+            .serve(app.into_make_service_with_connect_info::<SocketAddr>());
 
         tokio::select! {
             result = server => {
@@ -188,7 +190,7 @@ async fn main() {
 
         // Create the server future but don't await it yet
         let server = axum_server::bind(addr)
-            .serve(app.into_make_service());
+            .serve(app.into_make_service_with_connect_info::<SocketAddr>());
 
         tokio::select! {
             result = server => {
@@ -17,10 +17,14 @@ use tokio::time::interval;
 use chrono::Utc;
 
 // Importing custom database query functions
-use crate::database::users::fetch_active_user_by_email_from_db;
+use crate::database::users::{fetch_active_user_by_email_from_db, fetch_user_suspension_by_email_from_db};
+use crate::database::usage::{fetch_usage_count_from_db, fetch_usage_count_since_from_db};
+use crate::database::tiers::fetch_tier_by_level_from_db;
 
+use crate::i18n::locale::Locale;
 use crate::models::auth::AuthError; // Import the AuthError struct for error handling
 use crate::utils::auth::{decode_jwt, extract_token_from_header, extract_token_from_cookie};
+use crate::utils::quota::quota_period;
 use crate::core::config::get_env_bool; // For fetching environment variables
 use crate::routes::AppState; // For extacting the application state from the request
 
@@ -29,6 +33,8 @@ use crate::routes::AppState; // For extacting the application state from the req
 struct CachedRateLimit {
     tier_limit: i64,
     request_count: i64,
+    monthly_limit: Option<i64>,
+    monthly_count: i64,
 }
 
 // New struct for batched usage records
@@ -47,8 +53,12 @@ lazy_static::lazy_static! {
     static ref USAGE_QUEUE: Arc<Mutex<Vec<UsageRecord>>> = Arc::new(Mutex::new(Vec::new()));
 }
 
+// Function to drop all cached rate limits, so that changed tier limits apply immediately
+pub fn invalidate_rate_limit_cache() {
+    RATE_LIMIT_CACHE.invalidate_all();
+}
+
 // Function to start the background task for batched writes
-#[allow(dead_code)]
 pub fn start_batched_writes(pool: PgPool) {
     tokio::spawn(async move {
         let mut interval = interval(Duration::from_secs(60)); // Run every minute
@@ -69,7 +79,7 @@ async fn flush_usage_queue(pool: &PgPool) {
 
     // Prepare batch insert
     let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new(
-        "INSERT INTO usage (user_id, path, creation_date) "
+        "INSERT INTO usage (user_id, endpoint, creation_date) "
     );
 
     query_builder.push_values(queue.iter(), |mut b, record| {
@@ -116,41 +126,52 @@ pub async fn authorize(
         (false, _) => extract_token_from_header(&req),
     };
 
+    // Errors are in the language of the client until the user is known
+    let locale = Locale::from_headers(req.headers()).unwrap_or_else(Locale::fallback);
+
     // If no token is found, return an error
-    let token = token_opt.ok_or_else(|| AuthError {
-        message: "Authorization token missing.".to_string(),
-        status_code: StatusCode::UNAUTHORIZED,
-    })?;
+    let token = token_opt.ok_or_else(|| AuthError::new(locale, "auth.token_missing", StatusCode::UNAUTHORIZED))?;
 
     // Decode the JWT securely
-    let token_data = decode_jwt(token)?;
+    let token_data = decode_jwt(token, locale)?;
 
     // Fetch the user from the database using the email from the decoded token
-    let current_user = fetch_active_user_by_email_from_db(&database, &token_data.claims.sub).await
-        .map_err(|_| AuthError {
-            message: "Unauthorized user.".to_string(),
-            status_code: StatusCode::UNAUTHORIZED,
-        })?
-        .ok_or_else(|| AuthError {
-            message: "User not found.".to_string(),
-            status_code: StatusCode::UNAUTHORIZED,
-        })?;
+    let current_user = match fetch_active_user_by_email_from_db(database, &token_data.claims.sub).await {
+        Ok(Some(user)) => user,
+        Ok(None) => {
+            // Tell suspended users why their token is no longer accepted
+            if let Ok(Some((suspension, _))) = fetch_user_suspension_by_email_from_db(database, &token_data.claims.sub).await {
+                return Err(AuthError {
+                    message: suspension.message(suspension.locale(locale)),
+                    status_code: StatusCode::FORBIDDEN,
+                });
+            }
+            return Err(AuthError::new(locale, "auth.user_not_found", StatusCode::UNAUTHORIZED));
+        }
+        Err(_) => return Err(AuthError::new(locale, "auth.unauthorized", StatusCode::UNAUTHORIZED)),
+    };
+    let locale = Locale::for_user(current_user.language_code.as_deref(), locale);
+
+    // Reject tokens issued before the sessions of the user were invalidated (e.g. by a suspension),
+    // `iat` only has whole seconds, so a token from the second of the invalidation itself is refused too
+    if let Some(valid_after) = current_user.tokens_valid_after {
+        if (token_data.claims.iat as i64) <= valid_after.timestamp() {
+            return Err(AuthError::new(locale, "auth.session_invalidated", StatusCode::UNAUTHORIZED));
+        }
+    }
 
     // Check if the user's role is in the list of allowed roles
     if !allowed_roles.contains(&current_user.role_level) {
-        return Err(AuthError {
-            message: "Forbidden: insufficient role.".to_string(),
-            status_code: StatusCode::FORBIDDEN,
-        });
+        return Err(AuthError::new(locale, "auth.insufficient_role", StatusCode::FORBIDDEN));
     }
 
     // Check rate limit using cached data
-    check_rate_limit(&database, current_user.id, current_user.tier_level).await?;
+    check_rate_limit(&database, current_user.id, current_user.tier_level, locale).await?;
 
     // Queue the usage record for batch insert instead of immediate insertion
     USAGE_QUEUE.lock().await.push(UsageRecord {
         user_id: current_user.id,
-        path: req.uri().path().to_string(),
+        path: req.uri().path().chars().take(255).collect(),  // Fits the endpoint column
     });
 
     // Insert the current user into the request extensions for use in subsequent handlers
@@ -162,61 +183,65 @@ pub async fn authorize(
 
 // Function to check rate limits for a user
 #[instrument(skip(database))]
-async fn check_rate_limit(database: &PgPool, user_id: Uuid, tier_level: i32) -> Result<(), AuthError> {
+async fn check_rate_limit(database: &PgPool, user_id: Uuid, tier_level: i32, locale: Locale) -> Result<(), AuthError> {
     // Try to get cached rate limit data
     if let Some(cached) = RATE_LIMIT_CACHE.get(&(user_id, tier_level)).await {
-        if cached.request_count >= cached.tier_limit {
-            return Err(AuthError {
-                message: "Rate limit exceeded".to_string(),
-                status_code: StatusCode::TOO_MANY_REQUESTS,
-            });
-        }
-        // Update cache with incremented request count
+        check_limits(&cached, locale)?;
+        // Update cache with incremented request counts
         RATE_LIMIT_CACHE.insert((user_id, tier_level), CachedRateLimit {
-            tier_limit: cached.tier_limit,
             request_count: cached.request_count + 1,
+            monthly_count: cached.monthly_count + 1,
+            ..cached
         }).await;
         return Ok(());
     }
 
     // If not in cache, fetch from database
-    let tier_limit = sqlx::query!(
-        "SELECT requests_per_day FROM tiers WHERE level = $1",
-        tier_level
-    )
-    .fetch_one(database)
-    .await
-    .map_err(|_| AuthError {
-        message: "Failed to fetch tier information".to_string(),
-        status_code: StatusCode::INTERNAL_SERVER_ERROR,
-    })?
-    .requests_per_day as i64;
-
-    // Count user's requests for today
-    let request_count = sqlx::query!(
-        "SELECT COUNT(*) as count FROM usage WHERE user_id = $1 AND creation_date > NOW() - INTERVAL '24 hours'",
-        user_id
-    )
-    .fetch_one(database)
-    .await
-    .map_err(|_| AuthError {
-        message: "Failed to count user requests".to_string(),
-        status_code: StatusCode::INTERNAL_SERVER_ERROR,
-    })?
-    .count
-    .unwrap_or(0) as i64; // Use 0 if count is NULL
+    let tier = fetch_tier_by_level_from_db(database, tier_level)
+        .await
+        .ok()
+        .flatten()
+        .ok_or_else(|| AuthError::new(locale, "auth.tier_unavailable", StatusCode::INTERNAL_SERVER_ERROR))?;
+
+    // Count user's requests for today, using the usage aggregates
+    let request_count = fetch_usage_count_from_db(database, user_id, "24 hours")
+        .await
+        .map_err(|_| AuthError::new(locale, "auth.count_failed", StatusCode::INTERNAL_SERVER_ERROR))?;
+
+    // Count user's requests for the current month, only if the tier has a monthly quota
+    let monthly_count = match tier.requests_per_month {
+        Some(_) => {
+            let (period_start, _) = quota_period(Utc::now().date_naive());
+            fetch_usage_count_since_from_db(database, user_id, period_start)
+                .await
+                .map_err(|_| AuthError::new(locale, "auth.count_failed", StatusCode::INTERNAL_SERVER_ERROR))?
+        }
+        None => 0,
+    };
 
-    // Cache the result
-    RATE_LIMIT_CACHE.insert((user_id, tier_level), CachedRateLimit {
-        tier_limit,
+    let cached = CachedRateLimit {
+        tier_limit: tier.requests_per_day as i64,
         request_count,
-    }).await;
+        monthly_limit: tier.requests_per_month.map(i64::from),
+        monthly_count,
+    };
+
+    // Cache the result
+    RATE_LIMIT_CACHE.insert((user_id, tier_level), cached.clone()).await;
 
-    if request_count >= tier_limit {
-        return Err(AuthError {
-            message: "Rate limit exceeded".to_string(),
-            status_code: StatusCode::TOO_MANY_REQUESTS,
-        });
+    check_limits(&cached, locale)
+}
+
+// Function to compare the (cached) request counts against the limits of the tier
+fn check_limits(limits: &CachedRateLimit, locale: Locale) -> Result<(), AuthError> {
+    if limits.request_count >= limits.tier_limit {
+        return Err(AuthError::new(locale, "auth.rate_limited", StatusCode::TOO_MANY_REQUESTS));
+    }
+
+    if let Some(monthly_limit) = limits.monthly_limit {
+        if limits.monthly_count >= monthly_limit {
+            return Err(AuthError::new(locale, "auth.quota_exceeded", StatusCode::TOO_MANY_REQUESTS));
+        }
     }
 
     Ok(())
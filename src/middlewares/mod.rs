@@ -1,2 +1,3 @@
 // Module declarations
-pub mod auth;
\ No newline at end of file
+pub mod auth;
+pub mod throttle;
\ No newline at end of file
@@ -0,0 +1,106 @@
+use axum::{
+    body::{Body, to_bytes},
+    extract::ConnectInfo,
+    http::{header::RETRY_AFTER, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::json;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tracing::{error, instrument, warn};
+
+use crate::cache::increment::increment_in_cache;
+use crate::core::config::{get_env_bool, get_env_u64};
+use crate::routes::AppState;
+use crate::utils::client_ip::{resolve_client_ip, trusted_proxies};
+
+// Maximum size of a request body that is inspected for a target email address
+const MAX_THROTTLED_BODY_SIZE: usize = 64 * 1024;
+
+// Middleware that throttles unauthenticated routes, per client IP and per target email address.
+// Counters are kept in Redis, so that the limits are shared between all instances behind the load balancer.
+//
+// Configuration:
+// - `THROTTLE_ENABLED`: Enables the throttling (default: true)
+// - `THROTTLE_WINDOW_SECONDS`: Length of a throttling window (default: 900)
+// - `THROTTLE_MAX_PER_IP`: Maximum amount of requests per client IP and route within a window (default: 30)
+// - `THROTTLE_MAX_PER_EMAIL`: Maximum amount of requests per email address and route within a window (default: 5)
+#[instrument(skip(state, req, next))]
+pub async fn throttle(
+    scope: &'static str,
+    state: Arc<AppState>,
+    req: axum::extract::Request<Body>,
+    next: axum::middleware::Next,
+) -> Response {
+    if !get_env_bool("THROTTLE_ENABLED", true) {
+        return next.run(req).await;
+    }
+
+    let window = get_env_u64("THROTTLE_WINDOW_SECONDS", 900);
+    let max_per_ip = get_env_u64("THROTTLE_MAX_PER_IP", 30);
+    let max_per_email = get_env_u64("THROTTLE_MAX_PER_EMAIL", 5);
+
+    // Throttle per client IP
+    if let Some(ConnectInfo(peer)) = req.extensions().get::<ConnectInfo<SocketAddr>>() {
+        let client_ip = resolve_client_ip(peer.ip(), req.headers(), &trusted_proxies());
+        let key = format!("throttle:{}:ip:{}", scope, client_ip);
+        if let Some(retry_after) = exceeds_limit(&state, &key, window, max_per_ip).await {
+            warn!("Throttled {} requests from {}.", scope, client_ip);
+            return too_many_requests(retry_after);
+        }
+    }
+
+    // Throttle per target email address, which requires buffering the (small) JSON body
+    let (parts, body) = req.into_parts();
+    let bytes = match to_bytes(body, MAX_THROTTLED_BODY_SIZE).await {
+        Ok(bytes) => bytes,
+        Err(_) => {
+            return (
+                StatusCode::PAYLOAD_TOO_LARGE,
+                Json(json!({ "error": "Request body is too large." })),
+            ).into_response();
+        }
+    };
+
+    let email = serde_json::from_slice::<serde_json::Value>(&bytes)
+        .ok()
+        .and_then(|value| value.get("email").and_then(|email| email.as_str()).map(|email| email.trim().to_lowercase()))
+        .filter(|email| !email.is_empty());
+
+    if let Some(email) = email {
+        let key = format!("throttle:{}:email:{}", scope, email);
+        if let Some(retry_after) = exceeds_limit(&state, &key, window, max_per_email).await {
+            warn!("Throttled {} requests for {}.", scope, email);
+            return too_many_requests(retry_after);
+        }
+    }
+
+    next.run(axum::extract::Request::from_parts(parts, Body::from(bytes))).await
+}
+
+// Counts the request and returns the seconds until the window resets when the limit is exceeded.
+// Fails open when Redis is unavailable, so that an outage of the cache does not lock everyone out.
+async fn exceeds_limit(state: &AppState, key: &str, window: u64, limit: u64) -> Option<u64> {
+    match increment_in_cache(&state.cache, key, window).await {
+        Ok((count, ttl)) if count > limit => Some(ttl),
+        Ok(_) => None,
+        Err(e) => {
+            error!("Failed to update the throttle counter: {}", e);
+            None
+        }
+    }
+}
+
+fn too_many_requests(retry_after: u64) -> Response {
+    let mut response = (
+        StatusCode::TOO_MANY_REQUESTS,
+        Json(json!({ "error": "Too many requests. Please try again later." })),
+    ).into_response();
+
+    if let Ok(value) = HeaderValue::from_str(&retry_after.to_string()) {
+        response.headers_mut().insert(RETRY_AFTER, value);
+    }
+
+    response
+}
@@ -56,7 +56,7 @@ pub struct ApiKeyInsertResponse {
 }
 
 /// Response body for retrieving an API key.
-#[derive(Serialize, ToSchema)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ApiKeyResponse {
     /// The unique id of the API key.
     pub id: Uuid,
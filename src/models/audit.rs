@@ -0,0 +1,122 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use utoipa::{IntoParams, ToSchema};
+
+/// Represents an entry of the security audit log.
+#[derive(Deserialize, Debug, Serialize, FromRow, Clone, ToSchema)]
+#[sqlx(rename_all = "snake_case")]  // Ensures that field names are mapped to snake_case in SQL
+pub struct AuditLogEntry {
+    /// ID of the entry.
+    pub id: Uuid,
+
+    /// Position of the entry in the hash chain.
+    pub seq: i64,
+
+    /// Moment the event occurred.
+    pub occurred_at: DateTime<Utc>,
+
+    /// ID of the user that performed the action, absent for anonymous requests and background jobs.
+    pub actor_id: Option<Uuid>,
+
+    /// The action, e.g. "user.login" or "apikey.create".
+    pub action: String,
+
+    /// Type of the affected resource, e.g. "user" or "apikey".
+    pub target_type: Option<String>,
+
+    /// ID of the affected resource.
+    pub target_id: Option<String>,
+
+    /// Address of the client.
+    pub ip: Option<String>,
+
+    /// User agent of the client.
+    pub user_agent: Option<String>,
+
+    /// Changed fields before the event.
+    pub before: Option<serde_json::Value>,
+
+    /// Changed fields after the event.
+    pub after: Option<serde_json::Value>,
+
+    /// Hash of the previous entry.
+    pub prev_hash: Option<String>,
+
+    /// SHA-256 hash over the previous hash and the contents of this entry.
+    pub hash: String,
+}
+
+/// An event to add to the audit log.
+#[derive(Debug, Clone, Default)]
+pub struct AuditEvent {
+    pub actor_id: Option<Uuid>,
+    pub action: String,
+    pub target_type: Option<String>,
+    pub target_id: Option<String>,
+    pub before: Option<serde_json::Value>,
+    pub after: Option<serde_json::Value>,
+}
+
+impl AuditEvent {
+    pub fn new(action: &str, actor_id: Option<Uuid>) -> Self {
+        Self { action: action.to_string(), actor_id, ..Default::default() }
+    }
+
+    /// Sets the affected resource.
+    pub fn target(mut self, target_type: &str, target_id: impl ToString) -> Self {
+        self.target_type = Some(target_type.to_string());
+        self.target_id = Some(target_id.to_string());
+        self
+    }
+
+    /// Sets the state before and after the event.
+    pub fn change(mut self, before: Option<serde_json::Value>, after: Option<serde_json::Value>) -> Self {
+        self.before = before;
+        self.after = after;
+        self
+    }
+}
+
+/// Filters for listing and exporting audit log entries
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct AuditLogFilter {
+    /// Only entries of this actor.
+    pub actor_id: Option<Uuid>,
+
+    /// Only entries of this target type.
+    pub target_type: Option<String>,
+
+    /// Only entries of this target.
+    pub target_id: Option<String>,
+
+    /// Only entries of this action.
+    pub action: Option<String>,
+
+    /// Only entries that occurred on or after this moment (RFC 3339).
+    pub from: Option<DateTime<Utc>>,
+
+    /// Only entries that occurred before this moment (RFC 3339).
+    pub to: Option<DateTime<Utc>>,
+}
+
+/// Query parameters for exporting the audit log.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct AuditLogExportQuery {
+    /// "csv" or "json" (default: "json").
+    pub format: Option<String>,
+}
+
+/// Result of verifying the hash chain of the audit log.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AuditLogVerification {
+    /// Whether every entry matches its hash and links to the previous entry.
+    pub valid: bool,
+
+    /// Amount of verified entries.
+    pub checked: i64,
+
+    /// Position of the first entry that does not match, when the chain is broken.
+    pub broken_at_seq: Option<i64>,
+}
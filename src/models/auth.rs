@@ -6,6 +6,9 @@ use axum::{
 use utoipa::ToSchema;
 use serde::{Serialize, Deserialize};
 
+use crate::i18n::catalog::t;
+use crate::i18n::locale::Locale;
+
 /// Represents the claims to be included in a JWT payload.
 #[derive(Serialize, Deserialize, ToSchema)]
 pub struct Claims {
@@ -34,6 +37,16 @@ pub struct AuthError {
     pub status_code: StatusCode,
 }
 
+impl AuthError {
+    /// An error with the catalog message for the given key, in the given locale.
+    pub fn new(locale: Locale, key: &str, status_code: StatusCode) -> Self {
+        AuthError {
+            message: t(locale, key),
+            status_code,
+        }
+    }
+}
+
 impl IntoResponse for AuthError {
     fn into_response(self) -> Response {
         let body = Json(serde_json::json!({
@@ -0,0 +1,150 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, NaiveDate, Utc};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+/// Represents the price of a tier, valid from a certain date.
+#[derive(Deserialize, Debug, Serialize, FromRow, Clone, ToSchema)]
+#[sqlx(rename_all = "snake_case")]  // Ensures that field names are mapped to snake_case in SQL
+pub struct BillingPrice {
+    /// ID of the price.
+    pub id: Uuid,
+
+    /// ID of the tier the price applies to.
+    pub tier_id: Uuid,
+
+    /// First day of the first period the price applies to.
+    pub valid_from: NaiveDate,
+
+    /// ISO 4217 currency code.
+    pub currency: String,
+
+    /// Monthly base fee in cents.
+    pub base_fee_cents: i64,
+
+    /// Amount of requests per month included in the base fee.
+    pub included_requests: i64,
+
+    /// Price in cents per 1000 requests above the included amount.
+    pub overage_cents_per_1000: i64,
+
+    /// Moment the price was created.
+    pub creation_date: DateTime<Utc>,
+}
+
+/// Request body for adding a price to a tier.
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct BillingPriceInsertBody {
+    /// ID of the tier the price applies to.
+    pub tier_id: Uuid,
+
+    /// First day of the first period the price applies to (must be the first day of a month).
+    pub valid_from: NaiveDate,
+
+    /// ISO 4217 currency code (default: EUR).
+    #[validate(length(equal = 3, message = "Currency must be a 3 letter ISO 4217 code"))]
+    pub currency: Option<String>,
+
+    /// Monthly base fee in cents.
+    #[validate(range(min = 0, message = "Base fee cannot be negative"))]
+    pub base_fee_cents: i64,
+
+    /// Amount of requests per month included in the base fee.
+    #[validate(range(min = 0, message = "Included requests cannot be negative"))]
+    pub included_requests: i64,
+
+    /// Price in cents per 1000 requests above the included amount.
+    #[validate(range(min = 0, message = "Overage price cannot be negative"))]
+    pub overage_cents_per_1000: i64,
+}
+
+/// Represents a monthly billing statement of a user.
+#[derive(Deserialize, Debug, Serialize, FromRow, Clone, ToSchema)]
+#[sqlx(rename_all = "snake_case")]  // Ensures that field names are mapped to snake_case in SQL
+pub struct BillingStatement {
+    /// ID of the statement.
+    pub id: Uuid,
+
+    /// ID of the billed user, `None` once the user has been purged.
+    pub user_id: Option<Uuid>,
+
+    /// First day of the billed month.
+    pub period_start: NaiveDate,
+
+    /// First day of the next month (exclusive).
+    pub period_end: NaiveDate,
+
+    /// ID of the price that was applied, if it still exists.
+    pub price_id: Option<Uuid>,
+
+    /// Level of the tier at the moment of billing.
+    pub tier_level: i32,
+
+    /// Name of the tier at the moment of billing.
+    pub tier_name: String,
+
+    /// ISO 4217 currency code.
+    pub currency: String,
+
+    /// Monthly base fee in cents.
+    pub base_fee_cents: i64,
+
+    /// Amount of requests included in the base fee.
+    pub included_requests: i64,
+
+    /// Price in cents per 1000 requests above the included amount.
+    pub overage_cents_per_1000: i64,
+
+    /// Amount of requests made within the period.
+    pub request_count: i64,
+
+    /// Amount of requests above the included amount.
+    pub overage_requests: i64,
+
+    /// Price of the overage in cents (rounded up).
+    pub overage_cents: i64,
+
+    /// Total amount in cents.
+    pub total_cents: i64,
+
+    /// Moment the statement was generated.
+    pub generated_at: DateTime<Utc>,
+}
+
+/// Request body for generating the statements of a period.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct BillingStatementGenerateBody {
+    /// The month to bill, formatted as YYYY-MM.
+    pub period: String,
+
+    /// Only generate the statement of this user.
+    pub user_id: Option<Uuid>,
+}
+
+/// Response after generating the statements of a period.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BillingStatementGenerateResponse {
+    /// First day of the billed month.
+    pub period_start: NaiveDate,
+
+    /// Amount of newly generated statements. Existing statements are never regenerated.
+    pub generated: u64,
+}
+
+/// Query parameters for listing statements.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct BillingStatementsQuery {
+    /// List the statements of another user (admins only).
+    pub user_id: Option<Uuid>,
+}
+
+/// Query parameters for exporting a statement.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct BillingStatementExportQuery {
+    /// "csv" or "html" (default: "html").
+    pub format: Option<String>,
+}
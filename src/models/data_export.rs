@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+
+use crate::models::apikey::ApiKeyResponse;
+use crate::models::billing::BillingStatement;
+use crate::models::preference::UserPreferences;
+use crate::models::todo::{Todo, TodoAttachment, TodoList, TodoSeries};
+use crate::models::usage::UsageExportRow;
+use crate::models::user::UserGetResponse;
+
+/// Represents a request for an export of all personal data of a user.
+#[derive(Deserialize, Debug, Serialize, FromRow, Clone, ToSchema)]
+#[sqlx(rename_all = "snake_case")]  // Ensures that field names are mapped to snake_case in SQL
+pub struct DataExport {
+    /// ID of the export.
+    pub id: Uuid,
+
+    /// ID of the user whose data is exported.
+    pub user_id: Uuid,
+
+    /// "pending", "processing", "completed", "failed" or "expired".
+    pub status: String,
+
+    /// Key of the bundle in the data exports bucket.
+    #[serde(skip)]
+    pub object_key: Option<String>,
+
+    /// Reason the export failed, if it did.
+    pub error: Option<String>,
+
+    /// Moment the export was requested.
+    pub requested_at: DateTime<Utc>,
+
+    /// Moment the bundle was uploaded.
+    pub completed_at: Option<DateTime<Utc>>,
+
+    /// Moment the bundle will be deleted.
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Response with the state of an export, and a download link once completed.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DataExportResponse {
+    #[serde(flatten)]
+    pub export: DataExport,
+
+    /// Temporary link to download the bundle, only when the export has completed.
+    pub download_url: Option<String>,
+}
+
+/// A file embedded in the export bundle.
+#[derive(Debug, Serialize)]
+pub struct DataExportFile {
+    /// Original location of the file.
+    pub url: String,
+
+    /// The contents of the file, base64 encoded.
+    pub base64: String,
+}
+
+/// The bundle with all personal data of a user.
+#[derive(Debug, Serialize)]
+pub struct DataExportBundle {
+    pub generated_at: DateTime<Utc>,
+    pub profile: UserGetResponse,
+    pub preferences: UserPreferences,
+    pub todos: Vec<Todo>,
+    pub todo_lists: Vec<TodoList>,
+    pub todo_series: Vec<TodoSeries>,
+    pub todo_attachments: Vec<TodoAttachment>,
+    pub api_keys: Vec<ApiKeyResponse>,
+    pub usage: Vec<UsageExportRow>,
+    pub billing_statements: Vec<BillingStatement>,
+    pub profile_picture: Option<DataExportFile>,
+}
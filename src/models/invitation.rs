@@ -0,0 +1,128 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, NaiveDate, Utc};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+use crate::utils::validate::{validate_password, validate_username, validate_birthday, validate_country_code, validate_language_code};
+
+/// Represents an invitation of an admin to create an account.
+#[derive(Deserialize, Debug, Serialize, FromRow, Clone, ToSchema)]
+#[sqlx(rename_all = "snake_case")]  // Ensures that field names are mapped to snake_case in SQL
+pub struct Invitation {
+    /// ID of the invitation.
+    pub id: Uuid,
+
+    /// Email address the invitation was sent to.
+    pub email: String,
+
+    /// Role level of the user created when accepting.
+    pub role_level: i32,
+
+    /// Tier level of the user created when accepting.
+    pub tier_level: i32,
+
+    /// "pending", "accepted" or "revoked". Pending invitations past `expires_at` can no longer be accepted.
+    pub status: String,
+
+    /// Nonce included in the signed link.
+    #[serde(skip)]
+    pub token_nonce: String,
+
+    /// ID of the admin that sent the invitation.
+    pub invited_by: Option<Uuid>,
+
+    /// ID of the user created when accepting.
+    pub user_id: Option<Uuid>,
+
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub last_sent_at: DateTime<Utc>,
+
+    /// Amount of times the invitation has been sent.
+    pub send_count: i32,
+
+    pub accepted_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+/// Request body for inviting a new user.
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct InvitationInsertBody {
+    #[validate(email)]
+    pub email: String,
+
+    /// Role level of the new user (default: 1).
+    #[validate(range(min = 1, max = 2, message = "Role level must be 1 (regular) or 2 (admin)."))]
+    pub role_level: Option<i32>,
+
+    /// Tier level of the new user (default: 1).
+    pub tier_level: Option<i32>,
+}
+
+/// Filters for listing invitations
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct InvitationListQuery {
+    /// "pending", "accepted", "revoked" or "expired" (default: all).
+    pub status: Option<String>,
+}
+
+/// Claims of the token in an invitation link.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InvitationClaims {
+    /// ID of the invitation.
+    pub sub: String,
+    pub nonce: String,
+    pub iat: usize,
+    pub exp: usize,
+    pub iss: String,
+    pub aud: String,
+}
+
+/// Public details of an invitation, shown to the invitee before accepting.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct InvitationPreviewResponse {
+    pub email: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Query of the link in an invitation.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct InvitationTokenQuery {
+    pub token: String,
+}
+
+/// Request body for accepting an invitation, the invitee chooses its own password and profile.
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct InvitationAcceptBody {
+    /// The token from the invitation link.
+    pub token: String,
+
+    #[validate(length(min = 3, max = 50), custom(function = "validate_username"))]
+    pub username: String,
+
+    #[validate(custom(function = "validate_password"))]
+    pub password: String,
+
+    pub totp: Option<bool>,
+
+    #[validate(length(min = 1, max = 50))]
+    pub first_name: Option<String>,
+
+    #[validate(length(min = 1, max = 50))]
+    pub last_name: Option<String>,
+
+    #[validate(length(equal = 2), custom(function = "validate_country_code"))]
+    pub country_code: Option<String>,
+
+    #[validate(length(min = 2, max = 5), custom(function = "validate_language_code"))]
+    pub language_code: Option<String>,
+
+    #[validate(custom(function = "validate_birthday"))]
+    pub birthday: Option<NaiveDate>,
+
+    #[validate(length(max = 1000))]
+    pub description: Option<String>,
+}
@@ -1,9 +1,17 @@
 /// Module for user related models.
 pub mod user;
+/// Module for user import related models.
+pub mod user_import;
+/// Module for user preference related models.
+pub mod preference;
+/// Module for invitation related models.
+pub mod invitation;
 /// Module for API key related models.
 pub mod apikey;
 /// Module for userrole related models.
 pub mod role;
+/// Module for tier related models.
+pub mod tier;
 /// Module for to-do related models.
 pub mod todo;
 /// Module for documentation related models.
@@ -14,5 +22,13 @@ pub mod auth;
 pub mod health;
 /// Module for the health endpoint related models.
 pub mod usage;
+/// Module for billing related models.
+pub mod billing;
+/// Module for data export related models.
+pub mod data_export;
+/// Module for audit log related models.
+pub mod audit;
+/// Module for pagination related models.
+pub mod pagination;
 /// Module for errors.
 pub mod error;
\ No newline at end of file
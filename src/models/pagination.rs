@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+/// Query parameters shared by all paginated list endpoints.
+///
+/// Either `offset` or `cursor` can be used. Cursor pagination stays fast and stable
+/// on large tables, offset pagination allows jumping to an arbitrary page.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct PaginationQuery {
+    /// Maximum amount of items to return (default: 50, max: 200).
+    pub limit: Option<i64>,
+
+    /// Amount of items to skip. Cannot be combined with `cursor`.
+    pub offset: Option<i64>,
+
+    /// Opaque cursor from the `next_cursor` of a previous page, only valid with the same `sort` and `order`.
+    pub cursor: Option<String>,
+
+    /// Field to sort on. The sortable fields differ per endpoint.
+    pub sort: Option<String>,
+
+    /// Sort order: "asc" or "desc" (default: "asc").
+    pub order: Option<String>,
+}
+
+/// A page of a paginated list.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct Page<T> {
+    /// The items on this page.
+    pub items: Vec<T>,
+
+    /// Total amount of items matching the filters.
+    pub total: i64,
+
+    /// Maximum amount of items per page.
+    pub limit: i64,
+
+    /// Amount of skipped items, when using offset pagination.
+    pub offset: Option<i64>,
+
+    /// Cursor for the next page, absent on the last page.
+    pub next_cursor: Option<String>,
+}
@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+use chrono_tz::Tz;
+use utoipa::ToSchema;
+
+/// Color scheme of the frontend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Theme {
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+/// Todos shown when no view is requested.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TodoView {
+    #[default]
+    All,
+    Open,
+    Completed,
+}
+
+impl TodoView {
+    /// Parses a view from a query parameter.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "all" => Some(TodoView::All),
+            "open" => Some(TodoView::Open),
+            "completed" => Some(TodoView::Completed),
+            _ => None,
+        }
+    }
+
+    /// The completion state of the todos in this view, `None` for all todos.
+    pub fn completed(self) -> Option<bool> {
+        match self {
+            TodoView::All => None,
+            TodoView::Open => Some(false),
+            TodoView::Completed => Some(true),
+        }
+    }
+}
+
+/// The preferences of a user, with the defaults for everything the user did not set.
+///
+/// Adding a preference only requires a field with a default here, stored preferences are not migrated.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(default)]
+pub struct UserPreferences {
+    /// "system", "light" or "dark" (default: "system").
+    pub theme: Theme,
+
+    /// IANA time zone used for dates in emails and todos (default: "UTC").
+    #[schema(example = "Europe/Amsterdam")]
+    pub timezone: String,
+
+    /// "all", "open" or "completed", used by `/todos/all` when no view is requested (default: "all").
+    pub default_todo_view: TodoView,
+
+    /// Receive emails when crossing a monthly quota threshold (default: true).
+    pub email_usage_alerts: bool,
+
+    /// Receive emails reminding of todos that are due (default: true).
+    pub email_todo_reminders: bool,
+
+    /// Minutes before the due date of a todo to send reminders, at most 5 of at most 4 weeks (default: [60]).
+    #[schema(example = json!([1440, 60]))]
+    pub todo_reminder_lead_minutes: Vec<u32>,
+}
+
+impl Default for UserPreferences {
+    fn default() -> Self {
+        UserPreferences {
+            theme: Theme::default(),
+            timezone: "UTC".to_string(),
+            default_todo_view: TodoView::default(),
+            email_usage_alerts: true,
+            email_todo_reminders: true,
+            todo_reminder_lead_minutes: vec![60],
+        }
+    }
+}
+
+impl UserPreferences {
+    /// The time zone of the user, UTC when the stored one is no longer known.
+    pub fn tz(&self) -> Tz {
+        self.timezone.parse().unwrap_or(Tz::UTC)
+    }
+}
@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::NaiveDate;
+use utoipa::ToSchema;
+use validator::Validate;
+
+use crate::models::todo::nullable;
+
+/// Represents a tier, which determines the request limits of a user.
+#[derive(Deserialize, Debug, Serialize, FromRow, Clone, ToSchema)]
+#[sqlx(rename_all = "snake_case")]  // Ensures that field names are mapped to snake_case in SQL
+pub struct Tier {
+    /// ID of the tier.
+    pub id: Uuid,
+
+    /// Level of the tier.
+    pub level: i32,
+
+    /// The name of the tier.
+    pub name: String,
+
+    /// Description of the tier.
+    pub description: Option<String>,
+
+    /// Maximum amount of requests within a rolling 24 hour window.
+    pub requests_per_day: i32,
+
+    /// Maximum amount of requests within a calendar month (UTC), if any.
+    pub requests_per_month: Option<i32>,
+
+    /// Storage the todo attachments of a user may take up together (in MB), if it differs from the default.
+    pub attachment_quota_mb: Option<i32>,
+
+    /// Date when the tier was created.
+    pub creation_date: NaiveDate,
+}
+
+/// Request body for creating a tier.
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TierInsertBody {
+    /// Level of the tier, referenced by `users.tier_level`.
+    #[validate(range(min = 1, message = "Level must be at least 1"))]
+    pub level: i32,
+
+    /// The name of the tier.
+    #[validate(length(min = 1, max = 255, message = "Name must be between 1 and 255 characters"))]
+    pub name: String,
+
+    /// Description of the tier.
+    #[validate(length(max = 255, message = "Description must be at most 255 characters"))]
+    pub description: Option<String>,
+
+    /// Maximum amount of requests within a rolling 24 hour window.
+    #[validate(range(min = 0, message = "Requests per day cannot be negative"))]
+    pub requests_per_day: i32,
+
+    /// Maximum amount of requests within a calendar month (UTC), if any.
+    #[validate(range(min = 0, message = "Requests per month cannot be negative"))]
+    pub requests_per_month: Option<i32>,
+
+    /// Storage the todo attachments of a user may take up together (in MB), if it differs from the default.
+    #[validate(range(min = 0, message = "Attachment quota cannot be negative"))]
+    pub attachment_quota_mb: Option<i32>,
+}
+
+/// Request body for updating a tier. Fields not included remain unchanged.
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TierUpdateBody {
+    /// Level of the tier. Users on the old level are moved along.
+    #[validate(range(min = 1, message = "Level must be at least 1"))]
+    pub level: Option<i32>,
+
+    /// The name of the tier.
+    #[validate(length(min = 1, max = 255, message = "Name must be between 1 and 255 characters"))]
+    pub name: Option<String>,
+
+    /// Description of the tier, `null` removes it.
+    #[validate(length(max = 255, message = "Description must be at most 255 characters"))]
+    pub description: Option<Option<String>>,
+
+    /// Maximum amount of requests within a rolling 24 hour window.
+    #[validate(range(min = 0, message = "Requests per day cannot be negative"))]
+    pub requests_per_day: Option<i32>,
+
+    /// Maximum amount of requests within a calendar month (UTC), `null` removes the quota.
+    #[validate(range(min = 0, message = "Requests per month cannot be negative"))]
+    pub requests_per_month: Option<Option<i32>>,
+
+    /// Storage the todo attachments of a user may take up together (in MB), `null` restores the default.
+    #[serde(default, deserialize_with = "nullable")]
+    #[validate(range(min = 0, message = "Attachment quota cannot be negative"))]
+    pub attachment_quota_mb: Option<Option<i32>>,
+}
@@ -1,8 +1,11 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
-use chrono::NaiveDate;
-use utoipa::ToSchema;
+use chrono::{DateTime, NaiveDate, Utc};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+use crate::utils::validate::{validate_recurrence, validate_tags, validate_timezone};
 
 /// Represents a to-do item.
 #[derive(Deserialize, Debug, Serialize, FromRow, ToSchema)]
@@ -28,4 +31,485 @@ pub struct Todo {
     
     /// Whether the task is completed.
     pub completed: Option<bool>,
+
+    /// When the task is due (if ever).
+    pub due_at: Option<DateTime<Utc>>,
+
+    /// The time zone the due date was set in (IANA name, e.g. "Europe/Amsterdam").
+    pub due_timezone: Option<String>,
+
+    /// The priority: 0 (none), 1 (low), 2 (medium) or 3 (high).
+    pub priority: i16,
+
+    /// The tags of the task, sorted by name.
+    pub tags: Vec<String>,
+
+    /// The list the task belongs to (if any).
+    pub list_id: Option<Uuid>,
+
+    /// The task this is a subtask of (if any).
+    pub parent_id: Option<Uuid>,
+
+    /// Manual position of the task within its list, or within its parent for subtasks.
+    pub position: i32,
+
+    /// The amount of subtasks.
+    pub subtasks_total: i64,
+
+    /// The amount of completed subtasks.
+    pub subtasks_completed: i64,
+
+    /// The recurring series this task is an occurrence of (if any).
+    pub series_id: Option<Uuid>,
+
+    /// When this occurrence was scheduled by its series, also when its due date was moved.
+    pub occurrence_at: Option<DateTime<Utc>>,
+}
+
+/// Request body for creating a todo, its rules also apply to updated todos.
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct TodoBody {
+    #[validate(length(min = 3, max = 50))]
+    pub task: String,
+    #[validate(length(min = 3, max = 100))]
+    pub description: Option<String>,
+    /// When the task is due, as an RFC 3339 timestamp.
+    pub due_at: Option<DateTime<Utc>>,
+    /// Time zone of the due date (default: the `timezone` preference of the user).
+    #[validate(custom(function = "validate_timezone"))]
+    pub due_timezone: Option<String>,
+    /// 0 (none), 1 (low), 2 (medium) or 3 (high).
+    #[serde(default)]
+    #[validate(range(min = 0, max = 3))]
+    pub priority: i16,
+    /// Up to 20 tags of at most 30 characters.
+    #[serde(default)]
+    #[validate(custom(function = "validate_tags"))]
+    pub tags: Vec<String>,
+    /// The list to add the todo to, subtasks always belong to the list of their parent.
+    pub list_id: Option<Uuid>,
+    /// Makes the todo a subtask of this todo, which cannot be a subtask itself.
+    pub parent_id: Option<Uuid>,
+    /// RFC 5545 recurrence rule (e.g. "FREQ=MONTHLY;BYDAY=1MO"), repeating from the due date.
+    #[validate(custom(function = "validate_recurrence"))]
+    pub recurrence: Option<String>,
+}
+
+impl TodoBody {
+    /// Keeps the time zone only with a due date, falling back to the given (preferred) zone.
+    pub fn resolve_due_timezone(&mut self, preferred: &str) {
+        self.due_timezone = match self.due_at {
+            Some(_) => Some(self.due_timezone.take().unwrap_or_else(|| preferred.to_string())),
+            None => None,
+        };
+    }
+}
+
+/// Request body for updating a todo, fields that are left out stay unchanged.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TodoUpdateBody {
+    /// The new task description.
+    pub task: Option<String>,
+
+    /// The new detailed description, `null` removes it.
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<String>)]
+    pub description: Option<Option<String>>,
+
+    /// Marks the todo as completed (sets the completion date) or open again (clears it).
+    pub completed: Option<bool>,
+
+    /// The new due date, `null` removes it.
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<DateTime<Utc>>)]
+    pub due_at: Option<Option<DateTime<Utc>>>,
+
+    /// The new time zone of the due date.
+    pub due_timezone: Option<String>,
+
+    /// The new priority.
+    pub priority: Option<i16>,
+
+    /// The new tags, replacing all current tags.
+    pub tags: Option<Vec<String>>,
+}
+
+impl TodoUpdateBody {
+    /// Whether the body changes anything at all.
+    pub fn is_empty(&self) -> bool {
+        self.task.is_none()
+            && self.description.is_none()
+            && self.completed.is_none()
+            && self.due_at.is_none()
+            && self.due_timezone.is_none()
+            && self.priority.is_none()
+            && self.tags.is_none()
+    }
+
+    /// The todo as read with this update merged in, to be validated like a new todo.
+    pub fn merge_into(&self, todo: &Todo) -> TodoBody {
+        TodoBody {
+            task: self.task.clone().unwrap_or_else(|| todo.task.clone()),
+            description: self.description.clone().unwrap_or_else(|| todo.description.clone()),
+            due_at: self.due_at.unwrap_or(todo.due_at),
+            due_timezone: self.due_timezone.clone().or_else(|| todo.due_timezone.clone()),
+            priority: self.priority.unwrap_or(todo.priority),
+            tags: self.tags.clone().unwrap_or_else(|| todo.tags.clone()),
+            list_id: todo.list_id,
+            parent_id: todo.parent_id,
+            recurrence: None,
+        }
+    }
+}
+
+// Tells a field that was left out (`None`) apart from an explicit `null` (`Some(None)`)
+pub(crate) fn nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// Query parameters for filtering the list of todos
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct TodoListFilter {
+    /// "all", "open" or "completed" (default: the `default_todo_view` preference of the user).
+    #[serde(alias = "view")]
+    pub status: Option<String>,
+
+    /// Only todos with this tag (case-insensitive).
+    pub tag: Option<String>,
+
+    /// Only todos with this priority.
+    pub priority: Option<i16>,
+
+    /// Only todos due at or after this moment.
+    pub due_from: Option<DateTime<Utc>>,
+
+    /// Only todos due at or before this moment.
+    pub due_to: Option<DateTime<Utc>>,
+
+    /// Only todos shared with the user (`true`) or owned by the user (`false`), default: both.
+    pub shared: Option<bool>,
+
+    /// Only todos in this list.
+    pub list_id: Option<Uuid>,
+
+    /// Only subtasks of this todo.
+    pub parent_id: Option<Uuid>,
+}
+
+/// Request body for moving a todo to another list or position.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TodoMoveBody {
+    /// The list to move the todo to, `null` removes it from its list. Left out, it stays in its list.
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<Uuid>)]
+    pub list_id: Option<Option<Uuid>>,
+
+    /// The new position, the todos from this position on move down. Left out, the todo moves to the end.
+    pub position: Option<i32>,
+}
+
+/// A named list of todos, like a project.
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct TodoList {
+    /// The unique identifier of the list.
+    pub id: Uuid,
+
+    /// The owner of the list.
+    pub user_id: Uuid,
+
+    /// The name of the list, unique per user.
+    pub name: String,
+
+    /// An optional description of the list.
+    pub description: Option<String>,
+
+    /// Manual position of the list among the lists of the user.
+    pub position: i32,
+
+    /// When the list was created.
+    pub creation_date: DateTime<Utc>,
+
+    /// The amount of todos in the list, including subtasks.
+    pub todos_total: i64,
+
+    /// The amount of completed todos in the list.
+    pub todos_completed: i64,
+}
+
+/// Request body for creating a list, its rules also apply to updated lists.
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct TodoListBody {
+    #[validate(length(min = 1, max = 50))]
+    pub name: String,
+    #[validate(length(max = 200))]
+    pub description: Option<String>,
+}
+
+/// Request body for updating a list, fields that are left out stay unchanged.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TodoListUpdateBody {
+    /// The new name.
+    pub name: Option<String>,
+
+    /// The new description, `null` removes it.
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<String>)]
+    pub description: Option<Option<String>>,
+
+    /// The new position, the lists from this position on move down.
+    pub position: Option<i32>,
+}
+
+impl TodoListUpdateBody {
+    /// Whether the body changes anything at all.
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none() && self.description.is_none() && self.position.is_none()
+    }
+}
+
+/// Permission of a user a todo is shared with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TodoPermission {
+    /// Can view the todo.
+    Viewer,
+    /// Can view and update the todo, but not delete or share it.
+    Editor,
+}
+
+impl TodoPermission {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TodoPermission::Viewer => "viewer",
+            TodoPermission::Editor => "editor",
+        }
+    }
+}
+
+/// A user a todo is shared with.
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct TodoShare {
+    /// The shared todo.
+    pub todo_id: Uuid,
+
+    /// The user the todo is shared with.
+    pub user_id: Uuid,
+
+    /// The username of the user the todo is shared with.
+    pub username: String,
+
+    /// "viewer" or "editor".
+    pub permission: String,
+
+    /// The owner who shared the todo.
+    pub shared_by: Uuid,
+
+    /// When the todo was shared, or the permission last changed.
+    pub created_at: DateTime<Utc>,
+}
+
+/// A user a list is shared with, which gives access to all todos in the list.
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct TodoListShare {
+    /// The shared list.
+    pub list_id: Uuid,
+
+    /// The user the list is shared with.
+    pub user_id: Uuid,
+
+    /// The username of the user the list is shared with.
+    pub username: String,
+
+    /// "viewer" or "editor", for every todo in the list.
+    pub permission: String,
+
+    /// The owner who shared the list.
+    pub shared_by: Uuid,
+
+    /// When the list was shared, or the permission last changed.
+    pub created_at: DateTime<Utc>,
+}
+
+/// Request body for sharing a todo or a list, sharing it again changes the permission.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TodoShareBody {
+    /// Username of the (active) user to share with, in the same tenant as the owner.
+    pub username: String,
+
+    /// "viewer" or "editor".
+    pub permission: TodoPermission,
+}
+
+/// A recurring todo: completing an occurrence creates the next one from this template.
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct TodoSeries {
+    /// The unique identifier of the series.
+    pub id: Uuid,
+
+    /// The owner of the series.
+    pub user_id: Uuid,
+
+    /// The RFC 5545 recurrence rule.
+    pub rrule: String,
+
+    /// The first occurrence, the rule repeats from here.
+    pub dtstart: DateTime<Utc>,
+
+    /// The time zone the rule is expanded in, so occurrences keep their local time.
+    pub timezone: String,
+
+    /// The task of new occurrences.
+    pub task: String,
+
+    /// The description of new occurrences.
+    pub description: Option<String>,
+
+    /// The priority of new occurrences.
+    pub priority: i16,
+
+    /// The tags of new occurrences.
+    pub tags: Vec<String>,
+
+    /// The list new occurrences are added to.
+    pub list_id: Option<Uuid>,
+
+    /// When the series was created.
+    pub created_at: DateTime<Utc>,
+
+    /// The latest occurrence that has been created as a todo.
+    pub latest_occurrence_at: Option<DateTime<Utc>>,
+
+    /// The occurrences that are skipped.
+    pub skipped: Vec<DateTime<Utc>>,
+}
+
+/// Request body for updating a series, changes apply to the occurrences created from now on.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TodoSeriesUpdateBody {
+    /// The new recurrence rule, repeating from the original start.
+    pub rrule: Option<String>,
+
+    /// The new task.
+    pub task: Option<String>,
+
+    /// The new description, `null` removes it.
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<String>)]
+    pub description: Option<Option<String>>,
+
+    /// The new priority.
+    pub priority: Option<i16>,
+
+    /// The new tags.
+    pub tags: Option<Vec<String>>,
+}
+
+impl TodoSeriesUpdateBody {
+    /// Whether the body changes anything at all.
+    pub fn is_empty(&self) -> bool {
+        self.rrule.is_none()
+            && self.task.is_none()
+            && self.description.is_none()
+            && self.priority.is_none()
+            && self.tags.is_none()
+    }
+}
+
+/// Request body for skipping a single occurrence of a series.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TodoSeriesSkipBody {
+    /// The scheduled time of the occurrence, as listed by the preview.
+    pub occurrence_at: DateTime<Utc>,
+}
+
+/// An upcoming occurrence of a series.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TodoOccurrence {
+    /// The scheduled time of the occurrence.
+    pub occurrence_at: DateTime<Utc>,
+
+    /// Whether the occurrence is skipped.
+    pub skipped: bool,
+}
+
+/// Query parameters for previewing the upcoming occurrences of a series
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct TodoSeriesPreviewQuery {
+    /// Amount of occurrences (default: 10, max: 100).
+    pub limit: Option<usize>,
+}
+
+/// A file attached to a todo.
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct TodoAttachment {
+    /// The unique identifier of the attachment.
+    pub id: Uuid,
+
+    /// The todo the file is attached to.
+    pub todo_id: Uuid,
+
+    /// The owner of the todo, whose storage quota the file counts towards.
+    pub user_id: Uuid,
+
+    /// The user who uploaded the file, if that user still exists.
+    pub uploaded_by: Option<Uuid>,
+
+    /// The original name of the file.
+    pub file_name: String,
+
+    /// The content type of the file.
+    pub content_type: String,
+
+    /// The size of the file in bytes.
+    pub size: i64,
+
+    /// The key of the object in the attachments bucket.
+    #[serde(skip)]
+    pub object_key: String,
+
+    /// When the file was uploaded.
+    pub created_at: DateTime<Utc>,
+}
+
+/// An attachment with a temporary link to download it.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TodoAttachmentResponse {
+    #[serde(flatten)]
+    pub attachment: TodoAttachment,
+
+    /// Temporary link to download the file.
+    pub download_url: String,
+}
+
+/// Multipart body for uploading an attachment.
+#[allow(dead_code)]
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TodoAttachmentUploadBody {
+    /// The file to attach.
+    #[schema(format = Binary, value_type = String)]
+    pub file: String,
+}
+
+/// An upcoming open todo that may need reminders, used by the reminder emails.
+#[derive(Debug)]
+pub struct TodoReminderRow {
+    pub todo_id: Uuid,
+    pub task: String,
+    pub due_at: DateTime<Utc>,
+    pub due_timezone: Option<String>,
+    pub user_id: Uuid,
+    pub email: String,
+    pub language_code: Option<String>,
+    pub preferences: serde_json::Value,
+
+    /// The lead times (in minutes) already claimed for this due date.
+    pub claimed: Vec<i32>,
 }
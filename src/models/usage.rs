@@ -1,5 +1,7 @@
-use serde::Serialize;
-use utoipa::ToSchema;
+use serde::{Deserialize, Serialize};
+use chrono::NaiveDate;
+use uuid::Uuid;
+use utoipa::{IntoParams, ToSchema};
 
 /// Represents the usage statistics for the last 24 hours.
 #[derive(Debug, Serialize, ToSchema)]
@@ -16,3 +18,61 @@ pub struct UsageResponseLastWeek {
     #[serde(rename = "requests_last_7_days")]
     pub count: i64
 }
+
+/// Represents the usage of a single day, taken from the daily aggregates.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UsageResponseDaily {
+    /// The day (UTC) the requests were made on.
+    pub day: NaiveDate,
+    /// The number of requests made on that day.
+    pub count: i64
+}
+
+/// Query parameters for the usage history.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct UsageHistoryQuery {
+    /// The number of days to return, including today (1-365, default 30).
+    pub days: Option<i32>
+}
+
+/// Represents the monthly quota consumption of the current user.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UsageQuotaResponse {
+    /// The first day (UTC) of the current period.
+    pub period_start: NaiveDate,
+    /// The day (UTC) on which the quota resets.
+    pub reset_date: NaiveDate,
+    /// The number of requests made in the current period.
+    pub requests_this_period: i64,
+    /// The monthly quota of the user's tier, if any.
+    pub requests_per_month: Option<i64>,
+    /// The number of requests left in the current period, if there is a monthly quota.
+    pub remaining: Option<i64>,
+    /// The percentage of the monthly quota that has been used, if there is a monthly quota.
+    pub percentage_used: Option<f64>,
+    /// The daily limit of the user's tier.
+    pub requests_per_day: i64,
+}
+
+/// Monthly quota consumption of a single user, used for the threshold notifications.
+#[derive(Debug)]
+pub struct UsageQuotaRow {
+    pub user_id: Uuid,
+    pub email: String,
+    pub language_code: Option<String>,
+    pub requests_per_month: i32,
+    pub count: i64,
+}
+
+/// Daily usage per endpoint, as included in a data export.
+#[derive(Debug, Serialize)]
+pub struct UsageExportRow {
+    /// The day (UTC).
+    pub day: NaiveDate,
+
+    /// The requested endpoint.
+    pub endpoint: String,
+
+    /// Amount of requests.
+    pub count: i64,
+}
@@ -2,9 +2,12 @@ use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
 use chrono::{NaiveDate, NaiveDateTime, DateTime, Utc};
-use utoipa::ToSchema;
+use chrono_tz::Tz;
+use utoipa::{IntoParams, ToSchema};
 use validator::Validate;
 
+use crate::i18n::catalog::t_with;
+use crate::i18n::locale::Locale;
 use crate::utils::validate::{validate_password, validate_username, validate_birthday, validate_country_code, validate_language_code};
 
 /// Database model (SQLx compatible)
@@ -23,10 +26,12 @@ pub struct UserRow {
     pub language_code: Option<String>,
     pub birthday: Option<NaiveDate>,
     pub description: Option<String>,
+    pub tenant: Option<String>,
     pub password_hash: String,
     pub totp_secret: Option<String>,
     pub verification_code: Option<String>,
     pub verification_expires_at: Option<DateTime<Utc>>,
+    pub tokens_valid_after: Option<DateTime<Utc>>,
 }
 
 /// Internal domain model (non-SQLx)
@@ -45,17 +50,20 @@ pub struct User {
     pub language_code: Option<String>,
     pub birthday: Option<NaiveDate>,
     pub description: Option<String>,
+    pub tenant: Option<String>,
     #[serde(skip)]
     pub password_hash: String,
     #[serde(skip)]
     pub totp_secret: Option<String>,
     pub verification_code: Option<String>,
     pub verification_expires_at: Option<DateTime<Utc>>,
+    #[serde(skip)]
+    pub tokens_valid_after: Option<DateTime<Utc>>,
 }
 
 
 /// Public user response
-#[derive(Debug, Serialize, Clone, ToSchema)]
+#[derive(Debug, Serialize, Clone, FromRow, ToSchema)]
 pub struct UserGetResponse {
     pub id: Uuid,
     pub username: String,
@@ -70,6 +78,68 @@ pub struct UserGetResponse {
     pub language_code: Option<String>,
     pub birthday: Option<NaiveDate>,
     pub description: Option<String>,
+    /// The tenant (organization) of the user, todos are only shared within a tenant.
+    pub tenant: Option<String>,
+}
+
+/// Filters for listing users
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct UserListFilter {
+    /// "pending", "active", "disabled" or "all" (default: "active").
+    pub status: Option<String>,
+
+    /// Only users with this role level.
+    pub role_level: Option<i32>,
+
+    /// Only users with this tier level.
+    pub tier_level: Option<i32>,
+
+    /// Only users from this country (ISO 3166-1 alpha-2).
+    pub country_code: Option<String>,
+
+    /// Only users created on or after this date.
+    pub created_from: Option<NaiveDate>,
+
+    /// Only users created on or before this date.
+    pub created_to: Option<NaiveDate>,
+
+    /// Only users of this tenant.
+    pub tenant: Option<String>,
+}
+
+/// Query parameters for searching users
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct UserSearchQuery {
+    /// Search terms, matched (partially) against the username, email, name and description.
+    pub q: String,
+
+    /// Maximum amount of results (default: 20, max: 100).
+    pub limit: Option<i64>,
+}
+
+/// Query parameters for fetching an avatar
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct AvatarQuery {
+    /// Requested size in pixels, rounded up to the nearest variant (64, 128, 300 or 600; default: 300).
+    pub size: Option<u32>,
+
+    /// "redirect" to a pre-signed URL (default) or "stream" the image itself.
+    pub mode: Option<String>,
+}
+
+/// A user matching a search, with its relevance
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct UserSearchResult {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub user: UserGetResponse,
+
+    /// Relevance of the match, higher is better.
+    pub rank: f32,
+
+    /// Matching fields, HTML escaped with the matches wrapped in `<mark>` tags.
+    #[sqlx(skip)]
+    pub highlights: std::collections::HashMap<String, String>,
 }
 
 /// Request body for user creation
@@ -159,6 +229,9 @@ pub struct UserUpdateBody {
     pub role_level: Option<i32>,  // Added the role_level field to the update body
 
     pub tier_level: Option<i32>,  // Added the role_level field to the update body
+
+    #[validate(length(min = 1, max = 100))]
+    pub tenant: Option<Option<String>>,  // Admins only, null removes the user from its tenant
 }
 
 /// Response for user updates
@@ -197,10 +270,12 @@ impl From<UserRow> for User {
             language_code: row.language_code,
             birthday: row.birthday,
             description: row.description,
+            tenant: row.tenant,
             password_hash: row.password_hash,
             totp_secret: row.totp_secret,
             verification_code: row.verification_code,
             verification_expires_at: row.verification_expires_at,
+            tokens_valid_after: row.tokens_valid_after,
         }
     }
 }
@@ -221,6 +296,7 @@ impl From<User> for UserGetResponse {
             language_code: user.language_code,
             birthday: user.birthday,
             description: user.description,
+            tenant: user.tenant,
         }
     }
 }
@@ -288,7 +364,7 @@ pub struct UserPasswordResetCode {
 #[derive(Debug, Deserialize, Validate, ToSchema)]
 #[allow(dead_code)]
 pub struct UserRegisterBody {
-    #[validate(length(min = 3, max = 50))]
+    #[validate(length(min = 3, max = 50), custom(function = "validate_username"))]
     pub username: String,
 
     #[validate(email)]
@@ -324,4 +400,113 @@ pub struct UserRegisterEmailVerifyBody {
     #[validate(email)]
     pub email: String,
     pub code: String,
-}
\ No newline at end of file
+}
+
+/// Data sent to request a new verification code for a pending registration
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct UserRegisterResendBody {
+    #[validate(email)]
+    pub email: String,
+}
+
+/// Data sent to change a username
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UsernameChangeBody {
+    #[validate(length(min = 3, max = 50), custom(function = "validate_username"))]
+    pub username: String,
+}
+
+/// A changed username
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UsernameChangeResponse {
+    pub id: Uuid,
+    pub username: String,
+    pub previous_username: String,
+    pub changed_at: DateTime<Utc>,
+    /// Moment the user can change its username again.
+    pub next_change_at: DateTime<Utc>,
+}
+
+/// Data sent by a user to delete its own account
+#[derive(Deserialize, ToSchema)]
+pub struct UserDeleteBody {
+    /// The current password, to confirm the deletion.
+    pub password: String,
+}
+
+/// A user that has been scheduled for deletion
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UserDeletion {
+    pub id: Uuid,
+    #[serde(skip)]
+    pub email: String,
+    #[serde(skip)]
+    pub language_code: Option<String>,
+    /// Moment the user and all its data will be purged, until then the user can be restored.
+    pub purge_after: DateTime<Utc>,
+}
+
+/// A user whose grace period has passed, claimed for purging
+#[derive(Debug)]
+pub struct UserPurge {
+    pub id: Uuid,
+    pub email: String,
+    pub language_code: Option<String>,
+    pub profile_picture_url: Option<String>,
+}
+
+/// Request body for suspending a user
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct UserSuspendBody {
+    /// Reason of the suspension, shown to the user.
+    #[validate(length(min = 1, max = 500))]
+    pub reason: String,
+
+    /// Moment the suspension ends automatically, leave empty to suspend until reinstated.
+    pub until: Option<DateTime<Utc>>,
+}
+
+/// A suspended user
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UserSuspension {
+    pub id: Uuid,
+    #[serde(skip)]
+    pub email: String,
+    #[serde(skip)]
+    pub language_code: Option<String>,
+    pub suspension_reason: String,
+    pub suspended_at: DateTime<Utc>,
+    pub suspended_until: Option<DateTime<Utc>>,
+}
+
+impl UserSuspension {
+    /// The language of the suspended user, otherwise the given locale.
+    pub fn locale(&self, otherwise: Locale) -> Locale {
+        Locale::for_user(self.language_code.as_deref(), otherwise)
+    }
+
+    /// Message shown to the suspended user when signing in.
+    pub fn message(&self, locale: Locale) -> String {
+        self.message_in_timezone(locale, Tz::UTC)
+    }
+
+    /// Message for the suspended user, with the end date in the given time zone.
+    pub fn message_in_timezone(&self, locale: Locale, timezone: Tz) -> String {
+        match self.suspended_until {
+            Some(until) => {
+                let until = until.with_timezone(&timezone).format("%Y-%m-%d %H:%M %Z").to_string();
+                t_with(locale, "suspension.message_until", &[("until", &until), ("reason", &self.suspension_reason)])
+            }
+            None => t_with(locale, "suspension.message", &[("reason", &self.suspension_reason)]),
+        }
+    }
+}
+
+/// A user whose suspension has ended
+#[derive(Debug)]
+pub struct UserReinstatement {
+    pub id: Uuid,
+    pub email: String,
+    pub language_code: Option<String>,
+}
@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::NaiveDate;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::models::user::UserInsertBody;
+
+/// Options of a bulk import
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct UserImportQuery {
+    /// Only validate the rows, without creating any user (default: false).
+    pub dry_run: Option<bool>,
+
+    /// "transactional" (all rows or none, default) or "partial" (every valid row).
+    pub mode: Option<String>,
+
+    /// Email every created user an invitation to set a password (default: false).
+    pub send_invitations: Option<bool>,
+}
+
+/// A single user in a CSV or JSON import.
+///
+/// CSV files use the field names as header; the password may be left empty when invitations are sent.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UserImportRow {
+    pub username: String,
+    pub email: String,
+    pub password: Option<String>,
+    pub totp: Option<bool>,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub country_code: Option<String>,
+    pub language_code: Option<String>,
+    pub birthday: Option<NaiveDate>,
+    pub description: Option<String>,
+}
+
+impl UserImportRow {
+    /// Converts the row into the body used for creating a single user, so it is validated the same way.
+    pub fn into_insert_body(self, password: String) -> UserInsertBody {
+        UserInsertBody {
+            username: self.username.trim().to_string(),
+            email: self.email.trim().to_lowercase(),
+            password,
+            totp: self.totp,
+            first_name: self.first_name,
+            last_name: self.last_name,
+            country_code: self.country_code,
+            language_code: self.language_code,
+            birthday: self.birthday,
+            description: self.description,
+            profile_picture_url: None,
+        }
+    }
+}
+
+/// Outcome of a single row of an import.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UserImportRowResult {
+    /// Row number, starting at 1 (the CSV header is not counted).
+    pub row: usize,
+    pub username: Option<String>,
+    pub email: Option<String>,
+
+    /// "valid" (dry run), "created", "invalid", "failed" or "skipped".
+    pub status: String,
+
+    /// ID of the created user.
+    pub id: Option<Uuid>,
+    pub errors: Vec<String>,
+}
+
+/// Report of an import.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UserImportResponse {
+    pub dry_run: bool,
+    pub mode: String,
+    pub total: usize,
+    pub created: usize,
+    pub failed: usize,
+    pub invitations_sent: usize,
+    pub rows: Vec<UserImportRowResult>,
+}
+
+/// A validated row, ready to be inserted.
+#[derive(Debug)]
+pub struct UserImportInsert {
+    pub user: UserInsertBody,
+    pub password_hash: String,
+    pub totp_secret: String,
+}
@@ -0,0 +1,17 @@
+use axum::Router;
+use crate::routes::AppState;
+use std::sync::Arc;
+
+use crate::handlers::get_audit_log::{get_audit_log, get_audit_log_export, get_audit_log_verify};
+use crate::wrappers::authentication_route_builder::AuthenticatedRouteBuilder;
+
+pub fn create_audit_routes(state: Arc<AppState>) -> Router<Arc<AppState>> {
+    AuthenticatedRouteBuilder::new(state)
+        // Route for getting a page of audit log entries (requires role 2)
+        .get("/all", get_audit_log, vec![2])
+        // Route for exporting the audit log (requires role 2)
+        .get("/export", get_audit_log_export, vec![2])
+        // Route for verifying the hash chain of the audit log (requires role 2)
+        .get("/verify", get_audit_log_verify, vec![2])
+        .build()
+}
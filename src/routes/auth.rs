@@ -7,7 +7,7 @@ use crate::wrappers::authentication_route_builder::AuthenticatedRouteBuilder;
 
 pub fn create_auth_routes(state: Arc<AppState>) -> Router<Arc<AppState>> {
     AuthenticatedRouteBuilder::new(state)
-        .unauthenticated_post("/login", login)
+        .throttled_post("/login", login, "login")
         .get("/protected", protected, vec![1, 2])
         .build()
 }
\ No newline at end of file
@@ -0,0 +1,26 @@
+use axum::Router;
+use crate::routes::AppState;
+use std::sync::Arc;
+
+use crate::handlers::{
+    get_billing::{get_billing_prices, get_billing_statements, get_billing_statement_by_id, get_billing_statement_export},
+    post_billing::{post_billing_price, post_billing_statements_generate}
+};
+use crate::wrappers::authentication_route_builder::AuthenticatedRouteBuilder;
+
+pub fn create_billing_routes(state: Arc<AppState>) -> Router<Arc<AppState>> {
+    AuthenticatedRouteBuilder::new(state)
+        // Route for getting the price list
+        .get("/prices", get_billing_prices, vec![1, 2])
+        // Route for adding a price to a tier (requires role 2)
+        .post("/prices/new", post_billing_price, vec![2])
+        // Route for getting the statements of the current user (admins can pass a user_id)
+        .get("/statements", get_billing_statements, vec![1, 2])
+        // Route for generating the statements of a month (requires role 2)
+        .post("/statements/generate", post_billing_statements_generate, vec![2])
+        // Route for getting a statement by ID
+        .get("/statements/{id}", get_billing_statement_by_id, vec![1, 2])
+        // Route for downloading a statement as CSV or HTML
+        .get("/statements/{id}/export", get_billing_statement_export, vec![1, 2])
+        .build()
+}
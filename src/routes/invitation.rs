@@ -0,0 +1,29 @@
+use axum::Router;
+use crate::routes::AppState;
+use std::sync::Arc;
+
+use crate::handlers::{
+    get_invitations::{get_all_invitations, get_invitation_by_id, get_invitation_preview},
+    post_invitations::{post_invitation, post_invitation_resend, post_invitation_accept},
+    delete_invitations::delete_invitation_by_id
+};
+use crate::wrappers::authentication_route_builder::AuthenticatedRouteBuilder;
+
+pub fn create_invitation_routes(state: Arc<AppState>) -> Router<Arc<AppState>> {
+    AuthenticatedRouteBuilder::new(state)
+        // Route for getting all invitations (requires role 2)
+        .get("/all", get_all_invitations, vec![2])
+        // Route for inviting a new user (requires role 2)
+        .post("/new", post_invitation, vec![2])
+        // Route for checking an invitation link (unauthenticated, throttled)
+        .throttled_get("/accept", get_invitation_preview, "invitation-accept")
+        // Route for accepting an invitation (unauthenticated, throttled)
+        .throttled_post("/accept", post_invitation_accept, "invitation-accept")
+        // Route for sending an invitation again (requires role 2)
+        .post("/{id}/resend", post_invitation_resend, vec![2])
+        // Route for getting an invitation by ID (requires role 2)
+        .get("/{id}", get_invitation_by_id, vec![2])
+        // Route for revoking an invitation (requires role 2)
+        .delete("/{id}", delete_invitation_by_id, vec![2])
+        .build()
+}
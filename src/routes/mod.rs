@@ -1,8 +1,12 @@
 pub mod homepage;
 pub mod apikey;
+pub mod audit;
+pub mod billing;
 pub mod auth;
 pub mod health;
+pub mod invitation;
 pub mod todo;
+pub mod tier;
 pub mod usage;
 pub mod user;
 pub mod referencedata;
@@ -35,6 +39,10 @@ use self::{
     user::{create_user_root_routes, create_user_routes},
     apikey::create_apikey_routes,
     usage::create_usage_routes,
+    tier::create_tier_routes,
+    billing::create_billing_routes,
+    invitation::create_invitation_routes,
+    audit::create_audit_routes,
     auth::create_auth_routes,
     homepage::create_homepage_route,
     health::create_health_route,
@@ -86,10 +94,22 @@ impl Modify for SecurityAddon {
     paths(
         handlers::get_users::get_all_users,
         handlers::get_users::get_users_by_id,
+        handlers::get_users::get_user_avatar,
+        handlers::get_users::search_users,
         handlers::get_apikeys::get_all_apikeys,
         handlers::get_apikeys::get_apikeys_by_id,
         handlers::get_usage::get_usage_last_day,
         handlers::get_usage::get_usage_last_week,
+        handlers::get_usage::get_usage_history,
+        handlers::get_usage::get_usage_quota,
+        handlers::get_billing::get_billing_prices,
+        handlers::get_billing::get_billing_statements,
+        handlers::get_billing::get_billing_statement_by_id,
+        handlers::get_billing::get_billing_statement_export,
+        handlers::post_billing::post_billing_price,
+        handlers::post_billing::post_billing_statements_generate,
+        handlers::get_tiers::get_all_tiers,
+        handlers::get_tiers::get_tiers_by_id,
         handlers::get_todos::get_all_todos,
         handlers::get_todos::get_todos_by_id,
         handlers::get_health::get_health,
@@ -97,16 +117,62 @@ impl Modify for SecurityAddon {
         handlers::post_users::post_user,
         handlers::post_users::post_user_register_verify,
         handlers::post_users::post_user_register,
+        handlers::post_users::post_user_register_resend,
         handlers::post_users::post_user_password_reset_verify,
         handlers::post_users::post_user_password_reset,
         handlers::post_users::post_user_profilepicture,
+        handlers::post_data_exports::post_data_export,
+        handlers::get_data_exports::get_data_export_by_id,
+        handlers::get_preferences::get_current_user_preferences,
+        handlers::patch_preferences::patch_current_user_preferences,
         handlers::patch_users::patch_user_profile,
+        handlers::patch_users::patch_username,
         handlers::post_apikeys::post_apikey,
         handlers::post_todos::post_todo,
+        handlers::patch_todos::patch_todo_by_id,
+        handlers::post_tiers::post_tier,
+        handlers::patch_tiers::patch_tier,
         handlers::rotate_apikeys::rotate_apikey,
         handlers::delete_users::delete_user_by_id,
+        handlers::delete_users::delete_current_user,
+        handlers::post_users::post_user_restore,
+        handlers::import_users::import_users,
+        handlers::suspend_users::suspend_user,
+        handlers::suspend_users::reinstate_user,
+        handlers::get_invitations::get_all_invitations,
+        handlers::get_invitations::get_invitation_by_id,
+        handlers::get_invitations::get_invitation_preview,
+        handlers::post_invitations::post_invitation,
+        handlers::post_invitations::post_invitation_resend,
+        handlers::post_invitations::post_invitation_accept,
+        handlers::delete_invitations::delete_invitation_by_id,
+        handlers::get_audit_log::get_audit_log,
+        handlers::get_audit_log::get_audit_log_export,
+        handlers::get_audit_log::get_audit_log_verify,
         handlers::delete_apikeys::delete_apikey_by_id,
         handlers::delete_todos::delete_todo_by_id,
+        handlers::share_todos::get_todo_shares,
+        handlers::share_todos::post_todo_share,
+        handlers::share_todos::delete_todo_share,
+        handlers::share_todos::get_todo_list_shares,
+        handlers::share_todos::post_todo_list_share,
+        handlers::share_todos::delete_todo_list_share,
+        handlers::move_todos::move_todo,
+        handlers::get_todo_lists::get_all_todo_lists,
+        handlers::get_todo_lists::get_todo_list_by_id,
+        handlers::post_todo_lists::post_todo_list,
+        handlers::patch_todo_lists::patch_todo_list_by_id,
+        handlers::delete_todo_lists::delete_todo_list_by_id,
+        handlers::get_todo_series::get_todo_series_by_id,
+        handlers::get_todo_series::get_todo_series_preview,
+        handlers::patch_todo_series::patch_todo_series_by_id,
+        handlers::delete_todo_series::delete_todo_series_by_id,
+        handlers::skip_todo_series::post_todo_series_skip,
+        handlers::todo_attachments::post_todo_attachment,
+        handlers::todo_attachments::get_todo_attachments,
+        handlers::todo_attachments::get_todo_attachment,
+        handlers::todo_attachments::delete_todo_attachment,
+        handlers::delete_tiers::delete_tier_by_id,
         handlers::protected::protected,
         handlers::login::login,
     ),
@@ -123,7 +189,16 @@ impl Modify for SecurityAddon {
             models::apikey::ApiKeyRotateResponse,
             models::apikey::ApiKeyRotateResponseInfo,
             models::apikey::ApiKeyRotateBody,
+            models::audit::AuditLogEntry,
+            models::audit::AuditLogVerification,
             models::auth::Claims,
+            models::billing::BillingPrice,
+            models::billing::BillingPriceInsertBody,
+            models::billing::BillingStatement,
+            models::billing::BillingStatementGenerateBody,
+            models::billing::BillingStatementGenerateResponse,
+            models::data_export::DataExport,
+            models::data_export::DataExportResponse,
             models::documentation::SuccessResponse,
             models::documentation::ErrorResponse,
             models::health::HealthResponse,
@@ -131,18 +206,58 @@ impl Modify for SecurityAddon {
             models::health::DatabaseStatus,
             models::health::DiskUsage,
             models::health::MemoryStatus,
+            models::invitation::Invitation,
+            models::invitation::InvitationInsertBody,
+            models::invitation::InvitationPreviewResponse,
+            models::invitation::InvitationAcceptBody,
+            models::preference::UserPreferences,
+            models::preference::Theme,
+            models::preference::TodoView,
             models::role::Role,
+            models::tier::Tier,
+            models::tier::TierInsertBody,
+            models::tier::TierUpdateBody,
             models::todo::Todo,
+            models::todo::TodoBody,
+            models::todo::TodoUpdateBody,
+            models::todo::TodoPermission,
+            models::todo::TodoListShare,
+            models::todo::TodoShare,
+            models::todo::TodoShareBody,
+            models::todo::TodoMoveBody,
+            models::todo::TodoList,
+            models::todo::TodoListBody,
+            models::todo::TodoListUpdateBody,
+            models::todo::TodoSeries,
+            models::todo::TodoSeriesUpdateBody,
+            models::todo::TodoSeriesSkipBody,
+            models::todo::TodoOccurrence,
+            models::todo::TodoAttachment,
+            models::todo::TodoAttachmentResponse,
+            models::todo::TodoAttachmentUploadBody,
             models::usage::UsageResponseLastDay,
             models::usage::UsageResponseLastWeek,
+            models::usage::UsageResponseDaily,
+            models::usage::UsageQuotaResponse,
             models::user::User,
             models::user::UserGetResponse,
+            models::user::UserSearchResult,
+            models::user::UserDeleteBody,
+            models::user::UserDeletion,
+            models::user::UserSuspendBody,
+            models::user::UserSuspension,
+            models::user_import::UserImportRow,
+            models::user_import::UserImportRowResult,
+            models::user_import::UserImportResponse,
             models::user::UserInsertBody,
             models::user::UserInsertResponse,
             models::user::UserUpdateBody,
             models::user::UserUpdateResponse,
+            models::user::UsernameChangeBody,
+            models::user::UsernameChangeResponse,
             models::user::UserRegisterEmailVerifyBody,
             models::user::UserRegisterBody,
+            models::user::UserRegisterResendBody,
             models::user::UserPasswordResetCode,
             models::user::UserPasswordResetConfirmBody,
             models::user::UserPasswordResetRequestBody
@@ -152,6 +267,10 @@ impl Modify for SecurityAddon {
         (name = "user", description = "User related endpoints."),
         (name = "apikey", description = "API key related endpoints."),
         (name = "usage", description = "Usage related endpoints."),
+        (name = "tier", description = "Tier related endpoints."),
+        (name = "billing", description = "Billing related endpoints."),
+        (name = "invitation", description = "Invitation related endpoints."),
+        (name = "audit", description = "Security audit log endpoints."),
         (name = "todo", description = "Todo related endpoints."),
         (name = "health", description = "Health check endpoint."),
     )
@@ -177,6 +296,10 @@ pub fn create_routes(state: Arc<AppState>) -> Router<()> {
         .nest("/users", create_user_routes(state.clone()))
         .nest("/apikeys", create_apikey_routes(state.clone()))
         .nest("/usage", create_usage_routes(state.clone()))
+        .nest("/tiers", create_tier_routes(state.clone()))
+        .nest("/billing", create_billing_routes(state.clone()))
+        .nest("/invitations", create_invitation_routes(state.clone()))
+        .nest("/audit", create_audit_routes(state.clone()))
         .nest("/todos", create_todo_routes(state.clone()))
         .merge(create_health_route(state.clone()))
         .with_state(state)
@@ -9,6 +9,6 @@ use crate::wrappers::authentication_route_builder::AuthenticatedRouteBuilder;
 pub fn create_referencedata_routes(state: Arc<AppState>) -> Router<Arc<AppState>> {
     AuthenticatedRouteBuilder::new(state)
         // Route for getting the usage from the last day
-        .unauthenticated_get("/referencedata/{id}", get_referencedata)
+        .throttled_get("/referencedata/{id}", get_referencedata, "referencedata")
         .build()
 }
\ No newline at end of file
@@ -0,0 +1,26 @@
+use axum::Router;
+use crate::routes::AppState;
+use std::sync::Arc;
+
+use crate::handlers::{
+    get_tiers::{get_all_tiers, get_tiers_by_id},
+    post_tiers::post_tier,
+    patch_tiers::patch_tier,
+    delete_tiers::delete_tier_by_id
+};
+use crate::wrappers::authentication_route_builder::AuthenticatedRouteBuilder;
+
+pub fn create_tier_routes(state: Arc<AppState>) -> Router<Arc<AppState>> {
+    AuthenticatedRouteBuilder::new(state)
+        // Route for getting all tiers
+        .get("/all", get_all_tiers, vec![1, 2])
+        // Route for creating a new tier (requires role 2)
+        .post("/new", post_tier, vec![2])
+        // Route for getting a tier by ID
+        .get("/{id}", get_tiers_by_id, vec![1, 2])
+        // Route for updating a tier (requires role 2)
+        .patch("/{id}", patch_tier, vec![2])
+        // Route for deleting a tier by ID (requires role 2)
+        .delete("/{id}", delete_tier_by_id, vec![2])
+        .build()
+}
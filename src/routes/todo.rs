@@ -1,15 +1,35 @@
 use axum::Router;
+use axum::extract::DefaultBodyLimit;
 use crate::routes::AppState;
 use std::sync::Arc;
 
 use crate::handlers::{
     get_todos::{get_all_todos, get_todos_by_id},
     post_todos::post_todo,
-    delete_todos::delete_todo_by_id
+    patch_todos::patch_todo_by_id,
+    delete_todos::delete_todo_by_id,
+    share_todos::{get_todo_shares, post_todo_share, delete_todo_share, get_todo_list_shares, post_todo_list_share, delete_todo_list_share},
+    move_todos::move_todo,
+    get_todo_lists::{get_all_todo_lists, get_todo_list_by_id},
+    post_todo_lists::post_todo_list,
+    patch_todo_lists::patch_todo_list_by_id,
+    delete_todo_lists::delete_todo_list_by_id,
+    get_todo_series::{get_todo_series_by_id, get_todo_series_preview},
+    patch_todo_series::patch_todo_series_by_id,
+    delete_todo_series::delete_todo_series_by_id,
+    skip_todo_series::post_todo_series_skip,
+    todo_attachments::{get_todo_attachments, get_todo_attachment, post_todo_attachment, delete_todo_attachment}
 };
+use crate::utils::attachments::max_attachment_size;
 use crate::wrappers::authentication_route_builder::AuthenticatedRouteBuilder;
 
 pub fn create_todo_routes(state: Arc<AppState>) -> Router<Arc<AppState>> {
+    // Uploads get a body limit of their own, with some room for the multipart framing
+    let uploads = AuthenticatedRouteBuilder::new(state.clone())
+        .post("/{id}/attachments", post_todo_attachment, vec![1, 2])
+        .build()
+        .layer(DefaultBodyLimit::max(max_attachment_size() + 64 * 1024));
+
     AuthenticatedRouteBuilder::new(state)
         // Route for getting all todos
         .get("/all", get_all_todos, vec![1, 2])
@@ -17,7 +37,36 @@ pub fn create_todo_routes(state: Arc<AppState>) -> Router<Arc<AppState>> {
         .post("/new", post_todo,vec![1, 2])
         // Route for getting a todo by ID
         .get("/{id}", get_todos_by_id, vec![1, 2])
+        // Route for updating or completing a todo by ID
+        .patch("/{id}", patch_todo_by_id, vec![1, 2])
         // Route for deleting a todo by ID
         .delete("/{id}", delete_todo_by_id, vec![1, 2])
+        // Routes for sharing a todo with other users and revoking their access
+        .get("/{id}/shares", get_todo_shares, vec![1, 2])
+        .post("/{id}/shares", post_todo_share, vec![1, 2])
+        .delete("/{id}/shares/{user_id}", delete_todo_share, vec![1, 2])
+        // Routes for the files attached to a todo, uploads are routed above
+        .get("/{id}/attachments", get_todo_attachments, vec![1, 2])
+        .get("/{id}/attachments/{attachment_id}", get_todo_attachment, vec![1, 2])
+        .delete("/{id}/attachments/{attachment_id}", delete_todo_attachment, vec![1, 2])
+        // Route for moving a todo to another list or position
+        .post("/{id}/move", move_todo, vec![1, 2])
+        // Routes for managing the lists (projects) todos belong to
+        .get("/lists/all", get_all_todo_lists, vec![1, 2])
+        .post("/lists/new", post_todo_list, vec![1, 2])
+        .get("/lists/{id}", get_todo_list_by_id, vec![1, 2])
+        .patch("/lists/{id}", patch_todo_list_by_id, vec![1, 2])
+        .delete("/lists/{id}", delete_todo_list_by_id, vec![1, 2])
+        // Routes for sharing a list (and so all of its todos) with other users
+        .get("/lists/{id}/shares", get_todo_list_shares, vec![1, 2])
+        .post("/lists/{id}/shares", post_todo_list_share, vec![1, 2])
+        .delete("/lists/{id}/shares/{user_id}", delete_todo_list_share, vec![1, 2])
+        // Routes for managing recurring todos
+        .get("/series/{id}", get_todo_series_by_id, vec![1, 2])
+        .patch("/series/{id}", patch_todo_series_by_id, vec![1, 2])
+        .delete("/series/{id}", delete_todo_series_by_id, vec![1, 2])
+        .get("/series/{id}/preview", get_todo_series_preview, vec![1, 2])
+        .post("/series/{id}/skip", post_todo_series_skip, vec![1, 2])
         .build()
+        .merge(uploads)
 }
\ No newline at end of file
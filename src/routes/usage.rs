@@ -2,7 +2,7 @@ use axum::Router;
 use crate::routes::AppState;
 use std::sync::Arc;
 
-use crate::handlers::get_usage::{get_usage_last_day, get_usage_last_week};
+use crate::handlers::get_usage::{get_usage_last_day, get_usage_last_week, get_usage_history, get_usage_quota};
 use crate::wrappers::authentication_route_builder::AuthenticatedRouteBuilder;
 
 pub fn create_usage_routes(state: Arc<AppState>) -> Router<Arc<AppState>> {
@@ -11,5 +11,9 @@ pub fn create_usage_routes(state: Arc<AppState>) -> Router<Arc<AppState>> {
         .get("/lastday", get_usage_last_day, vec![1, 2])
         // Route for getting the usage from the last week
         .get("/lastweek", get_usage_last_week, vec![1, 2])
+        // Route for getting the daily usage history
+        .get("/history", get_usage_history, vec![1, 2])
+        // Route for getting the consumption of the monthly quota
+        .get("/quota", get_usage_quota, vec![1, 2])
         .build()
 }
\ No newline at end of file
@@ -3,10 +3,16 @@ use crate::routes::AppState;
 use std::sync::Arc;
 
 use crate::handlers::{
-    get_users::{get_all_users, get_users_by_id},
-    post_users::{post_user, post_user_profilepicture, post_user_password_reset, post_user_password_reset_verify, post_user_register, post_user_register_verify},
-    patch_users::patch_user_profile,
-    delete_users::delete_user_by_id
+    get_users::{get_all_users, get_users_by_id, get_user_avatar, search_users},
+    post_users::{post_user, post_user_profilepicture, post_user_restore, post_user_password_reset, post_user_password_reset_verify, post_user_register, post_user_register_verify, post_user_register_resend},
+    patch_users::{patch_user_profile, patch_username},
+    delete_users::{delete_user_by_id, delete_current_user},
+    import_users::import_users,
+    suspend_users::{suspend_user, reinstate_user},
+    post_data_exports::post_data_export,
+    get_data_exports::get_data_export_by_id,
+    get_preferences::get_current_user_preferences,
+    patch_preferences::patch_current_user_preferences
 };
 use crate::wrappers::authentication_route_builder::AuthenticatedRouteBuilder;
 
@@ -14,20 +20,49 @@ pub fn create_user_routes(state: Arc<AppState>) -> Router<Arc<AppState>> {
     AuthenticatedRouteBuilder::new(state)
         // Route for getting all users (requires role 2)
         .get("/all", get_all_users, vec![2])
+        // Route for searching users (requires role 2)
+        .get("/search", search_users, vec![2])
         // Route for creating a new user (requires role 2)
         .post("/new", post_user, vec![2])
-        // Route for requesting a password reset (unauthenticated)
-        .unauthenticated_post("/password-reset", post_user_password_reset)
-        // Route for confirming password reset (unauthenticated)
-        .unauthenticated_post("/password-reset/confirm", post_user_password_reset_verify)
+        // Route for importing users from CSV or JSON (requires role 2)
+        .post("/import", import_users, vec![2])
+        // Route for requesting a password reset (unauthenticated, throttled)
+        .throttled_post("/password-reset", post_user_password_reset, "reset")
+        // Route for confirming password reset (unauthenticated, throttled)
+        .throttled_post("/password-reset/confirm", post_user_password_reset_verify, "reset-verify")
 
-        // Route for requesting a password reset (unauthenticated)
-        .unauthenticated_post("/register", post_user_register)
-        // Route for confirming password reset (unauthenticated)
-        .unauthenticated_post("/register/confirm", post_user_register_verify)
+        // Route for requesting a password reset (unauthenticated, throttled)
+        .throttled_post("/register", post_user_register, "register")
+        // Route for confirming password reset (unauthenticated, throttled)
+        .throttled_post("/register/confirm", post_user_register_verify, "register-verify")
+        // Route for requesting a new verification code (unauthenticated, throttled)
+        .throttled_post("/register/resend", post_user_register_resend, "register-resend")
+
+        // Route for requesting an export of all personal data (requires roles 1 or 2)
+        .post("/current/export", post_data_export, vec![1, 2])
+        // Route for getting the state of a personal data export (requires roles 1 or 2)
+        .get("/current/export/{id}", get_data_export_by_id, vec![1, 2])
+
+        // Route for getting the preferences of the current user (requires roles 1 or 2)
+        .get("/current/preferences", get_current_user_preferences, vec![1, 2])
+        // Route for changing the preferences of the current user (requires roles 1 or 2)
+        .patch("/current/preferences", patch_current_user_preferences, vec![1, 2])
+
+        // Route for deleting the account of the current user, confirmed with its password (requires roles 1 or 2)
+        .delete("/current", delete_current_user, vec![1, 2])
+        // Route for restoring a deleted user during its grace period (requires role 2)
+        .post("/{id}/restore", post_user_restore, vec![2])
+        // Route for suspending a user (requires role 2)
+        .post("/{id}/suspend", suspend_user, vec![2])
+        // Route for reinstating a suspended user (requires role 2)
+        .post("/{id}/reinstate", reinstate_user, vec![2])
 
         // Route for adding profile pictures.
         .post("/{id}/profile-picture", post_user_profilepicture, vec![1, 2]) 
+        // Route for getting the avatar of a user by ID or 'current' in a given size (requires roles 1 or 2)
+        .get("/{id}/avatar", get_user_avatar, vec![1, 2])
+        // Route for changing the username of a user by ID or 'current' (requires roles 1 or 2)
+        .patch("/{id}/username", patch_username, vec![1, 2])
         // Route for getting user by ID (requires roles 1 or 2)
         .get("/{id}", get_users_by_id, vec![1, 2])
         // Route for updating user profile fields (requires roles 1 or 2)
@@ -39,14 +74,16 @@ pub fn create_user_routes(state: Arc<AppState>) -> Router<Arc<AppState>> {
 
 pub fn create_user_root_routes(state: Arc<AppState>) -> Router<Arc<AppState>> {
     AuthenticatedRouteBuilder::new(state)
-        // Route for requesting a password reset (unauthenticated)
-        .unauthenticated_post("/reset", post_user_password_reset)
-        // Route for confirming password reset (unauthenticated)
-        .unauthenticated_post("/reset/verify", post_user_password_reset_verify)
-
-        // Route for requesting a password reset (unauthenticated)
-        .unauthenticated_post("/register", post_user_register)
-        // Route for confirming password reset (unauthenticated)
-        .unauthenticated_post("/register/verify", post_user_register_verify)
+        // Route for requesting a password reset (unauthenticated, throttled)
+        .throttled_post("/reset", post_user_password_reset, "reset")
+        // Route for confirming password reset (unauthenticated, throttled)
+        .throttled_post("/reset/verify", post_user_password_reset_verify, "reset-verify")
+
+        // Route for requesting a password reset (unauthenticated, throttled)
+        .throttled_post("/register", post_user_register, "register")
+        // Route for confirming password reset (unauthenticated, throttled)
+        .throttled_post("/register/verify", post_user_register_verify, "register-verify")
+        // Route for requesting a new verification code (unauthenticated, throttled)
+        .throttled_post("/register/resend", post_user_register_resend, "register-resend")
         .build()
 }
\ No newline at end of file
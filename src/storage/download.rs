@@ -0,0 +1,53 @@
+use aws_sdk_s3::error::ProvideErrorMetadata;
+
+use crate::storage::StorageState;
+
+/// Downloads an object from S3/MinIO storage
+///
+/// # Returns
+/// - `Ok(Vec<u8>)` with the contents of the object
+/// - `Err(String)` with detailed error message on failure
+pub async fn download_from_storage(
+    state: &StorageState,
+    bucket: &str,
+    object_key: &str,
+) -> Result<Vec<u8>, String> {
+    // Input validation
+    if bucket.trim().is_empty() {
+        return Err("Download error: bucket name is empty".to_string());
+    }
+    if object_key.trim().is_empty() {
+        return Err("Download error: object key is empty".to_string());
+    }
+
+    let object = state.client
+        .get_object()
+        .bucket(bucket)
+        .key(object_key)
+        .send()
+        .await
+        .map_err(|err| format!(
+            "Failed to download from storage (code: {}): {}",
+            err.code().unwrap_or("Unknown"),
+            err.message().unwrap_or("No error message provided")
+        ))?;
+
+    let data = object.body
+        .collect()
+        .await
+        .map_err(|e| format!("Failed to read the object from storage: {}", e))?;
+
+    Ok(data.into_bytes().to_vec())
+}
+
+/// Splits a stored object URL (`{endpoint}/{bucket}/{object_key}`) into its bucket and object key
+pub fn split_storage_url<'a>(state: &StorageState, url: &'a str) -> Option<(&'a str, &'a str)> {
+    let path = url.strip_prefix(state.endpoint_url.as_str()).unwrap_or(url);
+    let (bucket, object_key) = path.trim_start_matches('/').split_once('/')?;
+
+    if bucket.is_empty() || object_key.is_empty() {
+        None
+    } else {
+        Some((bucket, object_key))
+    }
+}
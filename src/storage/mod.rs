@@ -3,6 +3,7 @@ pub mod connect;
 pub mod upload;
 pub mod delete;
 pub mod presign_url;
+pub mod download;
 
 use aws_sdk_s3::Client as S3Client;
 
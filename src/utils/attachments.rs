@@ -0,0 +1,208 @@
+use tracing::error;
+
+use crate::core::config::{get_env_u64, get_env_with_default};
+use crate::storage::StorageState;
+use crate::storage::delete::delete_from_storage;
+
+/// Content types that can be attached when `TODO_ATTACHMENT_CONTENT_TYPES` is not set.
+const DEFAULT_ATTACHMENT_CONTENT_TYPES: &str = "image/png,image/jpeg,image/webp,image/gif,application/pdf,text/plain,text/csv,\
+application/vnd.openxmlformats-officedocument.wordprocessingml.document,\
+application/vnd.openxmlformats-officedocument.spreadsheetml.sheet,\
+application/vnd.oasis.opendocument.text,application/vnd.oasis.opendocument.spreadsheet";
+
+/// Bucket the attachments are stored in.
+pub fn attachment_bucket() -> String {
+    get_env_with_default("STORAGE_BUCKET_TODO_ATTACHMENTS", "todo-attachments")
+}
+
+/// Maximum size of a single attachment, in bytes.
+pub fn max_attachment_size() -> usize {
+    get_env_u64("TODO_ATTACHMENT_MAX_SIZE_MB", 10).max(1) as usize * 1024 * 1024
+}
+
+/// Amount of bytes the attachments of a user may take up together: the quota of its tier,
+/// otherwise `TODO_ATTACHMENT_QUOTA_MB`.
+pub fn attachment_quota(tier_quota_mb: Option<i32>) -> i64 {
+    let quota_mb = match tier_quota_mb {
+        Some(quota_mb) => quota_mb.max(0) as i64,
+        None => get_env_u64("TODO_ATTACHMENT_QUOTA_MB", 100) as i64,
+    };
+    quota_mb * 1024 * 1024
+}
+
+/// Whether files of this content type can be attached.
+pub fn is_allowed_content_type(content_type: &str) -> bool {
+    let allowed = get_env_with_default("TODO_ATTACHMENT_CONTENT_TYPES", DEFAULT_ATTACHMENT_CONTENT_TYPES);
+    content_type_matches(&allowed, content_type)
+}
+
+/// The content type a file is stored with, recognized from its contents rather than taken from the client.
+///
+/// Returns `None` when the contents are not recognized, or are of a type that cannot be attached.
+/// Text has no signature, so text keeps the more specific text type (e.g. `text/csv`) it was declared as.
+pub fn detect_attachment_type(declared: &str, data: &[u8]) -> Option<String> {
+    let detected = sniff_content_type(data)?;
+    let content_type = match detected == "text/plain" && declared.starts_with("text/") {
+        true => declared.to_string(),
+        false => detected.to_string(),
+    };
+    is_allowed_content_type(&content_type).then_some(content_type)
+}
+
+/// Recognizes the type of a file from its leading bytes.
+fn sniff_content_type(data: &[u8]) -> Option<&'static str> {
+    const SIGNATURES: [(&[u8], &str); 6] = [
+        (b"\x89PNG\r\n\x1a\n", "image/png"),
+        (b"\xff\xd8\xff", "image/jpeg"),
+        (b"GIF87a", "image/gif"),
+        (b"GIF89a", "image/gif"),
+        (b"%PDF-", "application/pdf"),
+        (b"PK\x03\x04", "application/zip"),
+    ];
+
+    if data.len() >= 12 && data.starts_with(b"RIFF") && &data[8..12] == b"WEBP" {
+        return Some("image/webp");
+    }
+
+    match SIGNATURES.iter().find(|(signature, _)| data.starts_with(signature)) {
+        Some((_, "application/zip")) => Some(sniff_zip_content_type(data)),
+        Some((_, content_type)) => Some(content_type),
+        None if is_text(data) => Some("text/plain"),
+        None => None,
+    }
+}
+
+/// Tells office documents apart from other ZIP archives.
+///
+/// OpenDocument files start with an uncompressed `mimetype` entry, Office Open XML files
+/// contain a `word/` or `xl/` directory, whose names are stored uncompressed as well.
+fn sniff_zip_content_type(data: &[u8]) -> &'static str {
+    const OPEN_DOCUMENT_TYPES: [&str; 3] = [
+        "application/vnd.oasis.opendocument.text",
+        "application/vnd.oasis.opendocument.spreadsheet",
+        "application/vnd.oasis.opendocument.presentation",
+    ];
+    let contains = |needle: &[u8]| data.windows(needle.len()).any(|window| window == needle);
+
+    if data.get(30..38) == Some(b"mimetype".as_slice()) {
+        let mimetype = &data[38..];
+        if let Some(content_type) = OPEN_DOCUMENT_TYPES
+            .into_iter()
+            .find(|content_type| mimetype.starts_with(content_type.as_bytes()) && !mimetype[content_type.len()..].starts_with(b"-"))
+        {
+            return content_type;
+        }
+    }
+
+    if contains(b"[Content_Types].xml") {
+        if contains(b"word/") {
+            return "application/vnd.openxmlformats-officedocument.wordprocessingml.document";
+        }
+        if contains(b"xl/") {
+            return "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet";
+        }
+    }
+
+    "application/zip"
+}
+
+/// Whether the contents are UTF-8 text, without the control characters of binary files.
+fn is_text(data: &[u8]) -> bool {
+    std::str::from_utf8(data)
+        .is_ok_and(|text| text.chars().all(|c| !c.is_control() || matches!(c, '\n' | '\r' | '\t' | '\u{feff}')))
+}
+
+/// Whether a content type is on a comma separated allow-list, `image/*` allows all images.
+///
+/// Parameters such as `; charset=utf-8` are ignored, and so is the case.
+fn content_type_matches(allowed: &str, content_type: &str) -> bool {
+    let content_type = normalize_content_type(content_type);
+    let Some((kind, _)) = content_type.split_once('/') else {
+        return false;
+    };
+
+    allowed
+        .split(',')
+        .map(|entry| entry.trim().to_lowercase())
+        .any(|entry| entry == content_type || entry.strip_suffix("/*") == Some(kind))
+}
+
+/// The essence of a content type, as stored with the attachment (`text/plain; charset=utf-8` becomes `text/plain`).
+pub fn normalize_content_type(content_type: &str) -> String {
+    content_type.split(';').next().unwrap_or("").trim().to_lowercase()
+}
+
+/// The name an uploaded file is stored under: without directories or control characters,
+/// and at most 255 characters long.
+pub fn sanitize_file_name(file_name: &str) -> String {
+    let name: String = file_name
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or("")
+        .chars()
+        .filter(|c| !c.is_control())
+        .take(255)
+        .collect();
+
+    match name.trim() {
+        "" | "." | ".." => "attachment".to_string(),
+        name => name.to_string(),
+    }
+}
+
+/// Removes the files of deleted attachments from storage.
+///
+/// The attachments are already gone from the database, so failures are only logged.
+pub async fn delete_attachment_objects(storage: &StorageState, object_keys: &[String]) {
+    let bucket = attachment_bucket();
+    for object_key in object_keys {
+        if let Err(e) = delete_from_storage(storage, &bucket, object_key).await {
+            error!("Failed to delete attachment {}: {}", object_key, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_allowed_content_types() {
+        let allowed = "image/*, application/pdf";
+
+        assert!(content_type_matches(allowed, "image/png"));
+        assert!(content_type_matches(allowed, "Application/PDF"));
+        assert!(content_type_matches(allowed, "application/pdf; name=report.pdf"));
+        assert!(!content_type_matches(allowed, "application/zip"));
+        assert!(!content_type_matches(allowed, "imagefoo"));
+        assert!(!content_type_matches(allowed, ""));
+    }
+
+    #[test]
+    fn recognizes_contents() {
+        assert_eq!(sniff_content_type(b"\x89PNG\r\n\x1a\n...."), Some("image/png"));
+        assert_eq!(sniff_content_type(b"RIFF\x10\0\0\0WEBPVP8 "), Some("image/webp"));
+        assert_eq!(sniff_content_type(b"%PDF-1.7\n"), Some("application/pdf"));
+        assert_eq!(sniff_content_type(b"name,due\nfile taxes,2027-04-30\n"), Some("text/plain"));
+        assert_eq!(sniff_content_type(b"MZ\x90\0\x03\0\0\0"), None);
+
+        let mut odt = b"PK\x03\x04".to_vec();
+        odt.resize(30, 0);
+        odt.extend_from_slice(b"mimetypeapplication/vnd.oasis.opendocument.text");
+        assert_eq!(sniff_content_type(&odt), Some("application/vnd.oasis.opendocument.text"));
+        assert_eq!(sniff_content_type(b"PK\x03\x04....[Content_Types].xml....word/document.xml"),
+            Some("application/vnd.openxmlformats-officedocument.wordprocessingml.document"));
+        assert_eq!(sniff_content_type(b"PK\x03\x04....payload.exe"), Some("application/zip"));
+    }
+
+    #[test]
+    fn sanitizes_file_names() {
+        assert_eq!(sanitize_file_name("report.pdf"), "report.pdf");
+        assert_eq!(sanitize_file_name("../../etc/passwd"), "passwd");
+        assert_eq!(sanitize_file_name("C:\\Users\\me\\shot.png"), "shot.png");
+        assert_eq!(sanitize_file_name("a\nb.txt"), "ab.txt");
+        assert_eq!(sanitize_file_name(".."), "attachment");
+        assert_eq!(sanitize_file_name(""), "attachment");
+        assert_eq!(sanitize_file_name(&"x".repeat(300)).len(), 255);
+    }
+}
@@ -0,0 +1,317 @@
+use axum::{
+    extract::{ConnectInfo, FromRequestParts},
+    http::{header, request::Parts},
+};
+use chrono::{DateTime, SecondsFormat, Utc};
+use hmac::{Hmac, Mac};
+use serde_json::{json, Map, Value};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use std::{convert::Infallible, net::SocketAddr};
+use tracing::error;
+
+use crate::core::config::get_env;
+use crate::database::audit_log::{fetch_audit_log_batch_from_db, insert_audit_log_entry_into_db};
+use crate::models::audit::{AuditEvent, AuditLogEntry, AuditLogVerification};
+use crate::utils::client_ip::{resolve_client_ip, trusted_proxies};
+
+/// Maximum length of a stored user agent.
+const MAX_USER_AGENT_LENGTH: usize = 512;
+
+/// Amount of entries fetched at once when verifying the hash chain.
+const VERIFY_BATCH_SIZE: i64 = 1000;
+
+/// Fields that identify a person. The log outlives purged users and can not be changed,
+/// so these are only stored as a pseudonym.
+const PERSONAL_FIELDS: [&str; 8] = [
+    "username",
+    "email",
+    "first_name",
+    "last_name",
+    "birthday",
+    "description",
+    "profile_picture_url",
+    "verification_code",
+];
+
+/// Client details of a request, recorded with audit log entries.
+#[derive(Debug, Clone, Default)]
+pub struct AuditContext {
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for AuditContext {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let ip = parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(peer)| resolve_client_ip(peer.ip(), &parts.headers, &trusted_proxies()).to_string());
+
+        let user_agent = parts
+            .headers
+            .get(header::USER_AGENT)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.chars().take(MAX_USER_AGENT_LENGTH).collect());
+
+        Ok(Self { ip, user_agent })
+    }
+}
+
+/// Adds an event to the audit log.
+///
+/// Failures are logged but never fail the request that caused the event.
+pub async fn record_audit_event(pool: &PgPool, context: &AuditContext, mut event: AuditEvent) {
+    let key = audit_pseudonym_key();
+    for value in [event.before.as_mut(), event.after.as_mut()].into_iter().flatten() {
+        pseudonymize_personal_fields(value, key.as_bytes());
+    }
+
+    let action = event.action.clone();
+    if let Err(err) = insert_audit_log_entry_into_db(pool, context, event).await {
+        error!("Could not add '{}' to the audit log: {}", action, err);
+    }
+}
+
+/// Key of the pseudonyms of personal fields, from `AUDIT_PSEUDONYM_KEY` (default: `JWT_SECRET_KEY`).
+fn audit_pseudonym_key() -> String {
+    std::env::var("AUDIT_PSEUDONYM_KEY")
+        .ok()
+        .filter(|key| !key.is_empty())
+        .unwrap_or_else(|| get_env("JWT_SECRET_KEY"))
+}
+
+/// Replaces the values of personal fields by a keyed hash, also in nested objects.
+///
+/// The same value always gets the same pseudonym, so entries about it can still be related,
+/// but without the key the value can not be recovered or guessed. `null` is kept as it is.
+pub fn pseudonymize_personal_fields(value: &mut Value, key: &[u8]) {
+    match value {
+        Value::Object(fields) => {
+            for (name, field) in fields.iter_mut() {
+                if PERSONAL_FIELDS.contains(&name.as_str()) {
+                    if !field.is_null() {
+                        *field = Value::String(pseudonym(field, key));
+                    }
+                } else {
+                    pseudonymize_personal_fields(field, key);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| pseudonymize_personal_fields(item, key)),
+        _ => {}
+    }
+}
+
+fn pseudonym(value: &Value, key: &[u8]) -> String {
+    // Case is ignored, like it is when signing in with an email address
+    let plain = match value {
+        Value::String(text) => text.to_lowercase(),
+        other => other.to_string(),
+    };
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(plain.as_bytes());
+    format!("hmac-sha256:{}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Walks the whole audit log and checks that every entry matches its hash and links to the previous one.
+///
+/// A changed entry no longer matches its hash, a removed entry breaks the link of the entry after it.
+pub async fn verify_audit_chain(pool: &PgPool) -> Result<AuditLogVerification, sqlx::Error> {
+    let mut checked = 0;
+    let mut last_seq = 0;
+    let mut last_hash: Option<String> = None;
+
+    loop {
+        let batch = fetch_audit_log_batch_from_db(pool, last_seq, VERIFY_BATCH_SIZE).await?;
+        if batch.is_empty() {
+            return Ok(AuditLogVerification { valid: true, checked, broken_at_seq: None });
+        }
+
+        for entry in batch {
+            if entry.prev_hash != last_hash || audit_entry_hash(&entry) != entry.hash {
+                return Ok(AuditLogVerification { valid: false, checked, broken_at_seq: Some(entry.seq) });
+            }
+            checked += 1;
+            last_seq = entry.seq;
+            last_hash = Some(entry.hash);
+        }
+    }
+}
+
+/// Formats a moment the way it is hashed, with the microsecond precision of the database.
+fn format_occurred_at(occurred_at: &DateTime<Utc>) -> String {
+    occurred_at.to_rfc3339_opts(SecondsFormat::Micros, true)
+}
+
+/// Computes the hash of an entry, chained to the hash of the previous entry.
+///
+/// The fields are hashed as a JSON array. Objects in `before` and `after` serialize with
+/// sorted keys, so the hash does not depend on the key order of the stored JSONB.
+#[allow(clippy::too_many_arguments)]
+pub fn compute_audit_hash(
+    prev_hash: Option<&str>,
+    occurred_at: &DateTime<Utc>,
+    actor_id: Option<&str>,
+    action: &str,
+    target_type: Option<&str>,
+    target_id: Option<&str>,
+    ip: Option<&str>,
+    user_agent: Option<&str>,
+    before: Option<&Value>,
+    after: Option<&Value>,
+) -> String {
+    let canonical = json!([
+        prev_hash,
+        format_occurred_at(occurred_at),
+        actor_id,
+        action,
+        target_type,
+        target_id,
+        ip,
+        user_agent,
+        before,
+        after,
+    ]);
+
+    hex::encode(Sha256::digest(canonical.to_string().as_bytes()))
+}
+
+/// Recomputes the hash of a stored entry.
+pub fn audit_entry_hash(entry: &AuditLogEntry) -> String {
+    compute_audit_hash(
+        entry.prev_hash.as_deref(),
+        &entry.occurred_at,
+        entry.actor_id.map(|id| id.to_string()).as_deref(),
+        &entry.action,
+        entry.target_type.as_deref(),
+        entry.target_id.as_deref(),
+        entry.ip.as_deref(),
+        entry.user_agent.as_deref(),
+        entry.before.as_ref(),
+        entry.after.as_ref(),
+    )
+}
+
+/// Reduces two versions of an object to the fields that differ between them.
+///
+/// Returns `(None, None)` when nothing changed. Fields missing on one side are reported as `null`.
+pub fn json_diff(before: &Value, after: &Value) -> (Option<Value>, Option<Value>) {
+    let empty = Map::new();
+    let before_fields = before.as_object().unwrap_or(&empty);
+    let after_fields = after.as_object().unwrap_or(&empty);
+
+    let mut changed_before = Map::new();
+    let mut changed_after = Map::new();
+    for key in before_fields.keys().chain(after_fields.keys()) {
+        let old = before_fields.get(key).unwrap_or(&Value::Null);
+        let new = after_fields.get(key).unwrap_or(&Value::Null);
+        if old != new {
+            changed_before.insert(key.clone(), old.clone());
+            changed_after.insert(key.clone(), new.clone());
+        }
+    }
+
+    if changed_after.is_empty() {
+        (None, None)
+    } else {
+        (Some(Value::Object(changed_before)), Some(Value::Object(changed_after)))
+    }
+}
+
+/// Formats audit log entries as CSV, with `before` and `after` as JSON.
+pub fn audit_log_to_csv(entries: &[AuditLogEntry]) -> Result<String, csv::Error> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record([
+        "seq", "id", "occurred_at", "actor_id", "action", "target_type", "target_id",
+        "ip", "user_agent", "before", "after", "prev_hash", "hash",
+    ])?;
+
+    for entry in entries {
+        writer.write_record([
+            entry.seq.to_string(),
+            entry.id.to_string(),
+            format_occurred_at(&entry.occurred_at),
+            entry.actor_id.map(|id| id.to_string()).unwrap_or_default(),
+            entry.action.clone(),
+            entry.target_type.clone().unwrap_or_default(),
+            entry.target_id.clone().unwrap_or_default(),
+            entry.ip.clone().unwrap_or_default(),
+            entry.user_agent.clone().unwrap_or_default(),
+            entry.before.as_ref().map(Value::to_string).unwrap_or_default(),
+            entry.after.as_ref().map(Value::to_string).unwrap_or_default(),
+            entry.prev_hash.clone().unwrap_or_default(),
+            entry.hash.clone(),
+        ])?;
+    }
+
+    let bytes = writer.into_inner().map_err(|err| err.into_error())?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn hash(prev_hash: Option<&str>, after: Option<&Value>) -> String {
+        let occurred_at = Utc.with_ymd_and_hms(2026, 10, 18, 12, 0, 0).unwrap();
+        compute_audit_hash(prev_hash, &occurred_at, None, "user.login", Some("user"), Some("1"), None, None, None, after)
+    }
+
+    #[test]
+    fn hash_depends_on_previous_hash_and_contents() {
+        let first = hash(None, None);
+        assert_eq!(first.len(), 64);
+        assert_eq!(first, hash(None, None));
+        assert_ne!(first, hash(Some(&first), None));
+        assert_ne!(first, hash(None, Some(&json!({ "role_level": 2 }))));
+    }
+
+    #[test]
+    fn hash_ignores_key_order() {
+        let a: Value = serde_json::from_str(r#"{"a": 1, "b": 2}"#).unwrap();
+        let b: Value = serde_json::from_str(r#"{"b": 2, "a": 1}"#).unwrap();
+        assert_eq!(hash(None, Some(&a)), hash(None, Some(&b)));
+    }
+
+    #[test]
+    fn diff_keeps_changed_fields_only() {
+        let (before, after) = json_diff(
+            &json!({ "username": "alice", "role_level": 1, "country_code": "NL" }),
+            &json!({ "username": "alice", "role_level": 2, "description": "Admin" }),
+        );
+        assert_eq!(before, Some(json!({ "role_level": 1, "country_code": "NL", "description": null })));
+        assert_eq!(after, Some(json!({ "role_level": 2, "country_code": null, "description": "Admin" })));
+
+        assert_eq!(json_diff(&json!({ "a": 1 }), &json!({ "a": 1 })), (None, None));
+    }
+
+    #[test]
+    fn pseudonymizes_personal_fields_only() {
+        let mut value = json!({
+            "reason": "unknown_email",
+            "email": "Alice@Example.com",
+            "last_name": null,
+            "user": { "username": "alice", "role_level": 1 },
+        });
+        pseudonymize_personal_fields(&mut value, b"key");
+
+        let email = value["email"].as_str().unwrap();
+        assert!(email.starts_with("hmac-sha256:") && !email.contains("alice"));
+        assert_eq!(value["reason"], "unknown_email");
+        assert_eq!(value["last_name"], Value::Null);
+        assert_eq!(value["user"]["role_level"], 1);
+
+        // The same address gets the same pseudonym, another key another one
+        let mut same = json!({ "email": "alice@example.com" });
+        pseudonymize_personal_fields(&mut same, b"key");
+        assert_eq!(same["email"], value["email"]);
+        let mut other_key = json!({ "email": "alice@example.com" });
+        pseudonymize_personal_fields(&mut other_key, b"other");
+        assert_ne!(other_key["email"], value["email"]);
+        assert_ne!(value["user"]["username"], "alice");
+    }
+}
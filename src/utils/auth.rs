@@ -5,7 +5,7 @@ use argon2::{
     password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString, Error},
     Argon2, Params, Version,
 };
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
 use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, TokenData, Validation, errors::ErrorKind};
 use totp_rs::{Secret, TOTP};
 use rand::{rngs::OsRng, Rng};
@@ -13,8 +13,11 @@ use tracing::{warn, error, debug, instrument};
 use tokio::task;
 use moka::future::Cache;
 use lazy_static::lazy_static;
+use uuid::Uuid;
 
+use crate::i18n::locale::Locale;
 use crate::models::auth::{AuthError, Claims}; 
+use crate::models::invitation::InvitationClaims;
 use crate::core::config::{get_env, get_env_with_default};
 
 // Constants and lazy_static variables
@@ -111,8 +114,54 @@ pub fn encode_jwt(email: String) -> Result<String, StatusCode> {
     })
 }
 
+/// Signs the token of an invitation link.
+///
+/// The audience differs from the one of session tokens, so an invitation can never be used to authenticate.
+pub fn encode_invitation_token(invitation_id: Uuid, nonce: &str, expires_at: DateTime<Utc>) -> Result<String, StatusCode> {
+    let claims = InvitationClaims {
+        sub: invitation_id.to_string(),
+        nonce: nonce.to_string(),
+        iat: Utc::now().timestamp() as usize,
+        exp: expires_at.timestamp() as usize,
+        iss: get_env("JWT_ISSUER"),
+        aud: invitation_audience(),
+    };
+
+    encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(SECRET_KEY.as_ref()),
+    )
+    .map_err(|e| {
+        error!("Failed to encode invitation token: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+/// Verifies the signature and expiry of the token of an invitation link.
+#[instrument(skip(token))]
+pub fn decode_invitation_token(token: &str) -> Result<InvitationClaims, String> {
+    let mut validation = Validation::default();
+    validation.set_issuer(&[get_env("JWT_ISSUER")]);
+    validation.set_audience(&[invitation_audience()]);
+
+    decode::<InvitationClaims>(token, &DecodingKey::from_secret(SECRET_KEY.as_ref()), &validation)
+        .map(|data| data.claims)
+        .map_err(|err| {
+            warn!("Invitation token decode error: {:?}", err);
+            match err.kind() {
+                ErrorKind::ExpiredSignature => "The invitation has expired.".to_string(),
+                _ => "Invalid invitation link.".to_string(),
+            }
+        })
+}
+
+fn invitation_audience() -> String {
+    format!("{}:invitation", get_env("JWT_AUDIENCE"))
+}
+
 #[instrument(skip(jwt))]
-pub fn decode_jwt(jwt: String) -> Result<TokenData<Claims>, AuthError> {
+pub fn decode_jwt(jwt: String, locale: Locale) -> Result<TokenData<Claims>, AuthError> {
     let secret_key = get_env("JWT_SECRET_KEY");
 
     // Get issuer and audience from environment variables
@@ -137,21 +186,18 @@ pub fn decode_jwt(jwt: String) -> Result<TokenData<Claims>, AuthError> {
     ) {
         Ok(token_data) => Ok(token_data),
         Err(err) => {
-            let message = match err.kind() {
-                ErrorKind::InvalidToken => "Invalid token format.",
-                ErrorKind::InvalidSignature => "Invalid token signature.",
-                ErrorKind::ExpiredSignature => "Token has expired.",
-                ErrorKind::InvalidIssuer => "Invalid token issuer.",
-                ErrorKind::InvalidAudience => "Invalid token audience.",
-                _ => "Failed to decode token.",
+            let key = match err.kind() {
+                ErrorKind::InvalidToken => "auth.token_invalid",
+                ErrorKind::InvalidSignature => "auth.token_signature",
+                ErrorKind::ExpiredSignature => "auth.token_expired",
+                ErrorKind::InvalidIssuer => "auth.token_issuer",
+                ErrorKind::InvalidAudience => "auth.token_audience",
+                _ => "auth.token_decode_failed",
             };
 
             warn!("JWT decode error: {:?}", err);
 
-            Err(AuthError {
-                message: message.to_string(),
-                status_code: StatusCode::UNAUTHORIZED,
-            })
+            Err(AuthError::new(locale, key, StatusCode::UNAUTHORIZED))
         }
     }
 }
@@ -0,0 +1,132 @@
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+/// Sizes (in pixels) of the generated profile picture variants.
+pub const AVATAR_SIZES: [u32; 4] = [64, 128, 300, 600];
+
+/// Size returned when no size is requested, and the variant stored in `profile_picture_url`.
+pub const DEFAULT_AVATAR_SIZE: u32 = 300;
+
+/// Background colors of generated initials avatars.
+const INITIALS_COLORS: [&str; 8] = [
+    "#1abc9c", "#2e86de", "#8e44ad", "#e67e22", "#c0392b", "#16a085", "#d35400", "#2c3e50",
+];
+
+/// Picks the smallest variant that is at least as large as the requested size,
+/// or the largest variant when more is requested.
+pub fn select_avatar_size(requested: Option<u32>) -> u32 {
+    let Some(requested) = requested else { return DEFAULT_AVATAR_SIZE };
+    AVATAR_SIZES
+        .iter()
+        .copied()
+        .find(|size| *size >= requested)
+        .unwrap_or(AVATAR_SIZES[AVATAR_SIZES.len() - 1])
+}
+
+/// Object key of a variant, e.g. `profile_pictures/{user_id}_{timestamp}_{size}.webp`.
+pub fn avatar_variant_key(base_key: &str, size: u32) -> String {
+    format!("{}_{}.webp", base_key, size)
+}
+
+/// Object key of the requested size, derived from the stored key of the default variant.
+///
+/// Pictures uploaded before variants existed are a single object, which is returned for every size.
+pub fn resolve_avatar_key(stored_key: &str, size: u32) -> String {
+    match stored_key.strip_suffix(&format!("_{}.webp", DEFAULT_AVATAR_SIZE)) {
+        Some(base_key) => avatar_variant_key(base_key, size),
+        None => stored_key.to_string(),
+    }
+}
+
+/// Object keys of all variants of a stored profile picture, to delete them together.
+pub fn all_avatar_keys(stored_key: &str) -> Vec<String> {
+    match stored_key.strip_suffix(&format!("_{}.webp", DEFAULT_AVATAR_SIZE)) {
+        Some(base_key) => AVATAR_SIZES.iter().map(|size| avatar_variant_key(base_key, *size)).collect(),
+        None => vec![stored_key.to_string()],
+    }
+}
+
+/// Up to two initials, from the first and last name or else from the username.
+pub fn initials(first_name: Option<&str>, last_name: Option<&str>, username: &str) -> String {
+    let first_letter = |value: Option<&str>| value.and_then(|value| value.trim().chars().next());
+
+    let letters: String = match (first_letter(first_name), first_letter(last_name)) {
+        (Some(first), Some(last)) => [first, last].iter().collect(),
+        (Some(first), None) => first.to_string(),
+        _ => username.chars().filter(|c| c.is_alphanumeric()).take(2).collect(),
+    };
+
+    letters.to_uppercase()
+}
+
+/// Generates an SVG avatar with the initials of a user, in a color that stays the same per user.
+pub fn initials_avatar_svg(user_id: Uuid, initials: &str, size: u32) -> String {
+    let color = INITIALS_COLORS[user_id.as_bytes()[0] as usize % INITIALS_COLORS.len()];
+    format!(
+        concat!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{size}" height="{size}" viewBox="0 0 100 100">"#,
+            r#"<rect width="100" height="100" fill="{color}"/>"#,
+            r##"<text x="50" y="50" dy=".35em" text-anchor="middle" font-family="sans-serif" font-size="40" fill="#ffffff">{initials}</text>"##,
+            "</svg>"
+        ),
+        size = size,
+        color = color,
+        initials = html_escape::encode_text(initials),
+    )
+}
+
+/// Strong ETag of a response body, or of the (immutable) object key it is read from.
+pub fn etag(content: &[u8]) -> String {
+    format!("\"{}\"", &hex::encode(Sha256::digest(content))[..32])
+}
+
+/// Checks an `If-None-Match` header against the ETag of the current representation.
+pub fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    if_none_match
+        .split(',')
+        .map(|candidate| candidate.trim())
+        .any(|candidate| candidate == "*" || candidate.trim_start_matches("W/") == etag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selects_the_nearest_larger_variant() {
+        assert_eq!(select_avatar_size(None), 300);
+        assert_eq!(select_avatar_size(Some(1)), 64);
+        assert_eq!(select_avatar_size(Some(64)), 64);
+        assert_eq!(select_avatar_size(Some(100)), 128);
+        assert_eq!(select_avatar_size(Some(5000)), 600);
+    }
+
+    #[test]
+    fn resolves_variant_keys() {
+        let stored = "profile_pictures/abc_1700000000_300.webp";
+        assert_eq!(resolve_avatar_key(stored, 64), "profile_pictures/abc_1700000000_64.webp");
+        assert_eq!(all_avatar_keys(stored).len(), AVATAR_SIZES.len());
+
+        // Pictures from before the variants only exist in one size
+        let legacy = "profile_pictures/abc_1700000000.webp";
+        assert_eq!(resolve_avatar_key(legacy, 64), legacy);
+        assert_eq!(all_avatar_keys(legacy), vec![legacy.to_string()]);
+    }
+
+    #[test]
+    fn builds_initials() {
+        assert_eq!(initials(Some("ada"), Some("Lovelace"), "ada"), "AL");
+        assert_eq!(initials(Some("Ada"), None, "ada"), "A");
+        assert_eq!(initials(None, Some("Lovelace"), "_ada_99"), "AD");
+        assert_eq!(initials(Some("  "), None, "x"), "X");
+    }
+
+    #[test]
+    fn matches_etags() {
+        let tag = etag(b"avatar");
+        assert!(etag_matches(&tag, &tag));
+        assert!(etag_matches(&format!("\"other\", W/{}", tag), &tag));
+        assert!(etag_matches("*", &tag));
+        assert!(!etag_matches("\"other\"", &tag));
+    }
+}
@@ -0,0 +1,131 @@
+use chrono::NaiveDate;
+use html_escape::encode_text;
+
+use crate::models::billing::BillingStatement;
+use crate::utils::quota::quota_period;
+
+/// Parses a billing period formatted as `YYYY-MM`, returning its first day and the first day of the next month.
+pub fn parse_billing_period(value: &str) -> Option<(NaiveDate, NaiveDate)> {
+    let start = NaiveDate::parse_from_str(&format!("{}-01", value.trim()), "%Y-%m-%d").ok()?;
+    Some(quota_period(start))
+}
+
+/// Formats an amount in cents, e.g. `1234` as `12.34`.
+pub fn format_cents(cents: i64) -> String {
+    let sign = if cents < 0 { "-" } else { "" };
+    format!("{}{}.{:02}", sign, (cents / 100).abs(), (cents % 100).abs())
+}
+
+// Returns the line items of a statement as (description, quantity, unit price, amount)
+fn statement_lines(statement: &BillingStatement) -> Vec<(String, i64, String, i64)> {
+    vec![
+        (
+            format!("{} tier base fee (includes {} requests)", statement.tier_name, statement.included_requests),
+            1,
+            format_cents(statement.base_fee_cents),
+            statement.base_fee_cents,
+        ),
+        (
+            "Requests above the included amount".to_string(),
+            statement.overage_requests,
+            format!("{} per 1000", format_cents(statement.overage_cents_per_1000)),
+            statement.overage_cents,
+        ),
+    ]
+}
+
+/// Renders a statement as CSV, one line item per row followed by the total.
+pub fn statement_to_csv(statement: &BillingStatement) -> String {
+    let escape = |value: &str| format!("\"{}\"", value.replace('"', "\"\""));
+    let period = statement.period_start.format("%Y-%m").to_string();
+
+    let mut csv = String::from("period,description,quantity,unit_price,amount,currency\n");
+    for (description, quantity, unit_price, amount) in statement_lines(statement) {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{}\n",
+            period,
+            escape(&description),
+            quantity,
+            escape(&unit_price),
+            format_cents(amount),
+            statement.currency
+        ));
+    }
+    csv.push_str(&format!("{},\"Total\",,,{},{}\n", period, format_cents(statement.total_cents), statement.currency));
+    csv
+}
+
+/// Renders a statement as a standalone, printable HTML document.
+pub fn statement_to_html(statement: &BillingStatement) -> String {
+    let period = statement.period_start.format("%B %Y").to_string();
+    let currency = encode_text(&statement.currency);
+
+    let rows: String = statement_lines(statement)
+        .into_iter()
+        .map(|(description, quantity, unit_price, amount)| format!(
+            "<tr><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{} {}</td></tr>",
+            encode_text(&description),
+            quantity,
+            encode_text(&unit_price),
+            currency,
+            format_cents(amount)
+        ))
+        .collect();
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <title>Statement {period}</title>
+    <style>
+        body {{ font-family: Arial, sans-serif; margin: 2rem; color: #1a1f2c; }}
+        table {{ border-collapse: collapse; width: 100%; }}
+        th, td {{ border-bottom: 1px solid #ddd; padding: 0.5rem; text-align: left; }}
+        .num {{ text-align: right; }}
+        tfoot td {{ font-weight: bold; border-bottom: none; }}
+    </style>
+</head>
+<body>
+    <h1>Statement {period}</h1>
+    <p>Statement ID: {id}<br>Period: {start} until {end}<br>Requests made: {requests}<br>Generated at: {generated_at}</p>
+    <table>
+        <thead><tr><th>Description</th><th class="num">Quantity</th><th class="num">Unit price</th><th class="num">Amount</th></tr></thead>
+        <tbody>{rows}</tbody>
+        <tfoot><tr><td colspan="3">Total</td><td class="num">{currency} {total}</td></tr></tfoot>
+    </table>
+</body>
+</html>"#,
+        period = encode_text(&period),
+        id = statement.id,
+        start = statement.period_start,
+        end = statement.period_end,
+        requests = statement.request_count,
+        generated_at = statement.generated_at.format("%Y-%m-%d %H:%M UTC"),
+        rows = rows,
+        currency = currency,
+        total = format_cents(statement.total_cents),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_billing_period() {
+        let (start, end) = parse_billing_period("2026-12").unwrap();
+        assert_eq!(start, NaiveDate::from_ymd_opt(2026, 12, 1).unwrap());
+        assert_eq!(end, NaiveDate::from_ymd_opt(2027, 1, 1).unwrap());
+
+        assert!(parse_billing_period("2026-13").is_none());
+        assert!(parse_billing_period("2026-09-15").is_none());
+    }
+
+    #[test]
+    fn test_format_cents() {
+        assert_eq!(format_cents(0), "0.00");
+        assert_eq!(format_cents(1905), "19.05");
+        assert_eq!(format_cents(-250), "-2.50");
+    }
+}
@@ -0,0 +1,121 @@
+use axum::http::HeaderMap;
+use std::net::IpAddr;
+
+use crate::core::config::get_env_with_default;
+
+/// An IP address range in CIDR notation (e.g. `10.0.0.0/8`), or a single address.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IpRange {
+    address: IpAddr,
+    prefix: u8,
+}
+
+impl IpRange {
+    /// Parses `address` or `address/prefix`. Returns None for invalid input.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let (address, prefix) = match value.split_once('/') {
+            Some((address, prefix)) => (address.parse::<IpAddr>().ok()?, Some(prefix.parse::<u8>().ok()?)),
+            None => (value.parse::<IpAddr>().ok()?, None),
+        };
+
+        let max_prefix = if address.is_ipv4() { 32 } else { 128 };
+        let prefix = prefix.unwrap_or(max_prefix);
+        if prefix > max_prefix {
+            return None;
+        }
+
+        Some(Self { address, prefix })
+    }
+
+    /// Checks whether the range contains the given address.
+    pub fn contains(&self, ip: &IpAddr) -> bool {
+        match (self.address, ip.to_canonical()) {
+            (IpAddr::V4(range), IpAddr::V4(ip)) => {
+                let mask = u32::MAX.checked_shl(32 - self.prefix as u32).unwrap_or(0);
+                u32::from(range) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(range), IpAddr::V6(ip)) => {
+                let mask = u128::MAX.checked_shl(128 - self.prefix as u32).unwrap_or(0);
+                u128::from(range) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Reads the proxies whose `X-Forwarded-For` header is trusted from `SERVER_TRUSTED_PROXIES`
+/// (comma separated addresses or CIDR ranges). Invalid entries are ignored.
+pub fn trusted_proxies() -> Vec<IpRange> {
+    get_env_with_default("SERVER_TRUSTED_PROXIES", "")
+        .split(',')
+        .filter(|value| !value.trim().is_empty())
+        .filter_map(IpRange::parse)
+        .collect()
+}
+
+/// Determines the address of the client that made the request.
+///
+/// When the connecting peer is a trusted proxy (like HAProxy), `X-Forwarded-For` is walked from
+/// right to left and the first address that is not a trusted proxy is returned. Entries further
+/// to the left are supplied by the client and cannot be trusted.
+pub fn resolve_client_ip(peer: IpAddr, headers: &HeaderMap, trusted: &[IpRange]) -> IpAddr {
+    let peer = peer.to_canonical();
+    let is_trusted = |ip: &IpAddr| trusted.iter().any(|range| range.contains(ip));
+
+    if !is_trusted(&peer) {
+        return peer;
+    }
+
+    let forwarded: Vec<IpAddr> = headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|value| value.trim().parse::<IpAddr>().ok())
+        .map(|ip| ip.to_canonical())
+        .collect();
+
+    forwarded
+        .iter()
+        .rev()
+        .find(|ip| !is_trusted(ip))
+        .or_else(|| forwarded.first())
+        .copied()
+        .unwrap_or(peer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn test_ip_range() {
+        let range = IpRange::parse("172.16.0.0/12").unwrap();
+        assert!(range.contains(&"172.20.1.5".parse().unwrap()));
+        assert!(range.contains(&"::ffff:172.20.1.5".parse().unwrap()));
+        assert!(!range.contains(&"172.32.0.1".parse().unwrap()));
+
+        assert!(IpRange::parse("10.0.0.1").unwrap().contains(&"10.0.0.1".parse().unwrap()));
+        assert!(IpRange::parse("0.0.0.0/0").unwrap().contains(&"8.8.8.8".parse().unwrap()));
+        assert!(IpRange::parse("10.0.0.0/33").is_none());
+        assert!(IpRange::parse("proxy").is_none());
+    }
+
+    #[test]
+    fn test_resolve_client_ip() {
+        let trusted = vec![IpRange::parse("10.0.0.0/8").unwrap()];
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", HeaderValue::from_static("1.1.1.1, 2.2.2.2, 10.0.0.7"));
+
+        // Spoofed entries left of the first untrusted address are ignored
+        assert_eq!(resolve_client_ip("10.0.0.2".parse().unwrap(), &headers, &trusted), "2.2.2.2".parse::<IpAddr>().unwrap());
+
+        // The header is ignored when the peer is not a trusted proxy
+        assert_eq!(resolve_client_ip("3.3.3.3".parse().unwrap(), &headers, &trusted), "3.3.3.3".parse::<IpAddr>().unwrap());
+
+        // Without a header, the proxy itself is the client
+        assert_eq!(resolve_client_ip("10.0.0.2".parse().unwrap(), &HeaderMap::new(), &trusted), "10.0.0.2".parse::<IpAddr>().unwrap());
+    }
+}
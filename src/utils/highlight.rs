@@ -0,0 +1,94 @@
+use html_escape::encode_text;
+use regex::RegexBuilder;
+
+/// Highlights every (case-insensitive) occurrence of the terms in a text.
+///
+/// The text is HTML escaped and the matches are wrapped in `<mark>` tags. When `max_len`
+/// is given and the text is longer, only a snippet around the first match is returned.
+/// Returns None when none of the terms occur in the text.
+pub fn highlight(text: &str, terms: &[&str], max_len: Option<usize>) -> Option<String> {
+    let pattern = terms
+        .iter()
+        .filter(|term| !term.is_empty())
+        .map(|term| regex::escape(term))
+        .collect::<Vec<String>>()
+        .join("|");
+    if pattern.is_empty() {
+        return None;
+    }
+
+    let regex = RegexBuilder::new(&pattern).case_insensitive(true).build().ok()?;
+    let first = regex.find(text)?;
+
+    let (start, end) = match max_len {
+        Some(max_len) if text.len() > max_len => {
+            // Show some context before the first match
+            let start = floor_char_boundary(text, first.start().saturating_sub(max_len / 3));
+            (start, ceil_char_boundary(text, (start + max_len).min(text.len())))
+        }
+        _ => (0, text.len()),
+    };
+    let snippet = &text[start..end];
+
+    let mut highlighted = String::new();
+    if start > 0 {
+        highlighted.push('…');
+    }
+
+    let mut last = 0;
+    for found in regex.find_iter(snippet) {
+        highlighted.push_str(&encode_text(&snippet[last..found.start()]));
+        highlighted.push_str("<mark>");
+        highlighted.push_str(&encode_text(found.as_str()));
+        highlighted.push_str("</mark>");
+        last = found.end();
+    }
+    highlighted.push_str(&encode_text(&snippet[last..]));
+
+    if end < text.len() {
+        highlighted.push('…');
+    }
+
+    Some(highlighted)
+}
+
+fn floor_char_boundary(text: &str, mut index: usize) -> usize {
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+fn ceil_char_boundary(text: &str, mut index: usize) -> usize {
+    while !text.is_char_boundary(index) {
+        index += 1;
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highlight() {
+        assert_eq!(
+            highlight("Jan de Vries", &["VRIES", "jan"], None).unwrap(),
+            "<mark>Jan</mark> de <mark>Vries</mark>"
+        );
+        assert_eq!(
+            highlight("<b>admin</b>", &["admin"], None).unwrap(),
+            "&lt;b&gt;<mark>admin</mark>&lt;/b&gt;"
+        );
+        assert!(highlight("user@test.com", &["admin"], None).is_none());
+        assert!(highlight("user@test.com", &[], None).is_none());
+    }
+
+    #[test]
+    fn test_highlight_snippet() {
+        let text = format!("{} match {}", "é".repeat(50), "b".repeat(50));
+        let snippet = highlight(&text, &["match"], Some(30)).unwrap();
+        assert!(snippet.starts_with('…') && snippet.ends_with('…'));
+        assert!(snippet.contains("<mark>match</mark>"));
+    }
+}
@@ -1,4 +1,15 @@
 pub mod validate;
 pub mod auth;
 pub mod process_image;
-pub mod global_error_handler;
\ No newline at end of file
+pub mod global_error_handler;
+pub mod quota;
+pub mod client_ip;
+pub mod billing;
+pub mod highlight;
+pub mod audit;
+pub mod avatar;
+pub mod preferences;
+pub mod user_import;
+pub mod recurrence;
+pub mod attachments;
+pub mod reminders;
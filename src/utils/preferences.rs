@@ -0,0 +1,149 @@
+use chrono_tz::Tz;
+use serde_json::{Map, Value};
+use sqlx::PgPool;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::database::preferences::fetch_user_preferences_from_db;
+use crate::models::preference::UserPreferences;
+use crate::utils::reminders::{MAX_REMINDER_LEAD_MINUTES, MAX_REMINDER_LEAD_TIMES};
+
+/// A validated change of preferences.
+#[derive(Debug, Default, PartialEq)]
+pub struct PreferencesPatch {
+    /// Preferences to store.
+    pub set: Map<String, Value>,
+
+    /// Preferences to reset to their default.
+    pub reset: Vec<String>,
+}
+
+/// Loads the preferences of a user, with defaults for everything that was not set.
+///
+/// Failures are logged and fall back to the defaults, so that preferences never block mail or todos.
+pub async fn load_user_preferences(pool: &PgPool, user_id: Uuid) -> UserPreferences {
+    match fetch_user_preferences_from_db(pool, user_id).await {
+        Ok(Some(stored)) => resolve_preferences(&stored),
+        Ok(None) => UserPreferences::default(),
+        Err(err) => {
+            error!("Could not load the preferences of user {}: {}", user_id, err);
+            UserPreferences::default()
+        }
+    }
+}
+
+/// Applies the stored preferences to the defaults.
+///
+/// Stored values that are no longer known or valid (e.g. after a schema change) are ignored.
+pub fn resolve_preferences(stored: &Value) -> UserPreferences {
+    let mut resolved = default_preferences();
+    if let Some(stored) = stored.as_object() {
+        for (key, value) in stored {
+            if validate_preference(key, value).is_ok() {
+                resolved.insert(key.clone(), value.clone());
+            }
+        }
+    }
+
+    serde_json::from_value(Value::Object(resolved)).unwrap_or_default()
+}
+
+/// Validates a (partial) preferences object, where `null` resets a preference to its default.
+pub fn parse_preferences_patch(patch: &Value) -> Result<PreferencesPatch, String> {
+    let object = patch.as_object().ok_or_else(|| "Preferences must be a JSON object.".to_string())?;
+    if object.is_empty() {
+        return Err("No preferences provided.".to_string());
+    }
+
+    let mut parsed = PreferencesPatch::default();
+    for (key, value) in object {
+        if value.is_null() {
+            if !default_preferences().contains_key(key) {
+                return Err(format!("Unknown preference '{}'.", key));
+            }
+            parsed.reset.push(key.clone());
+        } else {
+            validate_preference(key, value)?;
+            parsed.set.insert(key.clone(), value.clone());
+        }
+    }
+
+    Ok(parsed)
+}
+
+// Checks a single preference against the schema of `UserPreferences`
+fn validate_preference(key: &str, value: &Value) -> Result<(), String> {
+    let mut candidate = default_preferences();
+    if candidate.insert(key.to_string(), value.clone()).is_none() {
+        return Err(format!("Unknown preference '{}'.", key));
+    }
+
+    let preferences: UserPreferences = serde_json::from_value(Value::Object(candidate))
+        .map_err(|_| format!("Invalid value for preference '{}'.", key))?;
+
+    if key == "timezone" && preferences.timezone.parse::<Tz>().is_err() {
+        return Err(format!("Unknown time zone '{}'.", preferences.timezone));
+    }
+
+    let lead_minutes = &preferences.todo_reminder_lead_minutes;
+    if key == "todo_reminder_lead_minutes"
+        && (lead_minutes.len() > MAX_REMINDER_LEAD_TIMES
+            || lead_minutes.iter().any(|minutes| *minutes == 0 || *minutes > MAX_REMINDER_LEAD_MINUTES))
+    {
+        return Err(format!(
+            "Reminders can be sent at most {} times, between 1 and {} minutes before the due date.",
+            MAX_REMINDER_LEAD_TIMES, MAX_REMINDER_LEAD_MINUTES
+        ));
+    }
+
+    Ok(())
+}
+
+fn default_preferences() -> Map<String, Value> {
+    match serde_json::to_value(UserPreferences::default()) {
+        Ok(Value::Object(map)) => map,
+        _ => Map::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::preference::{Theme, TodoView};
+    use serde_json::json;
+
+    #[test]
+    fn resolves_stored_preferences_over_defaults() {
+        let preferences = resolve_preferences(&json!({
+            "theme": "dark",
+            "timezone": "Europe/Amsterdam",
+            "removed_preference": true,
+            "default_todo_view": "unknown"
+        }));
+
+        assert_eq!(preferences.theme, Theme::Dark);
+        assert_eq!(preferences.timezone, "Europe/Amsterdam");
+        assert_eq!(preferences.default_todo_view, TodoView::All);
+        assert!(preferences.email_usage_alerts);
+        assert_eq!(resolve_preferences(&json!(null)), UserPreferences::default());
+    }
+
+    #[test]
+    fn validates_patches() {
+        let patch = parse_preferences_patch(&json!({ "theme": "light", "timezone": null })).unwrap();
+        assert_eq!(patch.set.get("theme"), Some(&json!("light")));
+        assert_eq!(patch.reset, vec!["timezone".to_string()]);
+
+        assert!(parse_preferences_patch(&json!({})).is_err());
+        assert!(parse_preferences_patch(&json!(["theme"])).is_err());
+        assert_eq!(parse_preferences_patch(&json!({ "colour": "red" })).unwrap_err(), "Unknown preference 'colour'.");
+        assert_eq!(parse_preferences_patch(&json!({ "theme": "pink" })).unwrap_err(), "Invalid value for preference 'theme'.");
+        assert_eq!(parse_preferences_patch(&json!({ "email_usage_alerts": "no" })).unwrap_err(), "Invalid value for preference 'email_usage_alerts'.");
+        assert_eq!(parse_preferences_patch(&json!({ "timezone": "Mars/Olympus" })).unwrap_err(), "Unknown time zone 'Mars/Olympus'.");
+        assert!(parse_preferences_patch(&json!({ "todo_reminder_lead_minutes": [1440, 60] })).is_ok());
+        assert!(parse_preferences_patch(&json!({ "todo_reminder_lead_minutes": [] })).is_ok());
+        assert!(parse_preferences_patch(&json!({ "todo_reminder_lead_minutes": [0] })).is_err());
+        assert!(parse_preferences_patch(&json!({ "todo_reminder_lead_minutes": [50000] })).is_err());
+        assert!(parse_preferences_patch(&json!({ "todo_reminder_lead_minutes": [1, 2, 3, 4, 5, 6] })).is_err());
+    }
+}
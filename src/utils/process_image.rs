@@ -4,31 +4,32 @@ use webp::Encoder;
 use std::time::Instant;
 use tokio::task;
 
-/// Processes an uploaded image by cropping, resizing, and converting to WebP format
-/// using optimized webp-encoder crate with multicore support.
-/// 
+/// Processes an uploaded image by cropping it to a square, resizing it to every requested size
+/// and converting the results to WebP format, using the optimized webp-encoder crate.
+///
+/// The image is decoded and cropped only once, the variants are resized from the cropped image.
+///
 /// # Arguments
 /// * `data` - Raw image bytes from the upload
-/// * `width` - Target output width in pixels
-/// * `height` - Target output height in pixels
+/// * `sizes` - Target widths and heights in pixels, one variant per size
 /// * `debug` - Optional debug flag to enable timing logs
-/// 
+///
 /// # Returns
-/// Result containing WebP-encoded bytes or error message
-/// 
+/// Result containing the WebP-encoded bytes per size or error message
+///
 /// # Example
 /// ```
-/// let processed = process_image(data, 300, 300, true).await?;
+/// let variants = process_image(data, &[64, 300], true).await?;
 /// ```
 pub async fn process_image(
     data: Bytes,
-    width: u32,
-    height: u32,
+    sizes: &[u32],
     debug: bool,
-) -> Result<Bytes, String> {
+) -> Result<Vec<(u32, Bytes)>, String> {
     let timer = Instant::now();
+    let sizes = sizes.to_vec();
 
-    let result: Result<Bytes, String> = task::spawn_blocking(move || {
+    let result: Result<Vec<(u32, Bytes)>, String> = task::spawn_blocking(move || {
         let stage_timer = if debug { Some(Instant::now()) } else { None };
 
         // Load image
@@ -45,33 +46,38 @@ pub async fn process_image(
             log_time("Square cropping", stage_timer.unwrap());
         }
 
-        // Resize
-        let stage_timer = debug.then(Instant::now);
-        let resized = cropped.resize_to_fill(
-            width,
-            height,
-            imageops::FilterType::Lanczos3,
-        );
-        if debug {
-            log_time("Image resizing", stage_timer.unwrap());
-        }
-
-        // Convert to RGB
-        let stage_timer = debug.then(Instant::now);
-        let rgb_img = resized.to_rgb8();
-        if debug {
-            log_time("RGB conversion", stage_timer.unwrap());
+        let mut variants = Vec::with_capacity(sizes.len());
+        for size in sizes {
+            // Resize
+            let stage_timer = debug.then(Instant::now);
+            let resized = cropped.resize_to_fill(
+                size,
+                size,
+                imageops::FilterType::Lanczos3,
+            );
+            if debug {
+                log_time(&format!("Image resizing ({size}px)"), stage_timer.unwrap());
+            }
+
+            // Convert to RGB
+            let stage_timer = debug.then(Instant::now);
+            let rgb_img = resized.to_rgb8();
+            if debug {
+                log_time(&format!("RGB conversion ({size}px)"), stage_timer.unwrap());
+            }
+
+            // WebP Encoding
+            let stage_timer = debug.then(Instant::now);
+            let encoder = Encoder::from_rgb(&rgb_img, size, size);
+            let webp_data = encoder.encode(60.0);
+            if debug {
+                log_time(&format!("WebP encoding ({size}px)"), stage_timer.unwrap());
+            }
+
+            variants.push((size, Bytes::copy_from_slice(&webp_data)));
         }
 
-        // WebP Encoding
-        let stage_timer = debug.then(Instant::now);
-        let encoder = Encoder::from_rgb(&rgb_img, width, height);
-        let webp_data = encoder.encode(60.0);
-        if debug {
-            log_time("WebP encoding", stage_timer.unwrap());
-        }
-
-        Ok::<Bytes, String>(Bytes::copy_from_slice(&webp_data))
+        Ok::<Vec<(u32, Bytes)>, String>(variants)
     })
     .await
     .map_err(|e| format!("Task execution failed: {e}"))?;
@@ -123,10 +129,13 @@ mod tests {
     #[tokio::test]
     async fn test_image_processing() {
         let data = test_image().await;
-        let result = process_image(data, 300, 300, true).await;
+        let result = process_image(data, &[64, 300], true).await;
         assert!(result.is_ok());
-        let webp = result.unwrap();
-        assert!(!webp.is_empty());
-        assert!(webp.len() < 100_000); // Should be <100KB for 300x300
+        let variants = result.unwrap();
+        assert_eq!(variants.iter().map(|(size, _)| *size).collect::<Vec<_>>(), vec![64, 300]);
+        for (_, webp) in &variants {
+            assert!(!webp.is_empty());
+            assert!(webp.len() < 100_000); // Should be <100KB for 300x300
+        }
     }
 }
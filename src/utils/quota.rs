@@ -0,0 +1,70 @@
+use chrono::{Datelike, NaiveDate};
+
+use crate::core::config::get_env_with_default;
+
+/// Returns the first day of the monthly quota period containing `date`, and the first day of the next period.
+pub fn quota_period(date: NaiveDate) -> (NaiveDate, NaiveDate) {
+    let start = NaiveDate::from_ymd_opt(date.year(), date.month(), 1)
+        .expect("The first day of a month is always valid.");
+    let reset = if date.month() == 12 {
+        NaiveDate::from_ymd_opt(date.year() + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(date.year(), date.month() + 1, 1)
+    }
+    .expect("The first day of a month is always valid.");
+
+    (start, reset)
+}
+
+/// Reads the quota thresholds (percentages) from `USAGE_QUOTA_THRESHOLDS`, e.g. "80,100".
+///
+/// Invalid entries are skipped. The result is sorted ascending without duplicates.
+pub fn quota_thresholds() -> Vec<i32> {
+    parse_thresholds(&get_env_with_default("USAGE_QUOTA_THRESHOLDS", "80,100"))
+}
+
+fn parse_thresholds(value: &str) -> Vec<i32> {
+    let mut thresholds: Vec<i32> = value
+        .split(',')
+        .filter_map(|t| t.trim().parse().ok())
+        .filter(|t| *t > 0)
+        .collect();
+    thresholds.sort_unstable();
+    thresholds.dedup();
+    thresholds
+}
+
+/// Returns the thresholds that have been reached by `count` out of `quota`, highest first.
+pub fn reached_thresholds(thresholds: &[i32], count: i64, quota: i64) -> Vec<i32> {
+    if quota <= 0 {
+        return Vec::new();
+    }
+    thresholds
+        .iter()
+        .rev()
+        .copied()
+        .filter(|t| count * 100 >= *t as i64 * quota)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quota_period() {
+        let date = NaiveDate::from_ymd_opt(2025, 12, 31).unwrap();
+        let (start, reset) = quota_period(date);
+        assert_eq!(start, NaiveDate::from_ymd_opt(2025, 12, 1).unwrap());
+        assert_eq!(reset, NaiveDate::from_ymd_opt(2026, 1, 1).unwrap());
+    }
+
+    #[test]
+    fn test_thresholds() {
+        let thresholds = parse_thresholds("100, 80,abc,80,-5");
+        assert_eq!(thresholds, vec![80, 100]);
+        assert_eq!(reached_thresholds(&thresholds, 799, 1000), Vec::<i32>::new());
+        assert_eq!(reached_thresholds(&thresholds, 800, 1000), vec![80]);
+        assert_eq!(reached_thresholds(&thresholds, 1200, 1000), vec![100, 80]);
+    }
+}
@@ -0,0 +1,541 @@
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Datelike, Duration, Months, NaiveDate, NaiveDateTime, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+use sqlx::PgPool;
+use tokio::task;
+use uuid::Uuid;
+
+use crate::database::todo_series::fetch_todo_series_by_id_from_db;
+use crate::database::todos::insert_todo_occurrence_into_db;
+use crate::models::todo::{Todo, TodoOccurrence, TodoSeries};
+
+
+/// Years before and after now that series can start in and occurrences can be asked about,
+/// which bounds how far a rule is expanded for a request.
+pub const RECURRENCE_WINDOW_YEARS: i64 = 10;
+
+/// Periods after which the expansion stops regardless, a daily rule runs for 400 years.
+const MAX_PERIODS: u32 = 146_097;
+
+/// Whether a moment is within `RECURRENCE_WINDOW_YEARS` of now.
+pub fn within_recurrence_window(moment: DateTime<Utc>) -> bool {
+    let window = Duration::days(RECURRENCE_WINDOW_YEARS * 366);
+    (moment - Utc::now()).abs() <= window
+}
+
+/// How often a rule repeats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// A recurrence rule, the subset of the RFC 5545 `RRULE` that todos support.
+///
+/// Supported parts are `FREQ` (DAILY, WEEKLY, MONTHLY or YEARLY), `INTERVAL`, `COUNT`,
+/// `UNTIL`, `BYDAY` (with ordinals like `-1FR` for MONTHLY and YEARLY), `BYMONTHDAY`,
+/// `BYMONTH` and `WKST=MO`. Other parts are rejected rather than silently ignored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurrenceRule {
+    pub frequency: Frequency,
+    pub interval: u32,
+    pub count: Option<u32>,
+    pub until: Option<DateTime<Utc>>,
+    pub by_day: Vec<(Option<i32>, Weekday)>,
+    pub by_month_day: Vec<i32>,
+    pub by_month: Vec<u32>,
+}
+
+impl RecurrenceRule {
+    /// Parses a rule like `FREQ=MONTHLY;BYDAY=-1FR`, optionally prefixed with `RRULE:`.
+    pub fn parse(rule: &str) -> Result<Self, String> {
+        let rule = rule.trim();
+        let rule = rule.strip_prefix("RRULE:").unwrap_or(rule);
+
+        let mut frequency = None;
+        let mut parsed = RecurrenceRule {
+            frequency: Frequency::Daily,
+            interval: 1,
+            count: None,
+            until: None,
+            by_day: Vec::new(),
+            by_month_day: Vec::new(),
+            by_month: Vec::new(),
+        };
+
+        for part in rule.split(';').filter(|part| !part.is_empty()) {
+            let (name, value) = part
+                .split_once('=')
+                .ok_or_else(|| format!("Invalid rule part '{}'.", part))?;
+            let invalid = || format!("Invalid value '{}' for {}.", value, name);
+
+            match name.to_uppercase().as_str() {
+                "FREQ" => {
+                    frequency = Some(match value.to_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        _ => return Err(format!("Unsupported frequency '{}'.", value)),
+                    })
+                }
+                "INTERVAL" => {
+                    parsed.interval = value.parse().ok().filter(|interval| *interval >= 1).ok_or_else(invalid)?
+                }
+                "COUNT" => parsed.count = Some(value.parse().ok().filter(|count| *count >= 1).ok_or_else(invalid)?),
+                "UNTIL" => parsed.until = Some(parse_until(value).ok_or_else(invalid)?),
+                "BYDAY" => {
+                    parsed.by_day = value.split(',').map(parse_weekday).collect::<Option<_>>().ok_or_else(invalid)?
+                }
+                "BYMONTHDAY" => {
+                    parsed.by_month_day = value
+                        .split(',')
+                        .map(|day| day.parse().ok().filter(|day: &i32| (1..=31).contains(&day.abs())))
+                        .collect::<Option<_>>()
+                        .ok_or_else(invalid)?
+                }
+                "BYMONTH" => {
+                    parsed.by_month = value
+                        .split(',')
+                        .map(|month| month.parse().ok().filter(|month| (1..=12).contains(month)))
+                        .collect::<Option<_>>()
+                        .ok_or_else(invalid)?
+                }
+                "WKST" if value.eq_ignore_ascii_case("MO") => {}
+                _ => return Err(format!("Unsupported rule part '{}'.", name)),
+            }
+        }
+
+        parsed.frequency = frequency.ok_or_else(|| "The rule needs a FREQ.".to_string())?;
+
+        if parsed.count.is_some() && parsed.until.is_some() {
+            return Err("COUNT and UNTIL cannot be combined.".to_string());
+        }
+        let has_ordinals = parsed.by_day.iter().any(|(ordinal, _)| ordinal.is_some());
+        if has_ordinals && !matches!(parsed.frequency, Frequency::Monthly | Frequency::Yearly) {
+            return Err("BYDAY ordinals are only supported with FREQ=MONTHLY or FREQ=YEARLY.".to_string());
+        }
+        if parsed.frequency == Frequency::Yearly && !parsed.by_day.is_empty() && parsed.by_month.is_empty() {
+            return Err("BYDAY with FREQ=YEARLY needs a BYMONTH.".to_string());
+        }
+
+        Ok(parsed)
+    }
+
+    /// All occurrences of the rule, in order, starting with `start` itself.
+    ///
+    /// The rule is expanded in the local time of `timezone`, so a daily 09:00 stays at 09:00
+    /// across daylight saving time changes.
+    pub fn occurrences(&self, start: DateTime<Utc>, timezone: Tz) -> Occurrences<'_> {
+        Occurrences {
+            rule: self,
+            timezone,
+            start: start.with_timezone(&timezone).naive_local(),
+            period: 0,
+            pending: VecDeque::from([start]),
+            emitted: 0,
+            empty_periods: 0,
+            done: false,
+        }
+    }
+
+    // The local dates of the occurrences in a period, before they are limited to the start
+    fn period_dates(&self, start: NaiveDate, period: u32) -> Vec<NaiveDate> {
+        // Periods beyond the supported dates have no occurrences
+        let Some(step) = period.checked_mul(self.interval) else {
+            return Vec::new();
+        };
+        let mut dates = match self.frequency {
+            Frequency::Daily => start
+                .checked_add_signed(Duration::days(step as i64))
+                .filter(|date| self.matches_limits(*date))
+                .into_iter()
+                .collect(),
+            Frequency::Weekly => {
+                let monday = start - Duration::days(start.weekday().num_days_from_monday() as i64);
+                let Some(week) = Duration::try_weeks(step as i64).and_then(|weeks| monday.checked_add_signed(weeks)) else {
+                    return Vec::new();
+                };
+                let weekdays = match self.by_day.is_empty() {
+                    true => vec![start.weekday()],
+                    false => self.by_day.iter().map(|(_, weekday)| *weekday).collect(),
+                };
+                weekdays
+                    .into_iter()
+                    .filter_map(|weekday| week.checked_add_signed(Duration::days(weekday.num_days_from_monday() as i64)))
+                    .filter(|date| self.by_month.is_empty() || self.by_month.contains(&date.month()))
+                    .collect()
+            }
+            Frequency::Monthly => {
+                match start.with_day(1).and_then(|first| first.checked_add_months(Months::new(step))) {
+                    Some(month) if self.by_month.is_empty() || self.by_month.contains(&month.month()) => {
+                        self.month_dates(month.year(), month.month(), start.day())
+                    }
+                    _ => Vec::new(),
+                }
+            }
+            Frequency::Yearly => {
+                let Some(year) = i32::try_from(step).ok().and_then(|step| start.year().checked_add(step)) else {
+                    return Vec::new();
+                };
+                let months = match self.by_month.is_empty() {
+                    true => vec![start.month()],
+                    false => self.by_month.clone(),
+                };
+                months
+                    .into_iter()
+                    .flat_map(|month| self.month_dates(year, month, start.day()))
+                    .collect()
+            }
+        };
+
+        dates.sort();
+        dates.dedup();
+        dates
+    }
+
+    // Periods in a row without any occurrence after which the rule never matches again, so rules
+    // like `FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=30` end. The calendar repeats every 400 years, so a
+    // rule that matches nothing during that many periods, whatever its interval, never will.
+    fn max_empty_periods(&self) -> u32 {
+        match self.frequency {
+            Frequency::Daily => 146_097,
+            Frequency::Weekly => 20_871,
+            Frequency::Monthly => 4_800,
+            Frequency::Yearly => 400,
+        }
+    }
+
+    // The dates within a month, from BYMONTHDAY and/or BYDAY, or the day of the start
+    fn month_dates(&self, year: i32, month: u32, start_day: u32) -> Vec<NaiveDate> {
+        let days_in_month = days_in_month(year, month);
+        let date = |day: u32| NaiveDate::from_ymd_opt(year, month, day);
+
+        if !self.by_month_day.is_empty() {
+            return self
+                .by_month_day
+                .iter()
+                .filter_map(|day| match *day > 0 {
+                    true => date(*day as u32),
+                    false => (days_in_month as i32 + 1 + day).try_into().ok().and_then(date),
+                })
+                .filter(|date| self.by_day.is_empty() || self.matches_by_day(*date))
+                .collect();
+        }
+
+        if !self.by_day.is_empty() {
+            return (1..=days_in_month)
+                .filter_map(date)
+                .filter(|date| self.matches_by_day(*date))
+                .collect();
+        }
+
+        // Months without the day of the start (e.g. the 31st) are skipped, as RFC 5545 prescribes
+        date(start_day).into_iter().collect()
+    }
+
+    // Whether a date matches BYDAY, ordinals count within the month
+    fn matches_by_day(&self, date: NaiveDate) -> bool {
+        let days_in_month = days_in_month(date.year(), date.month());
+        let from_start = (date.day() as i32 - 1) / 7 + 1;
+        let from_end = -((days_in_month as i32 - date.day() as i32) / 7 + 1);
+
+        self.by_day.iter().any(|(ordinal, weekday)| {
+            *weekday == date.weekday() && ordinal.is_none_or(|ordinal| ordinal == from_start || ordinal == from_end)
+        })
+    }
+
+    // BYMONTH, BYMONTHDAY and BYDAY limit the dates of a daily rule
+    fn matches_limits(&self, date: NaiveDate) -> bool {
+        let days_in_month = days_in_month(date.year(), date.month()) as i32;
+        (self.by_month.is_empty() || self.by_month.contains(&date.month()))
+            && (self.by_month_day.is_empty()
+                || self.by_month_day.iter().any(|day| {
+                    *day == date.day() as i32 || days_in_month + 1 + day == date.day() as i32
+                }))
+            && (self.by_day.is_empty() || self.by_day.iter().any(|(_, weekday)| *weekday == date.weekday()))
+    }
+}
+
+impl TodoSeries {
+    /// The time zone the rule is expanded in.
+    pub fn tz(&self) -> Tz {
+        self.timezone.parse().unwrap_or(Tz::UTC)
+    }
+
+    /// The upcoming occurrences after `after`, including skipped ones.
+    pub fn occurrences_after(&self, after: DateTime<Utc>, limit: usize) -> Vec<TodoOccurrence> {
+        let Ok(rule) = RecurrenceRule::parse(&self.rrule) else {
+            return Vec::new();
+        };
+
+        rule.occurrences(self.dtstart, self.tz())
+            .skip_while(|occurrence| *occurrence <= after)
+            .take(limit)
+            .map(|occurrence_at| TodoOccurrence { occurrence_at, skipped: self.skipped.contains(&occurrence_at) })
+            .collect()
+    }
+
+    /// Whether the rule of the series produces this exact occurrence.
+    pub fn has_occurrence(&self, occurrence_at: DateTime<Utc>) -> bool {
+        RecurrenceRule::parse(&self.rrule).is_ok_and(|rule| {
+            rule.occurrences(self.dtstart, self.tz())
+                .take_while(|occurrence| *occurrence <= occurrence_at)
+                .any(|occurrence| occurrence == occurrence_at)
+        })
+    }
+}
+
+/// Creates the first occurrence of a series after `after` that is not skipped, as a new todo.
+///
+/// Returns `None` when the series has ended or the occurrence already exists.
+pub async fn create_next_occurrence(
+    pool: &PgPool,
+    series_id: Uuid,
+    user_id: Uuid,
+    after: DateTime<Utc>,
+    previous_id: Option<Uuid>,
+) -> Result<Option<Todo>, sqlx::Error> {
+    let Some(series) = fetch_todo_series_by_id_from_db(pool, series_id, user_id).await? else {
+        return Ok(None);
+    };
+
+    let Ok(rule) = RecurrenceRule::parse(&series.rrule) else {
+        return Ok(None);
+    };
+
+    // Skipped occurrences still count towards COUNT, as EXDATE does in RFC 5545.
+    // Expanding a rule can take a while, so it stays off the async runtime.
+    let (series, next) = task::spawn_blocking(move || {
+        let next = rule
+            .occurrences(series.dtstart, series.tz())
+            .skip_while(|occurrence| *occurrence <= after)
+            .find(|occurrence| !series.skipped.contains(occurrence));
+        (series, next)
+    })
+    .await
+    .map_err(|e| sqlx::Error::Protocol(format!("Expanding the series failed: {}", e)))?;
+
+    match next {
+        Some(next) => insert_todo_occurrence_into_db(pool, &series, next, previous_id).await,
+        None => Ok(None),
+    }
+}
+
+/// Iterator over the occurrences of a rule, see [`RecurrenceRule::occurrences`].
+pub struct Occurrences<'a> {
+    rule: &'a RecurrenceRule,
+    timezone: Tz,
+    start: NaiveDateTime,
+    period: u32,
+    pending: VecDeque<DateTime<Utc>>,
+    emitted: u32,
+    empty_periods: u32,
+    done: bool,
+}
+
+impl Iterator for Occurrences<'_> {
+    type Item = DateTime<Utc>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            if let Some(occurrence) = self.pending.pop_front() {
+                let past_until = self.rule.until.is_some_and(|until| occurrence > until);
+                let past_count = self.rule.count.is_some_and(|count| self.emitted >= count);
+                if past_until || past_count {
+                    self.done = true;
+                    return None;
+                }
+                self.emitted += 1;
+                return Some(occurrence);
+            }
+
+            if self.period >= MAX_PERIODS {
+                self.done = true;
+                return None;
+            }
+            let dates = self.rule.period_dates(self.start.date(), self.period);
+            self.period += 1;
+
+            // The start is always the first occurrence, even when the rule itself does not match it
+            let start = self.start;
+            let timezone = self.timezone;
+            self.pending.extend(
+                dates
+                    .into_iter()
+                    .map(|date| date.and_time(start.time()))
+                    .filter(|local| *local > start)
+                    .filter_map(|local| to_utc(timezone, local)),
+            );
+
+            if self.pending.is_empty() {
+                self.empty_periods += 1;
+                self.done = self.empty_periods >= self.rule.max_empty_periods();
+            } else {
+                self.empty_periods = 0;
+            }
+        }
+        None
+    }
+}
+
+// Local times skipped by a daylight saving time change move forward by the size of the gap
+fn to_utc(timezone: Tz, local: NaiveDateTime) -> Option<DateTime<Utc>> {
+    timezone
+        .from_local_datetime(&local)
+        .earliest()
+        .or_else(|| timezone.from_local_datetime(&(local + Duration::hours(1))).earliest())
+        .map(|time| time.with_timezone(&Utc))
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    let first = NaiveDate::from_ymd_opt(year, month, 1).unwrap_or_default();
+    first
+        .checked_add_months(Months::new(1))
+        .map(|next| (next - first).num_days() as u32)
+        .unwrap_or(31)
+}
+
+// Parses weekdays with an optional ordinal, like "MO", "2TU" or "-1FR"
+fn parse_weekday(value: &str) -> Option<(Option<i32>, Weekday)> {
+    let value = value.trim().to_uppercase();
+    if value.len() < 2 {
+        return None;
+    }
+    let (ordinal, day) = value.split_at(value.len() - 2);
+    let weekday = match day {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        _ => return None,
+    };
+    let ordinal = match ordinal {
+        "" => None,
+        ordinal => Some(ordinal.parse::<i32>().ok().filter(|ordinal| (1..=5).contains(&ordinal.abs()))?),
+    };
+    Some((ordinal, weekday))
+}
+
+// UNTIL is either a UTC date-time ("20261231T235959Z") or a date, which includes that whole day
+fn parse_until(value: &str) -> Option<DateTime<Utc>> {
+    if let Ok(time) = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%SZ") {
+        return Some(time.and_utc());
+    }
+    NaiveDate::parse_from_str(value, "%Y%m%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(23, 59, 59))
+        .map(|time| time.and_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value).unwrap().with_timezone(&Utc)
+    }
+
+    fn expand(rule: &str, start: &str, timezone: Tz, amount: usize) -> Vec<String> {
+        RecurrenceRule::parse(rule)
+            .unwrap()
+            .occurrences(utc(start), timezone)
+            .take(amount)
+            .map(|occurrence| occurrence.to_rfc3339())
+            .collect()
+    }
+
+    #[test]
+    fn parses_supported_rules_only() {
+        let rule = RecurrenceRule::parse("RRULE:FREQ=MONTHLY;INTERVAL=2;BYDAY=-1FR;COUNT=5").unwrap();
+        assert_eq!(rule.frequency, Frequency::Monthly);
+        assert_eq!(rule.interval, 2);
+        assert_eq!(rule.by_day, vec![(Some(-1), Weekday::Fri)]);
+        assert_eq!(rule.count, Some(5));
+
+        assert!(RecurrenceRule::parse("INTERVAL=2").is_err());
+        assert!(RecurrenceRule::parse("FREQ=HOURLY").is_err());
+        assert!(RecurrenceRule::parse("FREQ=DAILY;BYSETPOS=1").is_err());
+        assert!(RecurrenceRule::parse("FREQ=WEEKLY;BYDAY=2MO").is_err());
+        assert!(RecurrenceRule::parse("FREQ=DAILY;COUNT=2;UNTIL=20270101").is_err());
+        assert!(RecurrenceRule::parse("FREQ=MONTHLY;BYMONTHDAY=32").is_err());
+    }
+
+    #[test]
+    fn expands_daily_and_weekly_rules() {
+        assert_eq!(
+            expand("FREQ=DAILY;INTERVAL=2;COUNT=3", "2026-10-19T09:00:00Z", Tz::UTC, 10),
+            vec!["2026-10-19T09:00:00+00:00", "2026-10-21T09:00:00+00:00", "2026-10-23T09:00:00+00:00"]
+        );
+        // Monday the 19th, on Mondays and Thursdays
+        assert_eq!(
+            expand("FREQ=WEEKLY;BYDAY=MO,TH", "2026-10-19T09:00:00Z", Tz::UTC, 4),
+            vec![
+                "2026-10-19T09:00:00+00:00",
+                "2026-10-22T09:00:00+00:00",
+                "2026-10-26T09:00:00+00:00",
+                "2026-10-29T09:00:00+00:00",
+            ]
+        );
+        assert_eq!(
+            expand("FREQ=DAILY;UNTIL=20261020", "2026-10-19T09:00:00Z", Tz::UTC, 10).len(),
+            2
+        );
+    }
+
+    #[test]
+    fn expands_monthly_and_yearly_rules() {
+        // The last Friday of every month
+        assert_eq!(
+            expand("FREQ=MONTHLY;BYDAY=-1FR", "2026-10-30T08:00:00Z", Tz::UTC, 3),
+            vec!["2026-10-30T08:00:00+00:00", "2026-11-27T08:00:00+00:00", "2026-12-25T08:00:00+00:00"]
+        );
+        // Months without a 31st are skipped
+        assert_eq!(
+            expand("FREQ=MONTHLY", "2026-10-31T08:00:00Z", Tz::UTC, 3),
+            vec!["2026-10-31T08:00:00+00:00", "2026-12-31T08:00:00+00:00", "2027-01-31T08:00:00+00:00"]
+        );
+        assert_eq!(
+            expand("FREQ=MONTHLY;BYMONTHDAY=-1", "2026-11-30T08:00:00Z", Tz::UTC, 3),
+            vec!["2026-11-30T08:00:00+00:00", "2026-12-31T08:00:00+00:00", "2027-01-31T08:00:00+00:00"]
+        );
+        assert_eq!(
+            expand("FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=29", "2028-02-29T08:00:00Z", Tz::UTC, 2),
+            vec!["2028-02-29T08:00:00+00:00", "2032-02-29T08:00:00+00:00"]
+        );
+        // Leap days are years apart, also across 2100, which is not a leap year
+        assert_eq!(
+            expand("FREQ=DAILY;BYMONTH=2;BYMONTHDAY=29", "2028-02-29T08:00:00Z", Tz::UTC, 3),
+            vec!["2028-02-29T08:00:00+00:00", "2032-02-29T08:00:00+00:00", "2036-02-29T08:00:00+00:00"]
+        );
+        assert_eq!(
+            expand("FREQ=DAILY;BYMONTH=2;BYMONTHDAY=29", "2096-02-29T08:00:00Z", Tz::UTC, 2),
+            vec!["2096-02-29T08:00:00+00:00", "2104-02-29T08:00:00+00:00"]
+        );
+        // Never matches again, but still ends
+        assert_eq!(expand("FREQ=DAILY;BYMONTH=2;BYMONTHDAY=30", "2026-10-19T08:00:00Z", Tz::UTC, 3).len(), 1);
+        // Rules without an end stop after a bounded amount of periods, wherever they start
+        let endless = RecurrenceRule::parse("FREQ=DAILY").unwrap();
+        assert_eq!(endless.occurrences(utc("0001-01-01T00:00:00Z"), Tz::UTC).count(), MAX_PERIODS as usize);
+        assert!(within_recurrence_window(Utc::now() + Duration::days(365)));
+        assert!(!within_recurrence_window(utc("9999-12-31T00:00:00Z")));
+        // Intervals that run past the supported dates end as well
+        assert_eq!(expand("FREQ=WEEKLY;INTERVAL=4000000000", "2026-10-19T08:00:00Z", Tz::UTC, 3).len(), 1);
+        assert_eq!(expand("FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=30", "2026-10-19T08:00:00Z", Tz::UTC, 3).len(), 1);
+    }
+
+    #[test]
+    fn keeps_the_local_time_across_daylight_saving_time() {
+        // Amsterdam switches from UTC+2 to UTC+1 on the 25th of October 2026
+        assert_eq!(
+            expand("FREQ=WEEKLY", "2026-10-19T07:00:00Z", Tz::Europe__Amsterdam, 2),
+            vec!["2026-10-19T07:00:00+00:00", "2026-10-26T08:00:00+00:00"]
+        );
+    }
+}
@@ -0,0 +1,49 @@
+use chrono::{DateTime, Duration, Utc};
+
+/// Maximum amount of reminders per todo a user can configure.
+pub const MAX_REMINDER_LEAD_TIMES: usize = 5;
+
+/// Maximum lead time of a reminder, in minutes (4 weeks).
+pub const MAX_REMINDER_LEAD_MINUTES: u32 = 4 * 7 * 24 * 60;
+
+/// Returns the lead times (in minutes) whose reminder is due at `now` and has not been claimed yet, longest first.
+///
+/// Reminders are only sent before the due date. A todo created shortly before its due date gets
+/// all lead times that already passed at once, which are sent as a single email.
+pub fn due_lead_minutes(lead_minutes: &[u32], due_at: DateTime<Utc>, now: DateTime<Utc>, claimed: &[i32]) -> Vec<i32> {
+    if due_at <= now {
+        return Vec::new();
+    }
+
+    let mut due: Vec<i32> = lead_minutes
+        .iter()
+        .filter(|minutes| **minutes > 0 && **minutes <= MAX_REMINDER_LEAD_MINUTES)
+        .map(|minutes| *minutes as i32)
+        .filter(|minutes| due_at - Duration::minutes(*minutes as i64) <= now)
+        .filter(|minutes| !claimed.contains(minutes))
+        .collect();
+    due.sort_unstable_by(|a, b| b.cmp(a));
+    due.dedup();
+    due
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn selects_due_lead_times() {
+        let due_at = Utc.with_ymd_and_hms(2026, 10, 20, 12, 0, 0).unwrap();
+        let at = |hour: u32, minute: u32| Utc.with_ymd_and_hms(2026, 10, 20, hour, minute, 0).unwrap();
+        let leads = [1440, 60, 60, 15];
+
+        assert_eq!(due_lead_minutes(&leads, due_at, at(10, 0), &[]), vec![1440]);
+        assert_eq!(due_lead_minutes(&leads, due_at, at(11, 0), &[]), vec![1440, 60]);
+        assert_eq!(due_lead_minutes(&leads, due_at, at(11, 0), &[1440]), vec![60]);
+        assert_eq!(due_lead_minutes(&leads, due_at, at(11, 50), &[1440, 60]), vec![15]);
+        assert!(due_lead_minutes(&leads, due_at, at(11, 50), &[1440, 60, 15]).is_empty());
+        assert!(due_lead_minutes(&leads, due_at, at(12, 0), &[]).is_empty());
+        assert!(due_lead_minutes(&[], due_at, at(11, 59), &[]).is_empty());
+    }
+}
@@ -0,0 +1,113 @@
+use validator::ValidationErrors;
+
+use crate::models::user_import::UserImportRow;
+
+/// Parses the body of an import into rows, based on its content type.
+///
+/// Every row is parsed on its own, so that a single malformed row does not reject the whole file.
+///
+/// # Returns
+/// - `Ok(rows)` with a result per row
+/// - `Err(String)` when the file as a whole cannot be read, or has more than `max_rows` rows
+pub fn parse_import_rows(
+    content_type: &str,
+    body: &[u8],
+    max_rows: usize,
+) -> Result<Vec<Result<UserImportRow, String>>, String> {
+    let mime = content_type.split(';').next().unwrap_or("").trim().to_ascii_lowercase();
+
+    let rows: Vec<Result<UserImportRow, String>> = match mime.as_str() {
+        "application/json" => {
+            let values: Vec<serde_json::Value> = serde_json::from_slice(body)
+                .map_err(|e| format!("Invalid JSON, expected an array of users: {}", e))?;
+            if values.len() > max_rows {
+                return Err(format!("An import can contain at most {} rows.", max_rows));
+            }
+            values
+                .into_iter()
+                .map(|value| serde_json::from_value(value).map_err(|e| e.to_string()))
+                .collect()
+        }
+        "text/csv" => {
+            let mut reader = csv::ReaderBuilder::new()
+                .trim(csv::Trim::All)
+                .from_reader(body);
+            let mut rows = Vec::new();
+            for record in reader.deserialize::<UserImportRow>() {
+                if rows.len() == max_rows {
+                    return Err(format!("An import can contain at most {} rows.", max_rows));
+                }
+                rows.push(record.map_err(|e| match e.kind() {
+                    csv::ErrorKind::Deserialize { err, .. } => err.to_string(),
+                    _ => e.to_string(),
+                }));
+            }
+            rows
+        }
+        _ => return Err("Unsupported content type, use text/csv or application/json.".to_string()),
+    };
+
+    if rows.is_empty() {
+        return Err("The import does not contain any rows.".to_string());
+    }
+
+    Ok(rows)
+}
+
+/// Flattens validation errors into readable messages, prefixed with the field they belong to.
+pub fn validation_messages(errors: &ValidationErrors) -> Vec<String> {
+    let mut messages: Vec<String> = errors
+        .field_errors()
+        .into_iter()
+        .flat_map(|(field, errors)| {
+            errors.iter().map(move |e| {
+                // Some validators only set a code, which then holds the message
+                let message = e.message.clone().unwrap_or_else(|| e.code.clone());
+                format!("{}: {}", field, message)
+            })
+        })
+        .collect();
+    messages.sort();
+    messages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_csv_rows_independently() {
+        let csv = "username,email,password,birthday\n\
+                   alice,alice@example.com,Secret123!,1990-01-01\n\
+                   bob,bob@example.com,,not-a-date\n\
+                   carol,carol@example.com,,\n";
+        let rows = parse_import_rows("text/csv; charset=utf-8", csv.as_bytes(), 10).unwrap();
+
+        assert_eq!(rows.len(), 3);
+        let alice = rows[0].as_ref().unwrap();
+        assert_eq!(alice.username, "alice");
+        assert_eq!(alice.password.as_deref(), Some("Secret123!"));
+        assert!(rows[1].is_err());
+        let carol = rows[2].as_ref().unwrap();
+        assert!(carol.password.is_none());
+        assert!(carol.birthday.is_none());
+    }
+
+    #[test]
+    fn parses_json_rows_independently() {
+        let json = r#"[{"username": "alice", "email": "alice@example.com"}, {"username": "bob"}]"#;
+        let rows = parse_import_rows("application/json", json.as_bytes(), 10).unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert!(rows[0].is_ok());
+        assert!(rows[1].as_ref().unwrap_err().contains("email"));
+    }
+
+    #[test]
+    fn rejects_invalid_files() {
+        assert!(parse_import_rows("text/plain", b"", 10).is_err());
+        assert!(parse_import_rows("application/json", b"{}", 10).is_err());
+        assert!(parse_import_rows("application/json", b"[]", 10).is_err());
+        assert!(parse_import_rows("text/csv", b"username,email\na,a@example.com\nb,b@example.com\n", 1).is_err());
+    }
+}
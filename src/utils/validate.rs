@@ -3,8 +3,20 @@ use validator::ValidationError;
 use regex::Regex;
 use lazy_static::lazy_static;
 
+use crate::core::config::get_env_with_default;
 use crate::referencedata::countries::countries;
 use crate::referencedata::languages::languages;
+use crate::utils::recurrence::RecurrenceRule;
+
+/// Usernames nobody can register, "current" would clash with the `/users/current` routes.
+const DEFAULT_RESERVED_USERNAMES: &str = "admin,administrator,root,support,system,security,staff,moderator,help,info,api,current,me,null,undefined";
+
+lazy_static! {
+    // Lowercased names from `RESERVED_USERNAMES`, read once
+    static ref RESERVED_USERNAMES: Vec<String> = parse_reserved_usernames(
+        &get_env_with_default("RESERVED_USERNAMES", DEFAULT_RESERVED_USERNAMES)
+    );
+}
 
 
 /// Validates that a date string is in the future
@@ -39,6 +51,7 @@ pub fn validate_future_date(date_str: &str) -> Result<(), ValidationError> {
 /// 
 /// Requirements:
 /// - Only alphanumeric, dash, and underscore characters
+/// - Not one of the reserved names in `RESERVED_USERNAMES` (case-insensitive)
 /// 
 /// # Arguments
 /// * `username` - String to validate
@@ -48,9 +61,25 @@ pub fn validate_username(username: &str) -> Result<(), ValidationError> {
     if !re.is_match(username) {
         return Err(ValidationError::new("Invalid username format. Only alphanumeric characters, dashes, and underscores are allowed."));
     }
+    if is_reserved_username(username, &RESERVED_USERNAMES) {
+        return Err(ValidationError::new("reserved_username").with_message("This username is reserved.".into()));
+    }
     Ok(())
 }
 
+/// Parses a comma separated list of reserved usernames.
+fn parse_reserved_usernames(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(|name| name.trim().to_lowercase())
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+/// Checks a username against the reserved names, ignoring case.
+fn is_reserved_username(username: &str, reserved: &[String]) -> bool {
+    reserved.contains(&username.to_lowercase())
+}
+
 /// Validates password complexity requirements
 /// 
 /// Requirements:
@@ -138,4 +167,57 @@ pub fn validate_language_code(code: &str) -> Result<(), ValidationError> {
         return Err(err);
     }
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Validates IANA time zone names (e.g. "Europe/Amsterdam")
+pub fn validate_timezone(timezone: &str) -> Result<(), ValidationError> {
+    if timezone.parse::<chrono_tz::Tz>().is_err() {
+        return Err(ValidationError::new("invalid_timezone")
+            .with_message(format!("Unknown time zone '{}'.", timezone).into()));
+    }
+    Ok(())
+}
+
+/// Validates the tags of a todo
+///
+/// Requirements:
+/// - At most 20 tags
+/// - Each tag is 1-30 characters after trimming
+pub fn validate_tags(tags: &[String]) -> Result<(), ValidationError> {
+    if tags.len() > 20 {
+        return Err(ValidationError::new("too_many_tags").with_message("A todo can have at most 20 tags.".into()));
+    }
+    if tags.iter().any(|tag| tag.trim().is_empty() || tag.trim().chars().count() > 30) {
+        return Err(ValidationError::new("invalid_tag").with_message("Tags must be 1 to 30 characters long.".into()));
+    }
+    Ok(())
+}
+
+/// Validates RFC 5545 recurrence rules, limited to the parts todos support
+pub fn validate_recurrence(rule: &str) -> Result<(), ValidationError> {
+    RecurrenceRule::parse(rule)
+        .map(|_| ())
+        .map_err(|e| ValidationError::new("invalid_recurrence").with_message(e.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_reserved_usernames_ignoring_case() {
+        let reserved = parse_reserved_usernames(" Admin, root,,support ");
+        assert_eq!(reserved, vec!["admin", "root", "support"]);
+        assert!(is_reserved_username("ADMIN", &reserved));
+        assert!(is_reserved_username("Support", &reserved));
+        assert!(!is_reserved_username("admin2", &reserved));
+    }
+
+    #[test]
+    fn validates_todo_tags() {
+        assert!(validate_tags(&["work".to_string(), " home ".to_string()]).is_ok());
+        assert!(validate_tags(&["  ".to_string()]).is_err());
+        assert!(validate_tags(&["a".repeat(31)]).is_err());
+        assert!(validate_tags(&vec!["tag".to_string(); 21]).is_err());
+    }
+}
@@ -9,6 +9,7 @@ use axum::{
 };
 use crate::routes::AppState;
 use crate::middlewares::auth::authorize;
+use crate::middlewares::throttle::throttle;
 use axum::middleware::from_fn_with_state;
 
 /// Builder for constructing routers with role-based authentication middleware.
@@ -184,6 +185,48 @@ impl AuthenticatedRouteBuilder {
         self
     }
 
+    // --- Throttled unauthenticated routes below ---
+
+    /// Add a GET route without authentication, throttled per client IP.
+    ///
+    /// `scope` names the counter, so that each route has its own limits.
+    #[allow(dead_code)]
+    pub fn throttled_get<H, T>(mut self, path: &str, handler: H, scope: &'static str) -> Self
+    where
+        H: axum::handler::Handler<T, Arc<AppState>> + Clone + Send + Sync + 'static,
+        T: 'static,
+    {
+        self.router = self.router.route(
+            path,
+            get(handler).layer(from_fn_with_state(
+                self.state.clone(),
+                move |State(state): State<Arc<AppState>>, req: Request<Body>, next: Next| {
+                    async move { throttle(scope, state, req, next).await }
+                },
+            )),
+        );
+        self
+    }
+
+    /// Add a POST route without authentication, throttled per client IP and per `email` in the JSON body.
+    #[allow(dead_code)]
+    pub fn throttled_post<H, T>(mut self, path: &str, handler: H, scope: &'static str) -> Self
+    where
+        H: axum::handler::Handler<T, Arc<AppState>> + Clone + Send + Sync + 'static,
+        T: 'static,
+    {
+        self.router = self.router.route(
+            path,
+            post(handler).layer(from_fn_with_state(
+                self.state.clone(),
+                move |State(state): State<Arc<AppState>>, req: Request<Body>, next: Next| {
+                    async move { throttle(scope, state, req, next).await }
+                },
+            )),
+        );
+        self
+    }
+
     /// Finalize the builder and return the constructed router.
     ///
     /// Note: The returned router still expects `Arc<AppState>` to be provided at the top level.